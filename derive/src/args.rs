@@ -19,6 +19,8 @@ pub struct CacheControl {
     private: bool,
     pub no_cache: bool,
     pub max_age: usize,
+    #[darling(multiple)]
+    pub vary: Vec<String>,
 }
 
 impl Default for CacheControl {
@@ -28,6 +30,7 @@ impl Default for CacheControl {
             private: false,
             no_cache: false,
             max_age: 0,
+            vary: Vec::new(),
         }
     }
 }
@@ -242,6 +245,7 @@ pub struct Argument {
     #[darling(multiple, rename = "tag")]
     pub tags: Vec<String>,
     pub secret: bool,
+    pub deprecation: Deprecation,
 }
 
 #[derive(FromMeta, Default)]
@@ -270,6 +274,7 @@ pub struct Object {
 pub enum ComplexityType {
     Const(usize),
     Fn(String),
+    Multiplier { arg: String, default: usize },
 }
 
 impl FromMeta for ComplexityType {
@@ -284,7 +289,27 @@ impl FromMeta for ComplexityType {
                 }
                 Ok(ComplexityType::Const(n as usize))
             }
-            Lit::Str(s) => Ok(ComplexityType::Fn(s.value())),
+            Lit::Str(s) => match s.value().strip_prefix("multiply:") {
+                Some(multiplier) => {
+                    let (arg, default) = match multiplier.split_once(':') {
+                        Some((arg, default)) => (
+                            arg,
+                            default.parse::<usize>().map_err(|_| {
+                                darling::Error::custom(format!(
+                                    "The default multiplier `{}` is not a valid integer.",
+                                    default
+                                ))
+                            })?,
+                        ),
+                        None => (multiplier, 1),
+                    };
+                    Ok(ComplexityType::Multiplier {
+                        arg: arg.to_string(),
+                        default,
+                    })
+                }
+                None => Ok(ComplexityType::Fn(s.value())),
+            },
             _ => Err(darling::Error::unexpected_lit_type(value)),
         }
     }
@@ -309,6 +334,8 @@ pub struct ObjectField {
     pub guard: Option<SpannedValue<String>>,
     pub visible: Option<Visible>,
     pub complexity: Option<ComplexityType>,
+    pub cost: Option<usize>,
+    pub serial: bool,
     #[darling(default, multiple)]
     pub derived: Vec<DerivedField>,
     pub flatten: bool,
@@ -366,6 +393,8 @@ pub struct EnumItem {
     pub inaccessible: bool,
     #[darling(default, multiple, rename = "tag")]
     pub tags: Vec<String>,
+    #[darling(default, multiple, rename = "directive")]
+    pub directives: Vec<SpannedValue<String>>,
 }
 
 #[derive(FromDeriveInput)]
@@ -396,6 +425,10 @@ pub struct UnionItem {
 
     #[darling(default)]
     pub flatten: bool,
+    #[darling(default)]
+    pub name: Option<String>,
+    #[darling(default, multiple, rename = "tag")]
+    pub tags: Vec<String>,
 }
 
 #[derive(FromField)]
@@ -433,6 +466,8 @@ pub struct InputObjectField {
     pub tags: Vec<String>,
     #[darling(default)]
     pub secret: bool,
+    #[darling(default)]
+    pub deprecation: Deprecation,
 }
 
 #[derive(FromDeriveInput)]
@@ -645,6 +680,7 @@ pub struct SubscriptionField {
     pub guard: Option<SpannedValue<String>>,
     pub visible: Option<Visible>,
     pub complexity: Option<ComplexityType>,
+    pub cost: Option<usize>,
 }
 
 #[derive(FromField)]
@@ -857,6 +893,8 @@ pub struct ComplexObjectField {
     pub guard: Option<SpannedValue<String>>,
     pub visible: Option<Visible>,
     pub complexity: Option<ComplexityType>,
+    pub cost: Option<usize>,
+    pub serial: bool,
     #[darling(multiple)]
     pub derived: Vec<DerivedField>,
     pub flatten: bool,
@@ -870,6 +908,7 @@ pub struct Directive {
     pub visible: Option<Visible>,
     pub repeatable: bool,
     pub rename_args: Option<RenameRule>,
+    pub deprecation: Deprecation,
     #[darling(multiple, rename = "location")]
     pub locations: Vec<DirectiveLocation>,
 }
@@ -6,8 +6,8 @@ use crate::{
     args,
     args::{Argument, RenameRuleExt, RenameTarget},
     utils::{
-        generate_default, get_crate_name, get_rustdoc, parse_graphql_attrs, remove_graphql_attrs,
-        visible_fn, GeneratorResult,
+        gen_deprecation, generate_default, get_crate_name, get_rustdoc, parse_graphql_attrs,
+        remove_graphql_attrs, visible_fn, GeneratorResult,
     },
 };
 
@@ -23,10 +23,11 @@ pub fn generate(
         .clone()
         .unwrap_or_else(|| item_fn.sig.ident.to_string());
     let desc = get_rustdoc(&item_fn.attrs)?
-        .map(|s| quote!(::std::option::Option::Some(#s)))
+        .map(|s| quote!(::std::option::Option::Some(::std::string::ToString::to_string(#s))))
         .unwrap_or_else(|| quote!(::std::option::Option::None));
     let visible = visible_fn(&directive_args.visible);
     let repeatable = directive_args.repeatable;
+    let deprecation = gen_deprecation(&directive_args.deprecation, &crate_name);
 
     let mut get_params = Vec::new();
     let mut use_params = Vec::new();
@@ -67,7 +68,7 @@ pub fn generate(
         });
         let desc = desc
             .as_ref()
-            .map(|s| quote! {::std::option::Option::Some(#s)})
+            .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
             .unwrap_or_else(|| quote! {::std::option::Option::None});
         let default = generate_default(&default, &default_with)?;
         let schema_default = default
@@ -84,10 +85,11 @@ pub fn generate(
 
         schema_args.push(quote! {
             args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
-                name: #name,
+                name: ::std::borrow::ToOwned::to_owned(#name),
                 description: #desc,
                 ty: <#arg_ty as #crate_name::InputType>::create_type_info(registry),
                 default_value: #schema_default,
+                deprecation: #crate_name::registry::Deprecation::NoDeprecated,
                 visible: #visible,
                 inaccessible: false,
                 tags: ::std::default::Default::default(),
@@ -156,6 +158,7 @@ pub fn generate(
                     },
                     is_repeatable: #repeatable,
                     visible: #visible,
+                    deprecation: #deprecation,
                 };
                 registry.add_directive(meta);
             }
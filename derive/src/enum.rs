@@ -5,7 +5,10 @@ use syn::{ext::IdentExt, Error};
 
 use crate::{
     args::{self, RenameRuleExt, RenameTarget},
-    utils::{gen_deprecation, get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
+    utils::{
+        gen_deprecation, gen_directive_invocations, get_crate_name, get_rustdoc, visible_fn,
+        GeneratorResult,
+    },
 };
 
 pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
@@ -24,7 +27,7 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
     let inaccessible = enum_args.inaccessible;
     let tags = &enum_args.tags;
     let desc = get_rustdoc(&enum_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
     let mut enum_items = Vec::new();
@@ -52,8 +55,9 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
         let inaccessible = variant.inaccessible;
         let tags = &variant.tags;
         let item_deprecation = gen_deprecation(&variant.deprecation, &crate_name);
+        let directive_invocations = gen_directive_invocations(&variant.directives, &crate_name)?;
         let item_desc = get_rustdoc(&variant.attrs)?
-            .map(|s| quote! { ::std::option::Option::Some(#s) })
+            .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
             .unwrap_or_else(|| quote! {::std::option::Option::None});
 
         enum_items.push(item_ident);
@@ -66,13 +70,14 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
 
         let visible = visible_fn(&variant.visible);
         schema_enum_items.push(quote! {
-            enum_items.insert(#gql_item_name, #crate_name::registry::MetaEnumValue {
-                name: #gql_item_name,
+            enum_items.insert(::std::borrow::ToOwned::to_owned(#gql_item_name), #crate_name::registry::MetaEnumValue {
+                name: ::std::borrow::ToOwned::to_owned(#gql_item_name),
                 description: #item_desc,
                 deprecation: #item_deprecation,
                 visible: #visible,
                 inaccessible: #inaccessible,
                 tags: &[ #(#tags),* ],
+                directive_invocations: #directive_invocations,
             });
         });
     }
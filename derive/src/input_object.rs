@@ -5,7 +5,9 @@ use syn::{ext::IdentExt, Error};
 
 use crate::{
     args::{self, RenameRuleExt, RenameTarget},
-    utils::{generate_default, get_crate_name, get_rustdoc, visible_fn, GeneratorResult},
+    utils::{
+        gen_deprecation, generate_default, get_crate_name, get_rustdoc, visible_fn, GeneratorResult,
+    },
 };
 
 pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream> {
@@ -49,7 +51,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
     let desc = get_rustdoc(&object_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
     let mut get_fields = Vec::new();
@@ -133,7 +135,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
         }
 
         let desc = get_rustdoc(&field.attrs)?
-            .map(|s| quote! { ::std::option::Option::Some(#s) })
+            .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
             .unwrap_or_else(|| quote! {::std::option::Option::None});
         let default = generate_default(&field.default, &field.default_with)?;
         let schema_default = default
@@ -185,12 +187,14 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
 
         fields.push(ident);
         let visible = visible_fn(&field.visible);
+        let deprecation = gen_deprecation(&field.deprecation, &crate_name);
         schema_fields.push(quote! {
             fields.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
-                name: #name,
+                name: ::std::borrow::ToOwned::to_owned(#name),
                 description: #desc,
                 ty: <#ty as #crate_name::InputType>::create_type_info(registry),
                 default_value: #schema_default,
+                deprecation: #deprecation,
                 visible: #visible,
                 inaccessible: #inaccessible,
                 tags: &[ #(#tags),* ],
@@ -39,7 +39,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
     let desc = get_rustdoc(&interface_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
     let mut registry_types = Vec::new();
@@ -215,7 +215,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
 
             let desc = desc
                 .as_ref()
-                .map(|s| quote! {::std::option::Option::Some(#s)})
+                .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
                 .unwrap_or_else(|| quote! {::std::option::Option::None});
             let schema_default = default
                 .as_ref()
@@ -230,10 +230,11 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             let visible = visible_fn(visible);
             schema_args.push(quote! {
                     args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
-                        name: #name,
+                        name: ::std::borrow::ToOwned::to_owned(#name),
                         description: #desc,
                         ty: <#ty as #crate_name::InputType>::create_type_info(registry),
                         default_value: #schema_default,
+                        deprecation: #crate_name::registry::Deprecation::NoDeprecated,
                         visible: #visible,
                         inaccessible: #inaccessible,
                         tags: &[ #(#tags),* ],
@@ -252,7 +253,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
 
         let desc = desc
             .as_ref()
-            .map(|s| quote! {::std::option::Option::Some(#s)})
+            .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
             .unwrap_or_else(|| quote! {::std::option::Option::None});
         let deprecation = gen_deprecation(deprecation, &crate_name);
 
@@ -285,6 +286,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                 ty: <#schema_ty as #crate_name::OutputType>::create_type_info(registry),
                 deprecation: #deprecation,
                 cache_control: ::std::default::Default::default(),
+                vary: &[],
                 external: #external,
                 provides: #provides,
                 requires: #requires,
@@ -294,6 +296,9 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                 override_from: #override_from,
                 visible: #visible,
                 compute_complexity: ::std::option::Option::None,
+                cost: ::std::option::Option::None,
+                serial: false,
+                directive_invocations: ::std::vec![],
             });
         });
 
@@ -380,6 +385,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                         inaccessible: #inaccessible,
                         tags: &[ #(#tags),* ],
                         rust_typename: ::std::any::type_name::<Self>(),
+                        directive_invocations: ::std::vec![],
                     }
                 })
             }
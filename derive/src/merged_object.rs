@@ -23,7 +23,7 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
     let desc = get_rustdoc(&object_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
     let s = match &object_args.data {
@@ -77,6 +77,10 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
             async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) ->  #crate_name::ServerResult<::std::option::Option<#crate_name::Value>> {
                #create_merged_obj.find_entity(ctx, params).await
             }
+
+            fn is_serial_field(&self, ctx: &#crate_name::ContextSelectionSet<'_>, name: &::std::primitive::str) -> bool {
+                #create_merged_obj.is_serial_field(ctx, name)
+            }
         }
 
         #[allow(clippy::all, clippy::pedantic)]
@@ -112,6 +116,7 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
                         keys: ::std::option::Option::None,
                         visible: #visible,
                         is_subscription: false,
+                        directive_invocations: ::std::vec![],
                         rust_typename: ::std::any::type_name::<Self>(),
                     }
                 })
@@ -19,7 +19,7 @@ pub fn generate(object_args: &args::MergedSubscription) -> GeneratorResult<Token
         .unwrap_or_else(|| RenameTarget::Type.rename(ident.to_string()));
 
     let desc = get_rustdoc(&object_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
     let s = match &object_args.data {
@@ -78,6 +78,7 @@ pub fn generate(object_args: &args::MergedSubscription) -> GeneratorResult<Token
                         inaccessible: false,
                         tags: ::std::default::Default::default(),
                         is_subscription: true,
+                        directive_invocations: ::std::vec![],
                         rust_typename: ::std::any::type_name::<Self>(),
                     }
                 })
@@ -20,7 +20,7 @@ pub fn generate(newtype_args: &args::NewType) -> GeneratorResult<TokenStream> {
         NewTypeName::Original => None,
     };
     let desc = get_rustdoc(&newtype_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
     let visible = visible_fn(&newtype_args.visible);
 
@@ -52,6 +52,11 @@ pub fn generate(newtype_args: &args::NewType) -> GeneratorResult<TokenStream> {
                 name: ::std::borrow::ToOwned::to_owned(#name),
                 description: #desc,
                 is_valid: |value| <#ident as #crate_name::ScalarType>::is_valid(value),
+                    round_trip: ::std::option::Option::Some(|value| {
+                        <#ident as #crate_name::ScalarType>::parse(value)
+                            .map(|value| <#ident as #crate_name::ScalarType>::to_value(&value))
+                            .map_err(|err| err.into_server_error(::std::default::Default::default()).message)
+                    }),
                 visible: #visible,
                 inaccessible: #inaccessible,
                 tags: &[ #(#tags),* ],
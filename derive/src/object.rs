@@ -12,9 +12,10 @@ use crate::{
     args::{self, ComplexityType, RenameRuleExt, RenameTarget},
     output_type::OutputType,
     utils::{
-        extract_input_args, gen_deprecation, generate_default, generate_guards, get_cfg_attrs,
-        get_crate_name, get_rustdoc, get_type_path_and_name, parse_complexity_expr,
-        parse_graphql_attrs, remove_graphql_attrs, visible_fn, GeneratorResult,
+        check_multiplier_arg, extract_input_args, gen_deprecation, gen_is_serial_field,
+        generate_default, generate_guards, get_cfg_attrs, get_crate_name, get_rustdoc,
+        get_type_path_and_name, parse_complexity_expr, parse_graphql_attrs, remove_graphql_attrs,
+        visible_fn, GeneratorResult,
     },
 };
 
@@ -43,15 +44,17 @@ pub fn generate(
     };
 
     let desc = if object_args.use_type_description {
-        quote! { ::std::option::Option::Some(<Self as #crate_name::Description>::description()) }
+        quote! { ::std::option::Option::Some(::std::string::ToString::to_string(<Self as #crate_name::Description>::description())) }
     } else {
         get_rustdoc(&item_impl.attrs)?
-            .map(|s| quote!(::std::option::Option::Some(#s)))
+            .map(|s| quote!(::std::option::Option::Some(::std::string::ToString::to_string(#s))))
             .unwrap_or_else(|| quote!(::std::option::Option::None))
     };
 
     let mut resolvers = Vec::new();
     let mut schema_fields = Vec::new();
+    let mut serial_fields = Vec::new();
+    let mut flattened_serial_fields = Vec::new();
     let mut find_entities = Vec::new();
     let mut add_keys = Vec::new();
     let mut create_entity_types = Vec::new();
@@ -289,6 +292,9 @@ pub fn generate(
                     let ty = ty.value_type();
                     let ident = &method.sig.ident;
 
+                    flattened_serial_fields.push(quote! {
+                        #crate_name::resolver_utils::is_registered_serial_field::<Self>(ctx, name)
+                    });
                     schema_fields.push(quote! {
                         <#ty>::create_type_info(registry);
                         if let #crate_name::registry::MetaType::Object { fields: obj_fields, .. } =
@@ -314,7 +320,7 @@ pub fn generate(
                         .rename(method.sig.ident.unraw().to_string(), RenameTarget::Field)
                 });
                 let field_desc = get_rustdoc(&method.attrs)?
-                    .map(|s| quote! { ::std::option::Option::Some(#s) })
+                    .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
                     .unwrap_or_else(|| quote! {::std::option::Option::None});
                 let field_deprecation = gen_deprecation(&method_args.deprecation, &crate_name);
                 let external = method_args.external;
@@ -333,6 +339,7 @@ pub fn generate(
                     Some(provides) => quote! { ::std::option::Option::Some(#provides) },
                     None => quote! { ::std::option::Option::None },
                 };
+                let vary = &method_args.cache_control.vary;
                 let cache_control = {
                     let public = method_args.cache_control.is_public();
                     let max_age = if method_args.cache_control.no_cache {
@@ -350,6 +357,7 @@ pub fn generate(
 
                 let args = extract_input_args::<args::Argument>(&crate_name, method)?;
                 let mut schema_args = Vec::new();
+                let mut arg_names = Vec::new();
                 let mut use_params = Vec::new();
                 let mut get_params = Vec::new();
 
@@ -367,6 +375,7 @@ pub fn generate(
                         secret,
                         inaccessible,
                         tags,
+                        deprecation,
                         ..
                     },
                 ) in &args
@@ -376,9 +385,10 @@ pub fn generate(
                             .rename_args
                             .rename(ident.ident.unraw().to_string(), RenameTarget::Argument)
                     });
+                    arg_names.push(name.clone());
                     let desc = desc
                         .as_ref()
-                        .map(|s| quote! {::std::option::Option::Some(#s)})
+                        .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
                         .unwrap_or_else(|| quote! {::std::option::Option::None});
                     let default = generate_default(default, default_with)?;
                     let schema_default = default
@@ -393,12 +403,14 @@ pub fn generate(
                         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
                     let visible = visible_fn(visible);
+                    let deprecation = gen_deprecation(deprecation, &crate_name);
                     schema_args.push(quote! {
                             args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
-                                name: #name,
+                                name: ::std::borrow::ToOwned::to_owned(#name),
                                 description: #desc,
                                 ty: <#ty as #crate_name::InputType>::create_type_info(registry),
                                 default_value: #schema_default,
+                                deprecation: #deprecation,
                                 visible: #visible,
                                 inaccessible: #inaccessible,
                                 tags: &[ #(#tags),* ],
@@ -458,11 +470,32 @@ pub fn generate(
                 let schema_ty = ty.value_type();
                 let visible = visible_fn(&method_args.visible);
 
-                let complexity = if let Some(complexity) = &method_args.complexity {
+                if let (Some(_), Some(_)) = (&method_args.complexity, &method_args.cost) {
+                    return Err(Error::new_spanned(
+                        &method.sig.ident,
+                        "The `complexity` and `cost` attributes cannot be used together.",
+                    )
+                    .into());
+                }
+                let cost = match method_args.cost {
+                    Some(cost) => quote! { ::std::option::Option::Some(#cost) },
+                    None => quote! { ::std::option::Option::None },
+                };
+                let serial = method_args.serial;
+                if serial {
+                    serial_fields.push(field_name.clone());
+                }
+                let complexity = if let Some(cost) = method_args.cost {
+                    quote! { ::std::option::Option::Some(#crate_name::registry::ComplexityType::Const(#cost)) }
+                } else if let Some(complexity) = &method_args.complexity {
                     match complexity {
                         ComplexityType::Const(n) => {
                             quote! { ::std::option::Option::Some(#crate_name::registry::ComplexityType::Const(#n)) }
                         }
+                        ComplexityType::Multiplier { arg, default } => {
+                            check_multiplier_arg(&method.sig.ident, arg, &arg_names)?;
+                            quote! { ::std::option::Option::Some(#crate_name::registry::ComplexityType::Multiplier { arg: #arg, default: #default }) }
+                        }
                         ComplexityType::Fn(s) => {
                             let (variables, expr) = parse_complexity_expr(s)?;
                             let mut parse_args = Vec::new();
@@ -522,6 +555,7 @@ pub fn generate(
                         ty: <#schema_ty as #crate_name::OutputType>::create_type_info(registry),
                         deprecation: #field_deprecation,
                         cache_control: #cache_control,
+                        vary: &[#(#vary),*],
                         external: #external,
                         provides: #provides,
                         requires: #requires,
@@ -531,6 +565,9 @@ pub fn generate(
                         override_from: #override_from,
                         visible: #visible,
                         compute_complexity: #complexity,
+                        cost: #cost,
+                        serial: #serial,
+                        directive_invocations: ::std::vec![],
                     });
                 });
 
@@ -585,6 +622,14 @@ pub fn generate(
         }
     }
 
+    if !object_args.cache_control.vary.is_empty() {
+        return Err(Error::new_spanned(
+            &self_ty,
+            "`vary` is only supported in the cache control of a field.",
+        )
+        .into());
+    }
+
     let cache_control = {
         let public = object_args.cache_control.is_public();
         let max_age = if object_args.cache_control.no_cache {
@@ -612,6 +657,8 @@ pub fn generate(
     }
 
     let visible = visible_fn(&object_args.visible);
+    let is_serial_field =
+        gen_is_serial_field(&crate_name, &serial_fields, &flattened_serial_fields);
     let resolve_container = if object_args.serial {
         quote! { #crate_name::resolver_utils::resolve_container_serial(ctx, self).await }
     } else {
@@ -646,6 +693,8 @@ pub fn generate(
                     #(#find_entities_iter)*
                     ::std::result::Result::Ok(::std::option::Option::None)
                 }
+
+                #is_serial_field
             }
 
             #[allow(clippy::all, clippy::pedantic)]
@@ -672,6 +721,7 @@ pub fn generate(
                         keys: ::std::option::Option::None,
                         visible: #visible,
                         is_subscription: false,
+                        directive_invocations: ::std::vec![],
                         rust_typename: ::std::any::type_name::<Self>(),
                     });
                     #(#create_entity_types)*
@@ -714,6 +764,7 @@ pub fn generate(
                         keys: ::std::option::Option::None,
                         visible: #visible,
                         is_subscription: false,
+                        directive_invocations: ::std::vec![],
                         rust_typename: ::std::any::type_name::<Self>(),
                     });
                     #(#create_entity_types)*
@@ -766,6 +817,8 @@ pub fn generate(
                     async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) -> #crate_name::ServerResult<::std::option::Option<#crate_name::Value>> {
                         self.__internal_find_entity(ctx, params).await
                     }
+
+                    #is_serial_field
                 }
 
                 #[#crate_name::async_trait::async_trait]
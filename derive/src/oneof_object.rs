@@ -14,7 +14,7 @@ pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream>
     let (impl_generics, ty_generics, where_clause) = object_args.generics.split_for_impl();
     let ident = &object_args.ident;
     let desc = get_rustdoc(&object_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
     let inaccessible = object_args.inaccessible;
     let tags = &object_args.tags;
@@ -46,7 +46,7 @@ pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream>
         let inaccessible = variant.inaccessible;
         let tags = &variant.tags;
         let desc = get_rustdoc(&object_args.attrs)?
-            .map(|s| quote! { ::std::option::Option::Some(#s) })
+            .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
             .unwrap_or_else(|| quote! {::std::option::Option::None});
         let ty = match variant.fields.style {
             Style::Tuple if variant.fields.fields.len() == 1 => &variant.fields.fields[0],
@@ -79,10 +79,11 @@ pub fn generate(object_args: &args::OneofObject) -> GeneratorResult<TokenStream>
 
             schema_fields.push(quote! {
                 fields.insert(::std::borrow::ToOwned::to_owned(#field_name), #crate_name::registry::MetaInputValue {
-                    name: #field_name,
+                    name: ::std::borrow::ToOwned::to_owned(#field_name),
                     description: #desc,
                     ty: <::std::option::Option<#ty> as #crate_name::InputType>::create_type_info(registry),
                     default_value: ::std::option::Option::None,
+                    deprecation: #crate_name::registry::Deprecation::NoDeprecated,
                     visible: #visible,
                     inaccessible: #inaccessible,
                     tags: &[ #(#tags),* ],
@@ -19,10 +19,10 @@ pub fn generate(
         .unwrap_or_else(|| RenameTarget::Type.rename(self_name.clone()));
 
     let desc = if scalar_args.use_type_description {
-        quote! { ::std::option::Option::Some(<Self as #crate_name::Description>::description()) }
+        quote! { ::std::option::Option::Some(::std::string::ToString::to_string(<Self as #crate_name::Description>::description())) }
     } else {
         get_rustdoc(&item_impl.attrs)?
-            .map(|s| quote!(::std::option::Option::Some(#s)))
+            .map(|s| quote!(::std::option::Option::Some(::std::string::ToString::to_string(#s))))
             .unwrap_or_else(|| quote!(::std::option::Option::None))
     };
 
@@ -53,6 +53,11 @@ pub fn generate(
                     name: ::std::borrow::ToOwned::to_owned(#gql_typename),
                     description: #desc,
                     is_valid: |value| <#self_ty as #crate_name::ScalarType>::is_valid(value),
+                    round_trip: ::std::option::Option::Some(|value| {
+                        <#self_ty as #crate_name::ScalarType>::parse(value)
+                            .map(|value| <#self_ty as #crate_name::ScalarType>::to_value(&value))
+                            .map_err(|err| err.into_server_error(::std::default::Default::default()).message)
+                    }),
                     visible: #visible,
                     inaccessible: #inaccessible,
                     tags: &[ #(#tags),* ],
@@ -85,6 +90,11 @@ pub fn generate(
                     name: ::std::borrow::ToOwned::to_owned(#gql_typename),
                     description: #desc,
                     is_valid: |value| <#self_ty as #crate_name::ScalarType>::is_valid(value),
+                    round_trip: ::std::option::Option::Some(|value| {
+                        <#self_ty as #crate_name::ScalarType>::parse(value)
+                            .map(|value| <#self_ty as #crate_name::ScalarType>::to_value(&value))
+                            .map_err(|err| err.into_server_error(::std::default::Default::default()).message)
+                    }),
                     visible: #visible,
                     inaccessible: #inaccessible,
                     tags: &[ #(#tags),* ],
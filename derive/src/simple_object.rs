@@ -8,7 +8,8 @@ use syn::{ext::IdentExt, visit::Visit, Error, Ident, LifetimeDef, Path, Type};
 use crate::{
     args::{self, RenameRuleExt, RenameTarget, SimpleObjectField},
     utils::{
-        gen_deprecation, generate_guards, get_crate_name, get_rustdoc, visible_fn, GeneratorResult,
+        gen_deprecation, gen_is_serial_field, generate_guards, get_crate_name, get_rustdoc,
+        visible_fn, GeneratorResult,
     },
 };
 
@@ -47,7 +48,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
     };
 
     let desc = get_rustdoc(&object_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
     let s = match &object_args.data {
@@ -63,6 +64,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
     let mut getters = Vec::new();
     let mut resolvers = Vec::new();
     let mut schema_fields = Vec::new();
+    let mut flattened_serial_fields = Vec::new();
 
     let mut processed_fields: Vec<SimpleObjectFieldGenerator> = vec![];
 
@@ -126,7 +128,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                 .rename(ident.unraw().to_string(), RenameTarget::Field)
         });
         let field_desc = get_rustdoc(&field.attrs)?
-            .map(|s| quote! {::std::option::Option::Some(#s)})
+            .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
             .unwrap_or_else(|| quote! {::std::option::Option::None});
         let field_deprecation = gen_deprecation(&field.deprecation, &crate_name);
         let external = field.external;
@@ -159,6 +161,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
             field.owned
         };
 
+        let vary = &field.cache_control.vary;
         let cache_control = {
             let public = field.cache_control.is_public();
             let max_age = if field.cache_control.no_cache {
@@ -185,6 +188,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                     ty: <#ty as #crate_name::OutputType>::create_type_info(registry),
                     deprecation: #field_deprecation,
                     cache_control: #cache_control,
+                    vary: &[#(#vary),*],
                     external: #external,
                     provides: #provides,
                     requires: #requires,
@@ -194,6 +198,9 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                     override_from: #override_from,
                     visible: #visible,
                     compute_complexity: ::std::option::Option::None,
+                    cost: ::std::option::Option::None,
+                    serial: false,
+                    directive_invocations: ::std::vec![],
                 });
             });
         } else {
@@ -261,6 +268,9 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                 }
             });
         } else {
+            flattened_serial_fields.push(quote! {
+                #crate_name::ContainerType::is_serial_field(&self.#ident, ctx, name)
+            });
             resolvers.push(quote! {
                 if let ::std::option::Option::Some(value) = #crate_name::ContainerType::resolve_field(&self.#ident, ctx).await? {
                     return ::std::result::Result::Ok(std::option::Option::Some(value));
@@ -277,6 +287,14 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         .into());
     }
 
+    if !object_args.cache_control.vary.is_empty() {
+        return Err(Error::new_spanned(
+            &ident,
+            "`vary` is only supported in the cache control of a field.",
+        )
+        .into());
+    }
+
     let cache_control = {
         let public = object_args.cache_control.is_public();
         let max_age = if object_args.cache_control.no_cache {
@@ -298,6 +316,9 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
     let mut complex_resolver = quote!();
 
     if object_args.complex {
+        flattened_serial_fields.push(quote! {
+            <Self as #crate_name::ComplexObject>::is_serial_field(self, ctx, name)
+        });
         concat_complex_fields = quote! {
             fields.extend(<Self as #crate_name::ComplexObject>::fields(registry));
         };
@@ -308,6 +329,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         };
     }
 
+    let is_serial_field = gen_is_serial_field(&crate_name, &[], &flattened_serial_fields);
     let resolve_container = if object_args.serial {
         quote! { #crate_name::resolver_utils::resolve_container_serial(ctx, self).await }
     } else {
@@ -330,6 +352,8 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                     #complex_resolver
                     ::std::result::Result::Ok(::std::option::Option::None)
                 }
+
+                #is_serial_field
             }
 
             #[allow(clippy::all, clippy::pedantic)]
@@ -357,6 +381,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                         keys: ::std::option::Option::None,
                         visible: #visible,
                         is_subscription: false,
+                        directive_invocations: ::std::vec![],
                         rust_typename: ::std::any::type_name::<Self>(),
                     })
                 }
@@ -424,6 +449,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                         keys: ::std::option::Option::None,
                         visible: #visible,
                         is_subscription: false,
+                        directive_invocations: ::std::vec![],
                         rust_typename: ::std::any::type_name::<Self>(),
                     })
                 }
@@ -448,6 +474,8 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                         #complex_resolver
                         self.__internal_resolve_field(ctx).await
                     }
+
+                    #is_serial_field
                 }
 
                 #[allow(clippy::all, clippy::pedantic)]
@@ -9,9 +9,10 @@ use crate::{
     args::{self, ComplexityType, RenameRuleExt, RenameTarget, SubscriptionField},
     output_type::OutputType,
     utils::{
-        extract_input_args, gen_deprecation, generate_default, generate_guards, get_cfg_attrs,
-        get_crate_name, get_rustdoc, get_type_path_and_name, parse_complexity_expr,
-        parse_graphql_attrs, remove_graphql_attrs, visible_fn, GeneratorResult,
+        check_multiplier_arg, extract_input_args, gen_deprecation, generate_default,
+        generate_guards, get_cfg_attrs, get_crate_name, get_rustdoc, get_type_path_and_name,
+        parse_complexity_expr, parse_graphql_attrs, remove_graphql_attrs, visible_fn,
+        GeneratorResult,
     },
 };
 
@@ -31,10 +32,10 @@ pub fn generate(
         .unwrap_or_else(|| RenameTarget::Type.rename(self_name.clone()));
 
     let desc = if subscription_args.use_type_description {
-        quote! { ::std::option::Option::Some(<Self as #crate_name::Description>::description()) }
+        quote! { ::std::option::Option::Some(::std::string::ToString::to_string(<Self as #crate_name::Description>::description())) }
     } else {
         get_rustdoc(&item_impl.attrs)?
-            .map(|s| quote!(::std::option::Option::Some(#s)))
+            .map(|s| quote!(::std::option::Option::Some(::std::string::ToString::to_string(#s))))
             .unwrap_or_else(|| quote!(::std::option::Option::None))
     };
 
@@ -56,7 +57,7 @@ pub fn generate(
                     .rename(method.sig.ident.unraw().to_string(), RenameTarget::Field)
             });
             let field_desc = get_rustdoc(&method.attrs)?
-                .map(|s| quote! {::std::option::Option::Some(#s)})
+                .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
                 .unwrap_or_else(|| quote! {::std::option::Option::None});
             let field_deprecation = gen_deprecation(&field.deprecation, &crate_name);
             let cfg_attrs = get_cfg_attrs(&method.attrs);
@@ -70,6 +71,7 @@ pub fn generate(
             }
 
             let mut schema_args = Vec::new();
+            let mut arg_names = Vec::new();
             let mut use_params = Vec::new();
             let mut get_params = Vec::new();
             let args = extract_input_args::<args::SubscriptionFieldArgument>(&crate_name, method)?;
@@ -94,9 +96,10 @@ pub fn generate(
                         .rename_args
                         .rename(ident.ident.unraw().to_string(), RenameTarget::Argument)
                 });
+                arg_names.push(name.clone());
                 let desc = desc
                     .as_ref()
-                    .map(|s| quote! {::std::option::Option::Some(#s)})
+                    .map(|s| quote! {::std::option::Option::Some(::std::string::ToString::to_string(#s))})
                     .unwrap_or_else(|| quote! {::std::option::Option::None});
                 let default = generate_default(default, default_with)?;
 
@@ -114,10 +117,11 @@ pub fn generate(
                 let visible = visible_fn(arg_visible);
                 schema_args.push(quote! {
                     args.insert(::std::borrow::ToOwned::to_owned(#name), #crate_name::registry::MetaInputValue {
-                            name: #name,
+                            name: ::std::borrow::ToOwned::to_owned(#name),
                             description: #desc,
                             ty: <#ty as #crate_name::InputType>::create_type_info(registry),
                             default_value: #schema_default,
+                            deprecation: #crate_name::registry::Deprecation::NoDeprecated,
                             visible: #visible,
                             inaccessible: false,
                             tags: ::std::default::Default::default(),
@@ -202,11 +206,28 @@ pub fn generate(
             }
 
             let visible = visible_fn(&field.visible);
-            let complexity = if let Some(complexity) = &field.complexity {
+            if let (Some(_), Some(_)) = (&field.complexity, &field.cost) {
+                return Err(Error::new_spanned(
+                    &method.sig.ident,
+                    "The `complexity` and `cost` attributes cannot be used together.",
+                )
+                .into());
+            }
+            let cost = match field.cost {
+                Some(cost) => quote! { ::std::option::Option::Some(#cost) },
+                None => quote! { ::std::option::Option::None },
+            };
+            let complexity = if let Some(cost) = field.cost {
+                quote! { ::std::option::Option::Some(#crate_name::registry::ComplexityType::Const(#cost)) }
+            } else if let Some(complexity) = &field.complexity {
                 match complexity {
                     ComplexityType::Const(n) => {
                         quote! { ::std::option::Option::Some(#crate_name::registry::ComplexityType::Const(#n)) }
                     }
+                    ComplexityType::Multiplier { arg, default } => {
+                        check_multiplier_arg(&method.sig.ident, arg, &arg_names)?;
+                        quote! { ::std::option::Option::Some(#crate_name::registry::ComplexityType::Multiplier { arg: #arg, default: #default }) }
+                    }
                     ComplexityType::Fn(s) => {
                         let (variables, expr) = parse_complexity_expr(s)?;
                         let mut parse_args = Vec::new();
@@ -266,6 +287,7 @@ pub fn generate(
                     ty: <<#stream_ty as #crate_name::futures_util::stream::Stream>::Item as #crate_name::OutputType>::create_type_info(registry),
                     deprecation: #field_deprecation,
                     cache_control: ::std::default::Default::default(),
+                    vary: &[],
                     external: false,
                     requires: ::std::option::Option::None,
                     provides: ::std::option::Option::None,
@@ -275,6 +297,9 @@ pub fn generate(
                     inaccessible: false,
                     tags: ::std::default::Default::default(),
                     compute_complexity: #complexity,
+                    cost: #cost,
+                    serial: false,
+                    directive_invocations: ::std::vec![],
                 });
             });
 
@@ -418,6 +443,7 @@ pub fn generate(
                     inaccessible: false,
                     tags: ::std::default::Default::default(),
                     is_subscription: true,
+                    directive_invocations: ::std::vec![],
                     rust_typename: ::std::any::type_name::<Self>(),
                 })
             }
@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use darling::ast::{Data, Style};
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{visit_mut::VisitMut, Error, Type};
+use syn::{parse_quote, visit::Visit, visit_mut::VisitMut, Error, Ident, Path, Type};
 
 use crate::{
     args::{self, RenameTarget},
@@ -13,7 +13,12 @@ use crate::{
 pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
     let crate_name = get_crate_name(union_args.internal);
     let ident = &union_args.ident;
-    let (impl_generics, ty_generics, where_clause) = union_args.generics.split_for_impl();
+    let type_params = union_args
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect::<HashSet<_>>();
+    let mut generics = union_args.generics.clone();
     let s = match &union_args.data {
         Data::Enum(s) => s,
         _ => {
@@ -22,7 +27,12 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
     };
     let mut enum_names = Vec::new();
     let mut enum_items = HashSet::new();
+    let mut member_names = HashSet::new();
+    let has_member_names = s.iter().any(|variant| variant.name.is_some());
+    let has_flatten = s.iter().any(|variant| variant.flatten);
     let mut type_into_impls = Vec::new();
+    let mut from_impls = Vec::new();
+    let mut try_from_impls = Vec::new();
     let gql_typename = union_args
         .name
         .clone()
@@ -31,7 +41,7 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
     let inaccessible = union_args.inaccessible;
     let tags = &union_args.tags;
     let desc = get_rustdoc(&union_args.attrs)?
-        .map(|s| quote! { ::std::option::Option::Some(#s) })
+        .map(|s| quote! { ::std::option::Option::Some(::std::string::ToString::to_string(#s)) })
         .unwrap_or_else(|| quote! {::std::option::Option::None});
 
     let mut registry_types = Vec::new();
@@ -70,6 +80,15 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
         }
 
         if matches!(ty, Type::Path(_) | Type::Macro(_)) {
+            let is_generic = uses_type_params(ty, &type_params);
+            if is_generic && variant.flatten {
+                return Err(Error::new_spanned(
+                    ty,
+                    "Type parameters cannot be used in a flatten variant",
+                )
+                .into());
+            }
+
             // This validates that the field type wasn't already used
             if !enum_items.insert(ty) {
                 return Err(Error::new_spanned(
@@ -79,52 +98,137 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
                 .into());
             }
 
+            if let Some(name) = &variant.name {
+                if variant.flatten {
+                    return Err(Error::new_spanned(
+                        enum_name,
+                        "The `name` attribute cannot be used with `flatten`",
+                    )
+                    .into());
+                }
+                if !member_names.insert(name) {
+                    return Err(Error::new_spanned(
+                        enum_name,
+                        format!(
+                            "The member name `{}` is already used by another variant",
+                            name
+                        ),
+                    )
+                    .into());
+                }
+            }
+
             enum_names.push(enum_name);
 
             let mut assert_ty = ty.clone();
             RemoveLifetime.visit_type_mut(&mut assert_ty);
 
-            if !variant.flatten {
+            if is_generic {
+                // The member type cannot be asserted statically, so bound it instead.
+                generics
+                    .make_where_clause()
+                    .predicates
+                    .push(parse_quote!(#ty: #crate_name::ObjectType));
+                // `From<T>` would conflict with the `From` impls of the other variants.
+                if !is_type_param(ty, &type_params) {
+                    from_impls.push((ty, enum_name));
+                    try_from_impls.push((ty, enum_name));
+                }
+            } else if !variant.flatten {
                 type_into_impls.push(quote! {
-                    #crate_name::static_assertions::assert_impl_one!(#assert_ty: #crate_name::ObjectType);
-
-                    #[allow(clippy::all, clippy::pedantic)]
-                    impl #impl_generics ::std::convert::From<#ty> for #ident #ty_generics #where_clause {
-                        fn from(obj: #ty) -> Self {
-                            #ident::#enum_name(obj)
-                        }
-                    }
+                    #crate_name::static_assertions::assert_impl_all!(#assert_ty: #crate_name::UnionMemberType);
                 });
+                from_impls.push((ty, enum_name));
+                try_from_impls.push((ty, enum_name));
             } else {
                 type_into_impls.push(quote! {
-                    #crate_name::static_assertions::assert_impl_one!(#assert_ty: #crate_name::UnionType);
-
-                    #[allow(clippy::all, clippy::pedantic)]
-                    impl #impl_generics ::std::convert::From<#ty> for #ident #ty_generics #where_clause {
-                        fn from(obj: #ty) -> Self {
-                            #ident::#enum_name(obj)
-                        }
-                    }
+                    #crate_name::static_assertions::assert_impl_any!(#assert_ty: #crate_name::UnionType, #crate_name::InterfaceType);
                 });
+                from_impls.push((ty, enum_name));
             }
 
             if !variant.flatten {
+                let member_name = match &variant.name {
+                    Some(name) => quote! { ::std::string::ToString::to_string(#name) },
+                    None => quote! { <#ty as #crate_name::OutputType>::type_name().into_owned() },
+                };
                 registry_types.push(quote! {
                     <#ty as #crate_name::OutputType>::create_type_info(registry);
                 });
-                possible_types.push(quote! {
-                    possible_types.insert(<#ty as #crate_name::OutputType>::type_name().into_owned());
-                });
+                let variant_tags = &variant.tags;
+                if !variant_tags.is_empty() {
+                    possible_types.push(quote! {
+                        possible_type_tags.insert(#member_name, ::std::vec![ #(#variant_tags),* ]);
+                    });
+                }
+                if has_member_names {
+                    possible_types.push(quote! {
+                        let member_name = #member_name;
+                        if !possible_types.insert(::std::clone::Clone::clone(&member_name)) {
+                            ::std::panic!(
+                                "The member `{}` is used by more than one variant of the union `{}`",
+                                member_name,
+                                #gql_typename,
+                            );
+                        }
+                    });
+                } else {
+                    possible_types.push(quote! {
+                        possible_types.insert(#member_name);
+                    });
+                }
             } else {
+                let variant_name = enum_name.to_string();
+                let variant_tags = &variant.tags;
+                let extend_tags = (!variant_tags.is_empty()).then(|| {
+                    quote! { member_tags.extend([ #(#variant_tags),* ]); }
+                });
                 possible_types.push(quote! {
-                    if let #crate_name::registry::MetaType::Union { possible_types: possible_types2, .. } =
-                        registry.create_fake_output_type::<#ty>() {
-                        possible_types.extend(possible_types2);
+                    let (possible_types2, possible_type_tags2) = match registry.create_fake_output_type::<#ty>() {
+                        #crate_name::registry::MetaType::Union { possible_types, possible_type_tags, .. } => {
+                            (possible_types, possible_type_tags)
+                        }
+                        #crate_name::registry::MetaType::Interface { possible_types, .. } => {
+                            (possible_types, ::std::default::Default::default())
+                        }
+                        ty => ::std::panic!(
+                            "The flattened variant `{}` of the union `{}` must be a union or an interface, but `{}` is a `{}`",
+                            #variant_name,
+                            #gql_typename,
+                            ty.name(),
+                            ty.type_id(),
+                        ),
+                    };
+                    for possible_type in possible_types2 {
+                        if let ::std::option::Option::Some(other_variant) = flattened_members
+                            .insert(::std::clone::Clone::clone(&possible_type), #variant_name)
+                        {
+                            ::std::panic!(
+                                "The possible type `{}` of the union `{}` is contributed by both the flattened variants `{}` and `{}`",
+                                possible_type,
+                                #gql_typename,
+                                other_variant,
+                                #variant_name,
+                            );
+                        }
+                        let mut member_tags = possible_type_tags2
+                            .get(&possible_type)
+                            .cloned()
+                            .unwrap_or_default();
+                        #extend_tags
+                        if !member_tags.is_empty() {
+                            possible_type_tags.insert(::std::clone::Clone::clone(&possible_type), member_tags);
+                        }
+                        possible_types.insert(possible_type);
                     }
                 });
             }
 
-            if !variant.flatten {
+            if let Some(name) = &variant.name {
+                get_introspection_typename.push(quote! {
+                    #ident::#enum_name(obj) => ::std::borrow::Cow::Borrowed(#name)
+                });
+            } else if !variant.flatten {
                 get_introspection_typename.push(quote! {
                     #ident::#enum_name(obj) => <#ty as #crate_name::OutputType>::type_name()
                 });
@@ -150,6 +254,46 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
         .into());
     }
 
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    for (ty, enum_name) in from_impls {
+        type_into_impls.push(quote! {
+            #[allow(clippy::all, clippy::pedantic)]
+            impl #impl_generics ::std::convert::From<#ty> for #ident #ty_generics #where_clause {
+                fn from(obj: #ty) -> Self {
+                    #ident::#enum_name(obj)
+                }
+            }
+        });
+    }
+
+    let mut ref_generics = generics.clone();
+    ref_generics.params.insert(0, parse_quote!('__union));
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+    for (ty, enum_name) in try_from_impls {
+        type_into_impls.push(quote! {
+            #[allow(clippy::all, clippy::pedantic)]
+            impl #ref_impl_generics ::std::convert::TryFrom<&'__union #ident #ty_generics> for &'__union #ty #where_clause {
+                type Error = &'__union #ident #ty_generics;
+
+                fn try_from(value: &'__union #ident #ty_generics) -> ::std::result::Result<Self, Self::Error> {
+                    #[allow(unreachable_patterns)]
+                    match value {
+                        #ident::#enum_name(obj) => ::std::result::Result::Ok(obj),
+                        _ => ::std::result::Result::Err(value),
+                    }
+                }
+            }
+        });
+    }
+
+    let flattened_members = if has_flatten {
+        Some(quote! {
+            let mut flattened_members = ::std::collections::HashMap::new();
+        })
+    } else {
+        None
+    };
+
     let visible = visible_fn(&union_args.visible);
     let expanded = quote! {
         #(#type_into_impls)*
@@ -186,14 +330,16 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
                 registry.create_output_type::<Self, _>(#crate_name::registry::MetaTypeId::Union, |registry| {
                     #(#registry_types)*
 
+                    let mut possible_types = #crate_name::indexmap::IndexSet::new();
+                    let mut possible_type_tags = #crate_name::indexmap::IndexMap::new();
+                    #flattened_members
+                    #(#possible_types)*
+
                     #crate_name::registry::MetaType::Union {
                         name: ::std::borrow::ToOwned::to_owned(#gql_typename),
                         description: #desc,
-                        possible_types: {
-                            let mut possible_types = #crate_name::indexmap::IndexSet::new();
-                            #(#possible_types)*
-                            possible_types
-                        },
+                        possible_types,
+                        possible_type_tags,
                         visible: #visible,
                         inaccessible: #inaccessible,
                         tags: &[ #(#tags),* ],
@@ -212,3 +358,41 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
 
     Ok(expanded.into())
 }
+
+/// Returns `true` if the type mentions one of the type parameters.
+fn uses_type_params(ty: &Type, type_params: &HashSet<&Ident>) -> bool {
+    struct UsesTypeParams<'a> {
+        type_params: &'a HashSet<&'a Ident>,
+        found: bool,
+    }
+
+    impl<'a, 'ast> Visit<'ast> for UsesTypeParams<'a> {
+        fn visit_path(&mut self, path: &'ast Path) {
+            if let Some(segment) = path.segments.first() {
+                if path.leading_colon.is_none() && self.type_params.contains(&segment.ident) {
+                    self.found = true;
+                }
+            }
+            syn::visit::visit_path(self, path);
+        }
+    }
+
+    let mut visitor = UsesTypeParams {
+        type_params,
+        found: false,
+    };
+    visitor.visit_type(ty);
+    visitor.found
+}
+
+/// Returns `true` if the type is one of the type parameters.
+fn is_type_param(ty: &Type, type_params: &HashSet<&Ident>) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .get_ident()
+            .map(|ident| type_params.contains(ident))
+            .unwrap_or_default(),
+        _ => false,
+    }
+}
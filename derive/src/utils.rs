@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use async_graphql_parser::types::{TypeKind, TypeSystemDefinition};
+use async_graphql_value::ConstValue;
 use darling::{util::SpannedValue, FromMeta};
 use proc_macro2::{Span, TokenStream, TokenTree};
 use proc_macro_crate::{crate_name, FoundCrate};
@@ -200,6 +202,50 @@ pub fn visible_fn(visible: &Option<Visible>) -> TokenStream {
     }
 }
 
+/// Generates `is_serial_field` for a container with the specified serial
+/// fields, `flattened` are the expressions deciding for flattened fields.
+///
+/// `Self` is bound by `OutputType`, so that flattened fields can be looked up
+/// in the registry.
+///
+/// Returns nothing if the container has neither, so that the default
+/// implementation is used.
+pub fn gen_is_serial_field(
+    crate_name: &TokenStream,
+    serial_fields: &[String],
+    flattened: &[TokenStream],
+) -> TokenStream {
+    if serial_fields.is_empty() && flattened.is_empty() {
+        return quote!();
+    }
+
+    let own = if serial_fields.is_empty() {
+        quote!(false)
+    } else {
+        quote!(::std::matches!(name, #(#serial_fields)|*))
+    };
+    quote! {
+        #[allow(unused_variables)]
+        fn is_serial_field(&self, ctx: &#crate_name::ContextSelectionSet<'_>, name: &::std::primitive::str) -> bool
+        where
+            Self: #crate_name::OutputType,
+        {
+            #own #(|| #flattened)*
+        }
+    }
+}
+
+pub fn check_multiplier_arg(ident: &Ident, arg: &str, arg_names: &[String]) -> GeneratorResult<()> {
+    if !arg_names.iter().any(|name| name == arg) {
+        return Err(Error::new_spanned(
+            ident,
+            format!("The multiplier argument `{}` does not exist.", arg),
+        )
+        .into());
+    }
+    Ok(())
+}
+
 pub fn parse_complexity_expr(s: &str) -> GeneratorResult<(HashSet<String>, Expr)> {
     #[derive(Default)]
     struct VisitComplexityExpr {
@@ -238,6 +284,98 @@ pub fn gen_deprecation(deprecation: &Deprecation, crate_name: &TokenStream) -> T
     }
 }
 
+/// Parses directive applications such as `@foo(bar: "baz")` and generates
+/// the corresponding `MetaDirectiveInvocation`s.
+pub fn gen_directive_invocations(
+    directives: &[SpannedValue<String>],
+    crate_name: &TokenStream,
+) -> GeneratorResult<TokenStream> {
+    let mut invocations = Vec::new();
+
+    for directive in directives {
+        let err = |message: String| Error::new(directive.span(), message);
+        let doc = async_graphql_parser::parse_schema(format!("scalar S {}", **directive))
+            .map_err(|e| err(format!("Invalid directive `{}`: {}", **directive, e)))?;
+        let directives = match doc.definitions.into_iter().next() {
+            Some(TypeSystemDefinition::Type(ty)) if matches!(ty.node.kind, TypeKind::Scalar) => {
+                ty.node.directives
+            }
+            _ => Vec::new(),
+        };
+        if directives.len() != 1 {
+            return Err(err(format!(
+                "Expected a single directive such as `@foo(bar: \"baz\")`, found `{}`",
+                **directive
+            ))
+            .into());
+        }
+
+        for directive in directives {
+            let name = directive.node.name.node.as_str();
+            let args = directive.node.arguments.iter().map(|(name, value)| {
+                let name = name.node.as_str();
+                let value = gen_const_value(&value.node, crate_name);
+                quote! {
+                    args.insert(::std::string::ToString::to_string(#name), #value);
+                }
+            });
+            invocations.push(quote! {
+                #crate_name::registry::MetaDirectiveInvocation {
+                    name: ::std::string::ToString::to_string(#name),
+                    args: {
+                        #[allow(unused_mut)]
+                        let mut args = #crate_name::indexmap::IndexMap::new();
+                        #(#args)*
+                        args
+                    },
+                }
+            });
+        }
+    }
+
+    Ok(quote! { ::std::vec![ #(#invocations),* ] })
+}
+
+fn gen_const_value(value: &ConstValue, crate_name: &TokenStream) -> TokenStream {
+    match value {
+        ConstValue::Null => quote! { #crate_name::Value::Null },
+        ConstValue::Number(n) => {
+            let n = n.to_string();
+            quote! {
+                #crate_name::Value::Number(::std::str::FromStr::from_str(#n).unwrap())
+            }
+        }
+        ConstValue::String(s) => {
+            quote! { #crate_name::Value::String(::std::string::ToString::to_string(#s)) }
+        }
+        ConstValue::Boolean(b) => quote! { #crate_name::Value::Boolean(#b) },
+        ConstValue::Binary(_) => unreachable!("binary values cannot be parsed"),
+        ConstValue::Enum(name) => {
+            let name = name.as_str();
+            quote! { #crate_name::Value::Enum(#crate_name::Name::new(#name)) }
+        }
+        ConstValue::List(items) => {
+            let items = items.iter().map(|item| gen_const_value(item, crate_name));
+            quote! { #crate_name::Value::List(::std::vec![ #(#items),* ]) }
+        }
+        ConstValue::Object(map) => {
+            let entries = map.iter().map(|(name, value)| {
+                let name = name.as_str();
+                let value = gen_const_value(value, crate_name);
+                quote! { map.insert(#crate_name::Name::new(#name), #value); }
+            });
+            quote! {
+                #crate_name::Value::Object({
+                    #[allow(unused_mut)]
+                    let mut map = #crate_name::indexmap::IndexMap::new();
+                    #(#entries)*
+                    map
+                })
+            }
+        }
+    }
+}
+
 pub fn extract_input_args<T: FromMeta + Default>(
     crate_name: &proc_macro2::TokenStream,
     method: &mut ImplItemMethod,
@@ -45,6 +45,7 @@ pub enum Rule {
   input_fields_definition,
   extend,
   directive_definition,
+  repeatable,
   directive_locations,
   directive_location,
   arguments_definition,
@@ -1105,6 +1106,8 @@ impl ::pest::Parser<Rule> for GraphQLParser {
                 .and_then(|state| super::hidden::skip(state))
                 .and_then(|state| state.optional(|state| self::arguments_definition(state)))
                 .and_then(|state| super::hidden::skip(state))
+                .and_then(|state| state.optional(|state| self::repeatable(state)))
+                .and_then(|state| super::hidden::skip(state))
                 .and_then(|state| state.match_string("on"))
                 .and_then(|state| super::hidden::skip(state))
                 .and_then(|state| self::directive_locations(state))
@@ -1113,6 +1116,13 @@ impl ::pest::Parser<Rule> for GraphQLParser {
         }
         #[inline]
         #[allow(non_snake_case, unused_variables)]
+        pub fn repeatable(
+          state: ::std::boxed::Box<::pest::ParserState<Rule>>,
+        ) -> ::pest::ParseResult<::std::boxed::Box<::pest::ParserState<Rule>>> {
+          state.rule(Rule::repeatable, |state| state.match_string("repeatable"))
+        }
+        #[inline]
+        #[allow(non_snake_case, unused_variables)]
         pub fn directive_locations(
           state: ::std::boxed::Box<::pest::ParserState<Rule>>,
         ) -> ::pest::ParseResult<::std::boxed::Box<::pest::ParserState<Rule>>> {
@@ -2098,6 +2108,7 @@ impl ::pest::Parser<Rule> for GraphQLParser {
       Rule::input_fields_definition => rules::input_fields_definition(state),
       Rule::extend => rules::extend(state),
       Rule::directive_definition => rules::directive_definition(state),
+      Rule::repeatable => rules::repeatable(state),
       Rule::directive_locations => rules::directive_locations(state),
       Rule::directive_location => rules::directive_location(state),
       Rule::arguments_definition => rules::arguments_definition(state),
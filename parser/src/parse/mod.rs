@@ -22,11 +22,13 @@ mod executable;
 mod generated;
 mod service;
 mod utils;
+mod value;
 
 use async_graphql_value::{ConstValue, Name, Number, Value};
 pub use executable::parse_query;
 use generated::Rule;
 pub use service::parse_schema;
+pub use value::parse_value_literal;
 
 struct GraphQLParser;
 
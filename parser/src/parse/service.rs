@@ -307,6 +307,7 @@ fn parse_directive_definition(
             .collect()
     })?
     .unwrap_or_default();
+    let is_repeatable = next_if_rule(&mut pairs, Rule::repeatable).is_some();
     let locations = {
         let pair = pairs.next().unwrap();
         debug_assert_eq!(pair.as_rule(), Rule::directive_locations);
@@ -350,6 +351,7 @@ fn parse_directive_definition(
             description,
             name,
             arguments,
+            is_repeatable,
             locations,
         },
         pos,
@@ -422,4 +424,21 @@ mod tests {
             parse_schema(fs::read_to_string(entry.path()).unwrap()).unwrap();
         }
     }
+
+    #[test]
+    fn test_repeatable_directive() {
+        let doc = parse_schema(
+            "directive @a on FIELD\ndirective @b(x: Int) repeatable on OBJECT | INTERFACE",
+        )
+        .unwrap();
+        let repeatable = doc
+            .definitions
+            .iter()
+            .map(|definition| match definition {
+                TypeSystemDefinition::Directive(directive) => directive.node.is_repeatable,
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(repeatable, vec![false, true]);
+    }
 }
@@ -0,0 +1,74 @@
+use pest::{
+    error::{Error as PestError, ErrorVariant},
+    Position,
+};
+
+use super::*;
+
+/// Parse a GraphQL constant value, such as the default value of an argument.
+///
+/// The value can be followed by whitespace, but not by anything else.
+///
+/// # Errors
+///
+/// Fails if the input is not a valid GraphQL constant value.
+pub fn parse_value_literal<T: AsRef<str>>(input: T) -> Result<ConstValue> {
+    let input = input.as_ref();
+    let mut pc = PositionCalculator::new(input);
+
+    let pair = exactly_one(GraphQLParser::parse(Rule::const_value, input)?);
+    let end = pair.as_span().end();
+    if !input[end..].chars().all(is_ignored) {
+        let pos = Position::new(input, end).unwrap();
+        return Err(PestError::<Rule>::new_from_pos(
+            ErrorVariant::CustomError {
+                message: "unexpected input after the value".to_string(),
+            },
+            pos,
+        )
+        .into());
+    }
+
+    Ok(parse_const_value(pair, &mut pc)?.node)
+}
+
+fn is_ignored(c: char) -> bool {
+    matches!(c, ' ' | ',' | '\t' | '\u{feff}' | '\n' | '\r')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_value_literal() {
+        assert_eq!(
+            parse_value_literal(r#"{a: [1, 2.5], b: "x", c: ENUM, d: null} "#).unwrap(),
+            ConstValue::Object(
+                [
+                    (
+                        Name::new("a"),
+                        ConstValue::List(vec![
+                            ConstValue::Number(1.into()),
+                            ConstValue::Number(Number::from_f64(2.5).unwrap()),
+                        ]),
+                    ),
+                    (Name::new("b"), ConstValue::String("x".to_string())),
+                    (Name::new("c"), ConstValue::Enum(Name::new("ENUM"))),
+                    (Name::new("d"), ConstValue::Null),
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+        assert_eq!(
+            parse_value_literal("true").unwrap(),
+            ConstValue::Boolean(true)
+        );
+
+        assert!(parse_value_literal("").is_err());
+        assert!(parse_value_literal("$var").is_err());
+        assert!(parse_value_literal("[1, 2").is_err());
+        assert!(parse_value_literal("1 2").is_err());
+    }
+}
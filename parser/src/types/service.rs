@@ -188,6 +188,9 @@ pub struct DirectiveDefinition {
     pub name: Positioned<Name>,
     /// The arguments of the directive.
     pub arguments: Vec<Positioned<InputValueDefinition>>,
+    /// Whether the directive can be applied more than once at the same
+    /// location.
+    pub is_repeatable: bool,
     /// The locations the directive applies to.
     pub locations: Vec<Positioned<DirectiveLocation>>,
 }
@@ -95,6 +95,10 @@ impl<T: OutputType + ?Sized> OutputType for &T {
         T::type_name()
     }
 
+    fn introspection_type_name(&self) -> Cow<'static, str> {
+        T::introspection_type_name(*self)
+    }
+
     fn create_type_info(registry: &mut Registry) -> String {
         T::create_type_info(registry)
     }
@@ -148,9 +152,29 @@ impl<T: ObjectType + ?Sized> ObjectType for Arc<T> {}
 /// A GraphQL interface.
 pub trait InterfaceType: ContainerType {}
 
+impl<T: InterfaceType + ?Sized> InterfaceType for &T {}
+
+impl<T: InterfaceType + ?Sized> InterfaceType for Box<T> {}
+
+impl<T: InterfaceType + ?Sized> InterfaceType for Arc<T> {}
+
 /// A GraphQL interface.
 pub trait UnionType: ContainerType {}
 
+impl<T: UnionType + ?Sized> UnionType for &T {}
+
+impl<T: UnionType + ?Sized> UnionType for Box<T> {}
+
+impl<T: UnionType + ?Sized> UnionType for Arc<T> {}
+
+/// Implemented by the types that can be members of a union, so that a variant
+/// of a union that is not an object is reported as not implementing
+/// `UnionMemberType`.
+#[doc(hidden)]
+pub trait UnionMemberType {}
+
+impl<T: ObjectType + ?Sized> UnionMemberType for T {}
+
 /// A GraphQL input object.
 pub trait InputObjectType: InputType {}
 
@@ -163,6 +187,10 @@ impl<T: OutputType + ?Sized> OutputType for Box<T> {
         T::type_name()
     }
 
+    fn introspection_type_name(&self) -> Cow<'static, str> {
+        T::introspection_type_name(&**self)
+    }
+
     fn create_type_info(registry: &mut Registry) -> String {
         T::create_type_info(registry)
     }
@@ -210,6 +238,10 @@ impl<T: OutputType + ?Sized> OutputType for Arc<T> {
         T::type_name()
     }
 
+    fn introspection_type_name(&self) -> Cow<'static, str> {
+        T::introspection_type_name(&**self)
+    }
+
     fn create_type_info(registry: &mut Registry) -> String {
         T::create_type_info(registry)
     }
@@ -256,4 +288,11 @@ pub trait ComplexObject {
     fn fields(registry: &mut registry::Registry) -> Vec<(String, registry::MetaField)>;
 
     async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<Value>>;
+
+    fn is_serial_field(&self, _ctx: &ContextSelectionSet<'_>, _name: &str) -> bool
+    where
+        Self: OutputType,
+    {
+        false
+    }
 }
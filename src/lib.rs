@@ -214,6 +214,8 @@ pub use async_graphql_value::{
 pub use async_stream;
 #[doc(hidden)]
 pub use async_trait;
+#[doc(hidden)]
+pub use base::UnionMemberType;
 pub use base::{
     ComplexObject, Description, InputObjectType, InputType, InterfaceType, ObjectType,
     OneofObjectType, OutputType, TypeName, UnionType,
@@ -235,7 +237,7 @@ pub use indexmap;
 pub use look_ahead::Lookahead;
 #[doc(no_inline)]
 pub use parser::{Pos, Positioned};
-pub use registry::{CacheControl, SDLExportOptions};
+pub use registry::{CacheControl, FederationVersion, SDLExportOptions};
 pub use request::{BatchRequest, Request};
 #[doc(no_inline)]
 pub use resolver_utils::{ContainerType, EnumType, ScalarType};
@@ -24,14 +24,19 @@ impl<'a> __Field<'a> {
 
     #[inline]
     async fn description(&self) -> Option<&str> {
-        self.field.description
+        self.field.description.as_deref()
     }
 
-    async fn args(&self, ctx: &Context<'_>) -> Vec<__InputValue<'a>> {
+    async fn args(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = true)] include_deprecated: bool,
+    ) -> Vec<__InputValue<'a>> {
         self.field
             .args
             .values()
             .filter(|input_value| is_visible(ctx, &input_value.visible))
+            .filter(|input_value| include_deprecated || !input_value.deprecation.is_deprecated())
             .map(|input_value| __InputValue {
                 registry: self.registry,
                 visible_types: self.visible_types,
@@ -15,12 +15,12 @@ pub struct __InputValue<'a> {
 impl<'a> __InputValue<'a> {
     #[inline]
     async fn name(&self) -> &str {
-        self.input_value.name
+        &self.input_value.name
     }
 
     #[inline]
     async fn description(&self) -> Option<&str> {
-        self.input_value.description
+        self.input_value.description.as_deref()
     }
 
     #[graphql(name = "type")]
@@ -33,4 +33,14 @@ impl<'a> __InputValue<'a> {
     async fn default_value(&self) -> Option<&str> {
         self.input_value.default_value.as_deref()
     }
+
+    #[inline]
+    async fn is_deprecated(&self) -> bool {
+        self.input_value.deprecation.is_deprecated()
+    }
+
+    #[inline]
+    async fn deprecation_reason(&self) -> Option<&str> {
+        self.input_value.deprecation.reason()
+    }
 }
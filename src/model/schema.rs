@@ -91,17 +91,13 @@ impl<'a> __Schema<'a> {
 
     /// A list of all directives supported by this server.
     async fn directives(&self) -> Vec<__Directive<'a>> {
-        let mut directives: Vec<_> = self
-            .registry
-            .directives
-            .values()
+        self.registry
+            .directives()
             .map(|directive| __Directive {
                 registry: self.registry,
                 visible_types: self.visible_types,
                 directive,
             })
-            .collect();
-        directives.sort_by(|a, b| a.directive.name.cmp(b.directive.name));
-        directives
+            .collect()
     }
 }
@@ -101,14 +101,7 @@ impl<'a> __Type<'a> {
     #[inline]
     async fn description(&self) -> Option<&str> {
         match &self.detail {
-            TypeDetail::Named(ty) => match ty {
-                registry::MetaType::Scalar { description, .. }
-                | registry::MetaType::Object { description, .. }
-                | registry::MetaType::Interface { description, .. }
-                | registry::MetaType::Union { description, .. }
-                | registry::MetaType::Enum { description, .. }
-                | registry::MetaType::InputObject { description, .. } => description.as_deref(),
-            },
+            TypeDetail::Named(ty) => ty.description(),
             TypeDetail::NonNull(_) => None,
             TypeDetail::List(_) => None,
         }
@@ -195,7 +188,11 @@ impl<'a> __Type<'a> {
         }
     }
 
-    async fn input_fields(&self, ctx: &Context<'_>) -> Option<Vec<__InputValue<'a>>> {
+    async fn input_fields(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = true)] include_deprecated: bool,
+    ) -> Option<Vec<__InputValue<'a>>> {
         if let TypeDetail::Named(registry::MetaType::InputObject { input_fields, .. }) =
             &self.detail
         {
@@ -203,6 +200,9 @@ impl<'a> __Type<'a> {
                 input_fields
                     .values()
                     .filter(|input_value| is_visible(ctx, &input_value.visible))
+                    .filter(|input_value| {
+                        include_deprecated || !input_value.deprecation.is_deprecated()
+                    })
                     .map(|input_value| __InputValue {
                         registry: self.registry,
                         visible_types: self.visible_types,
@@ -0,0 +1,375 @@
+use indexmap::{IndexMap, IndexSet};
+
+use crate::registry::{
+    is_builtin_scalar, Deprecation, MetaEnumValue, MetaField, MetaInputValue, MetaKey, MetaType,
+    MetaTypeName, Registry,
+};
+
+/// A builder for hand-written registries, mainly useful as test fixtures for
+/// registry algorithms.
+///
+/// Fields and arguments are added to the most recently added type or field.
+/// The built-in scalars referenced by the fields are registered
+/// automatically.
+///
+/// ```
+/// use async_graphql::registry::RegistryBuilder;
+///
+/// let registry = RegistryBuilder::new()
+///     .object("Query")
+///     .field("user", "User")
+///     .arg("id", "ID!")
+///     .object("User")
+///     .field("id", "ID!")
+///     .union("Media", &["Image", "Video"])
+///     .query_type("Query")
+///     .build();
+///
+/// assert!(registry.types.contains_key("ID"));
+/// ```
+#[derive(Default)]
+pub struct RegistryBuilder {
+    registry: Registry,
+    current_type: Option<String>,
+    current_field: Option<String>,
+}
+
+impl RegistryBuilder {
+    /// Create a `RegistryBuilder`
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn add_type(mut self, ty: MetaType) -> Self {
+        let name = ty.name().to_string();
+        self.registry.types.insert(name.clone(), ty);
+        self.current_type = Some(name);
+        self.current_field = None;
+        self
+    }
+
+    /// Add a scalar type.
+    #[must_use]
+    pub fn scalar(self, name: &str) -> Self {
+        self.add_type(MetaType::Scalar {
+            name: name.to_string(),
+            description: None,
+            is_valid: |_| true,
+            round_trip: None,
+            visible: None,
+            inaccessible: false,
+            tags: &[],
+            specified_by_url: None,
+        })
+    }
+
+    /// Add an object type.
+    #[must_use]
+    pub fn object(self, name: &str) -> Self {
+        self.add_type(MetaType::object(name).build())
+    }
+
+    /// Add an interface type.
+    #[must_use]
+    pub fn interface(self, name: &str) -> Self {
+        self.add_type(MetaType::Interface {
+            name: name.to_string(),
+            description: None,
+            fields: Default::default(),
+            possible_types: Default::default(),
+            extends: false,
+            keys: None,
+            visible: None,
+            inaccessible: false,
+            tags: &[],
+            rust_typename: "",
+            directive_invocations: Vec::new(),
+        })
+    }
+
+    /// Add a union type with the specified members.
+    #[must_use]
+    pub fn union(self, name: &str, possible_types: &[&str]) -> Self {
+        self.add_type(MetaType::Union {
+            name: name.to_string(),
+            description: None,
+            possible_types: possible_types.iter().map(ToString::to_string).collect(),
+            possible_type_tags: Default::default(),
+            visible: None,
+            inaccessible: false,
+            tags: &[],
+            rust_typename: "",
+        })
+    }
+
+    /// Add an enum type with the specified values.
+    #[must_use]
+    pub fn enum_type(self, name: &str, values: &[&str]) -> Self {
+        self.add_type(MetaType::Enum {
+            name: name.to_string(),
+            description: None,
+            enum_values: values
+                .iter()
+                .map(|value| {
+                    (
+                        value.to_string(),
+                        MetaEnumValue {
+                            name: value.to_string(),
+                            description: None,
+                            deprecation: Deprecation::NoDeprecated,
+                            visible: None,
+                            inaccessible: false,
+                            tags: &[],
+                            directive_invocations: Vec::new(),
+                        },
+                    )
+                })
+                .collect(),
+            visible: None,
+            inaccessible: false,
+            tags: &[],
+            rust_typename: "",
+        })
+    }
+
+    /// Add an input object type.
+    #[must_use]
+    pub fn input_object(self, name: &str) -> Self {
+        self.add_type(MetaType::InputObject {
+            name: name.to_string(),
+            description: None,
+            input_fields: Default::default(),
+            visible: None,
+            inaccessible: false,
+            tags: &[],
+            rust_typename: "",
+            oneof: false,
+        })
+    }
+
+    /// Add a field to the current object, interface or input object.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current type is not an object, an interface or an input
+    /// object.
+    #[must_use]
+    pub fn field(mut self, name: &str, ty: &str) -> Self {
+        match self.current_type_mut() {
+            MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => {
+                fields.insert(name.to_string(), MetaField::new(name, ty));
+                self.current_field = Some(name.to_string());
+            }
+            MetaType::InputObject { input_fields, .. } => {
+                input_fields.insert(name.to_string(), input_value(name, ty));
+            }
+            ty => panic!("Cannot add the field `{}` to `{}`", name, ty.name()),
+        }
+        self
+    }
+
+    /// Add an argument to the current field.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no field has been added to the current type.
+    #[must_use]
+    pub fn arg(mut self, name: &str, ty: &str) -> Self {
+        let field_name = self
+            .current_field
+            .clone()
+            .unwrap_or_else(|| panic!("Cannot add the argument `{}` without a field", name));
+        let field = match self.current_type_mut() {
+            MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => {
+                fields.get_mut(&field_name).unwrap()
+            }
+            _ => unreachable!(),
+        };
+        field.args.insert(name.to_string(), input_value(name, ty));
+        self
+    }
+
+    /// Declare that the current type implements the specified interface.
+    #[must_use]
+    pub fn implements(mut self, interface: &str) -> Self {
+        let name = self.current_type_mut().name().to_string();
+        self.registry.add_implements(&name, interface);
+        self
+    }
+
+    /// Set the query root type.
+    #[must_use]
+    pub fn query_type(mut self, name: &str) -> Self {
+        self.registry.query_type = name.to_string();
+        self
+    }
+
+    /// Set the mutation root type.
+    #[must_use]
+    pub fn mutation_type(mut self, name: &str) -> Self {
+        self.registry.mutation_type = Some(name.to_string());
+        self
+    }
+
+    /// Set the subscription root type.
+    #[must_use]
+    pub fn subscription_type(mut self, name: &str) -> Self {
+        self.registry.subscription_type = Some(name.to_string());
+        self
+    }
+
+    /// Build the registry.
+    pub fn build(mut self) -> Registry {
+        let registry = &mut self.registry;
+
+        for (name, interfaces) in &registry.implements {
+            for interface in interfaces {
+                if let Some(MetaType::Interface { possible_types, .. }) =
+                    registry.types.get_mut(interface)
+                {
+                    possible_types.insert(name.clone());
+                }
+            }
+        }
+
+        let mut scalars = IndexSet::new();
+        for ty in registry.types.values() {
+            let types = match ty {
+                MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => fields
+                    .values()
+                    .flat_map(|field| {
+                        Some(&field.ty)
+                            .into_iter()
+                            .chain(field.args.values().map(|arg| &arg.ty))
+                    })
+                    .collect::<Vec<_>>(),
+                MetaType::InputObject { input_fields, .. } => {
+                    input_fields.values().map(|field| &field.ty).collect()
+                }
+                _ => continue,
+            };
+            scalars.extend(
+                types
+                    .into_iter()
+                    .map(|ty| MetaTypeName::concrete_typename(ty))
+                    .filter(|name| is_builtin_scalar(name) && !registry.types.contains_key(*name))
+                    .map(ToString::to_string),
+            );
+        }
+
+        let mut builder = self;
+        for name in scalars {
+            builder = builder.scalar(&name);
+        }
+        builder.registry
+    }
+
+    fn current_type_mut(&mut self) -> &mut MetaType {
+        self.current_type
+            .as_ref()
+            .and_then(|name| self.registry.types.get_mut(name))
+            .expect("No type has been added")
+    }
+}
+
+fn input_value(name: &str, ty: &str) -> MetaInputValue {
+    MetaInputValue {
+        name: name.to_string(),
+        description: None,
+        ty: ty.to_string(),
+        default_value: None,
+        deprecation: Deprecation::NoDeprecated,
+        visible: None,
+        inaccessible: false,
+        tags: &[],
+        is_secret: false,
+    }
+}
+
+/// A builder for object types, created with [`MetaType::object`].
+///
+/// The properties that are not set by the builder have their default
+/// values, and can still be changed on the built [`MetaType`].
+///
+/// ```
+/// use async_graphql::registry::{MetaField, MetaKey, MetaType};
+///
+/// let ty = MetaType::object("User")
+///     .description("A user of the application.")
+///     .key("id")
+///     .field(MetaField::new("id", "ID!"))
+///     .field(MetaField::new("name", "String"))
+///     .build();
+///
+/// assert_eq!(ty.name(), "User");
+/// assert_eq!(ty.keys(), Some(&[MetaKey::new("id")][..]));
+/// assert_eq!(ty.field_by_name("name").unwrap().ty, "String");
+/// ```
+pub struct MetaTypeBuilder {
+    name: String,
+    description: Option<String>,
+    fields: IndexMap<String, MetaField>,
+    keys: Option<Vec<MetaKey>>,
+    rust_typename: &'static str,
+}
+
+impl MetaTypeBuilder {
+    pub(crate) fn new(name: String) -> Self {
+        Self {
+            name,
+            description: None,
+            fields: Default::default(),
+            keys: None,
+            rust_typename: "",
+        }
+    }
+
+    /// Set the description of the type.
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a field, replacing any field with the same name.
+    #[must_use]
+    pub fn field(mut self, field: MetaField) -> Self {
+        self.fields.insert(field.name.clone(), field);
+        self
+    }
+
+    /// Add a federation key, such as `id` or `id organization { id }`.
+    #[must_use]
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.keys
+            .get_or_insert_with(Vec::new)
+            .push(MetaKey::new(key));
+        self
+    }
+
+    /// Set the name of the Rust type, see [`MetaType::rust_typename`].
+    #[must_use]
+    pub fn rust_typename(mut self, rust_typename: &'static str) -> Self {
+        self.rust_typename = rust_typename;
+        self
+    }
+
+    /// Build the object type.
+    pub fn build(self) -> MetaType {
+        MetaType::Object {
+            name: self.name,
+            description: self.description,
+            fields: self.fields,
+            cache_control: Default::default(),
+            extends: false,
+            shareable: false,
+            keys: self.keys,
+            visible: None,
+            inaccessible: false,
+            tags: &[],
+            is_subscription: false,
+            rust_typename: self.rust_typename,
+            directive_invocations: Vec::new(),
+        }
+    }
+}
@@ -23,6 +23,11 @@
 ///     async fn value3(&self) -> i32 {
 ///         0
 ///     }
+///
+///     #[graphql(cache_control(max_age = 30, vary = "Authorization"))]
+///     async fn value4(&self) -> i32 {
+///         0
+///     }
 /// }
 ///
 /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
@@ -36,7 +41,7 @@
 ///         .cache_control,
 ///     CacheControl {
 ///         public: true,
-///         max_age: 30
+///         max_age: 30,
 ///     }
 /// );
 ///
@@ -49,7 +54,7 @@
 ///         .cache_control,
 ///     CacheControl {
 ///         public: false,
-///         max_age: 60
+///         max_age: 60,
 ///     }
 /// );
 ///
@@ -62,7 +67,7 @@
 ///         .cache_control,
 ///     CacheControl {
 ///         public: false,
-///         max_age: 30
+///         max_age: 30,
 ///     }
 /// );
 ///
@@ -75,9 +80,19 @@
 ///         .cache_control,
 ///     CacheControl {
 ///         public: false,
-///         max_age: -1
+///         max_age: -1,
 ///     }
 /// );
+///
+/// assert_eq!(
+///     schema
+///         .execute("{ value1 value4 }")
+///         .await
+///         .into_result()
+///         .unwrap()
+///         .vary,
+///     vec!["Authorization".to_string()]
+/// );
 /// # });
 /// ```
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -123,9 +138,50 @@ impl CacheControl {
             None
         }
     }
+
+    /// Get a complete 'Cache-Control' header value for an HTTP response.
+    ///
+    /// Unlike [`CacheControl::value`], the scope is always included, e.g.
+    /// `max-age=60, public`, and a response without a max age or with
+    /// `no_cache` is not stored at all, with `no-store`.
+    #[must_use]
+    pub fn to_http_header(&self) -> String {
+        if self.max_age <= 0 {
+            return "no-store".to_string();
+        }
+
+        let scope = if self.public { "public" } else { "private" };
+        format!("max-age={}, {}", self.max_age, scope)
+    }
 }
 
 impl CacheControl {
+    /// Combine two cache controls into the most restrictive one.
+    ///
+    /// The result is private if either is private. A `max_age` of `-1`
+    /// (`no-cache`) wins over any other value, then a `max_age` of `0`, which
+    /// means that the response must not be cached, and otherwise the minimum
+    /// max age is taken.
+    ///
+    /// Note that the default cache control has a `max_age` of `0`, so
+    /// combining with it disables caching.
+    #[must_use]
+    pub fn combine(self, other: CacheControl) -> CacheControl {
+        CacheControl {
+            public: self.public && other.public,
+            max_age: match (self.max_age, other.max_age) {
+                (-1, _) => -1,
+                (_, -1) => -1,
+                (a, b) => a.min(b),
+            },
+        }
+    }
+
+    /// Merge the cache control of a field with the cache control of the type
+    /// it returns, or of the fields of a response.
+    ///
+    /// Unlike [`CacheControl::combine`], a `max_age` of `0` means that the
+    /// field or type has no cache hint and is ignored.
     #[must_use]
     pub(crate) fn merge(self, other: &CacheControl) -> CacheControl {
         CacheControl {
@@ -201,4 +257,72 @@ mod tests {
             Some("no-cache, private".to_string())
         );
     }
+
+    #[test]
+    fn to_http_header() {
+        let cache_control = |public, max_age| CacheControl { public, max_age };
+
+        assert_eq!(
+            cache_control(true, 60).to_http_header(),
+            "max-age=60, public"
+        );
+        assert_eq!(
+            cache_control(false, 60).to_http_header(),
+            "max-age=60, private"
+        );
+        assert_eq!(cache_control(true, 0).to_http_header(), "no-store");
+        assert_eq!(cache_control(false, -1).to_http_header(), "no-store");
+    }
+
+    #[test]
+    fn combine() {
+        let cache_control = |public, max_age| CacheControl { public, max_age };
+
+        assert_eq!(
+            cache_control(true, 60).combine(cache_control(true, 30)),
+            cache_control(true, 30)
+        );
+        assert_eq!(
+            cache_control(true, 30).combine(cache_control(false, 60)),
+            cache_control(false, 30)
+        );
+        assert_eq!(
+            cache_control(true, 0).combine(cache_control(true, 60)),
+            cache_control(true, 0)
+        );
+        assert_eq!(
+            cache_control(true, 60).combine(cache_control(false, 0)),
+            cache_control(false, 0)
+        );
+        assert_eq!(
+            cache_control(true, 60).combine(CacheControl::default()),
+            CacheControl::default()
+        );
+        assert_eq!(
+            cache_control(true, -1).combine(cache_control(true, 60)),
+            cache_control(true, -1)
+        );
+        assert_eq!(
+            cache_control(false, 0).combine(cache_control(true, -1)),
+            cache_control(false, -1)
+        );
+        assert_eq!(
+            CacheControl::default().combine(CacheControl::default()),
+            CacheControl::default()
+        );
+
+        // `merge` ignores a max age of 0, which means no cache hint.
+        assert_eq!(
+            cache_control(true, 0).merge(&cache_control(true, 60)),
+            cache_control(true, 60)
+        );
+        assert_eq!(
+            cache_control(true, 60).merge(&cache_control(false, 0)),
+            cache_control(false, 60)
+        );
+        assert_eq!(
+            cache_control(true, 60).merge(&cache_control(true, -1)),
+            cache_control(true, -1)
+        );
+    }
 }
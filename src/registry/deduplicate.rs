@@ -0,0 +1,657 @@
+use std::collections::{HashMap, HashSet};
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::registry::{
+    stable_hash::StableHasher, ComplexityType, Deprecation, MetaDirective, MetaEnumValue,
+    MetaField, MetaInputValue, MetaKey, MetaType, MetaTypeId, MetaTypeName, MetaVisibleFn,
+    Registry,
+};
+
+/// Compares visibility functions by address.
+///
+/// The same function may have different addresses in different codegen units,
+/// so this can report two identical functions as different, but never two
+/// different functions as equal.
+pub(crate) fn visible_eq(a: Option<MetaVisibleFn>, b: Option<MetaVisibleFn>) -> bool {
+    a.map(|f| f as usize) == b.map(|f| f as usize)
+}
+
+fn input_value_eq(a: &MetaInputValue, b: &MetaInputValue) -> bool {
+    a.name == b.name
+        && a.description == b.description
+        && a.ty == b.ty
+        && a.default_value == b.default_value
+        && a.deprecation == b.deprecation
+        && visible_eq(a.visible, b.visible)
+        && a.inaccessible == b.inaccessible
+        && a.tags == b.tags
+        && a.is_secret == b.is_secret
+}
+
+pub(crate) fn field_eq(a: &MetaField, b: &MetaField) -> bool {
+    a.name == b.name
+        && a.description == b.description
+        && all_eq(a.args.values(), b.args.values(), input_value_eq)
+        && a.ty == b.ty
+        && a.deprecation == b.deprecation
+        && a.cache_control == b.cache_control
+        && a.vary == b.vary
+        && a.external == b.external
+        && a.requires == b.requires
+        && a.provides == b.provides
+        && visible_eq(a.visible, b.visible)
+        && a.shareable == b.shareable
+        && a.inaccessible == b.inaccessible
+        && a.tags == b.tags
+        && a.override_from == b.override_from
+        && a.cost == b.cost
+        && a.serial == b.serial
+        && a.directive_invocations == b.directive_invocations
+        && match (&a.compute_complexity, &b.compute_complexity) {
+            (None, None) => true,
+            (Some(ComplexityType::Const(a)), Some(ComplexityType::Const(b))) => a == b,
+            (
+                Some(ComplexityType::Multiplier {
+                    arg: a_arg,
+                    default: a_default,
+                }),
+                Some(ComplexityType::Multiplier {
+                    arg: b_arg,
+                    default: b_default,
+                }),
+            ) => a_arg == b_arg && a_default == b_default,
+            _ => false,
+        }
+}
+
+pub(crate) fn directive_eq(a: &MetaDirective, b: &MetaDirective) -> bool {
+    a.name == b.name
+        && a.description == b.description
+        && a.locations == b.locations
+        && all_eq(a.args.values(), b.args.values(), input_value_eq)
+        && a.is_repeatable == b.is_repeatable
+        && visible_eq(a.visible, b.visible)
+        && a.deprecation == b.deprecation
+}
+
+fn enum_value_eq(a: &MetaEnumValue, b: &MetaEnumValue) -> bool {
+    a.name == b.name
+        && a.description == b.description
+        && a.deprecation == b.deprecation
+        && visible_eq(a.visible, b.visible)
+        && a.inaccessible == b.inaccessible
+        && a.tags == b.tags
+        && a.directive_invocations == b.directive_invocations
+}
+
+fn all_eq<'a, T: 'a>(
+    a: impl ExactSizeIterator<Item = &'a T>,
+    b: impl ExactSizeIterator<Item = &'a T>,
+    eq: fn(&T, &T) -> bool,
+) -> bool {
+    a.len() == b.len() && a.zip(b).all(|(a, b)| eq(a, b))
+}
+
+impl MetaType {
+    /// Returns `true` if both types have the same kind and structure, ignoring
+    /// their names and Rust type names.
+    ///
+    /// Visibility functions are compared by address, so the same function in
+    /// different codegen units may still be considered different. Fields with
+    /// complexity functions are never considered structurally equal.
+    pub fn structurally_eq(&self, other: &MetaType) -> bool {
+        match (self, other) {
+            (
+                MetaType::Scalar {
+                    description: a_description,
+                    visible: a_visible,
+                    inaccessible: a_inaccessible,
+                    tags: a_tags,
+                    specified_by_url: a_specified_by_url,
+                    ..
+                },
+                MetaType::Scalar {
+                    description: b_description,
+                    visible: b_visible,
+                    inaccessible: b_inaccessible,
+                    tags: b_tags,
+                    specified_by_url: b_specified_by_url,
+                    ..
+                },
+            ) => {
+                a_description == b_description
+                    && visible_eq(*a_visible, *b_visible)
+                    && a_inaccessible == b_inaccessible
+                    && a_tags == b_tags
+                    && a_specified_by_url == b_specified_by_url
+            }
+            (
+                MetaType::Object {
+                    description: a_description,
+                    fields: a_fields,
+                    cache_control: a_cache_control,
+                    extends: a_extends,
+                    shareable: a_shareable,
+                    keys: a_keys,
+                    visible: a_visible,
+                    inaccessible: a_inaccessible,
+                    tags: a_tags,
+                    is_subscription: a_is_subscription,
+                    directive_invocations: a_directive_invocations,
+                    ..
+                },
+                MetaType::Object {
+                    description: b_description,
+                    fields: b_fields,
+                    cache_control: b_cache_control,
+                    extends: b_extends,
+                    shareable: b_shareable,
+                    keys: b_keys,
+                    visible: b_visible,
+                    inaccessible: b_inaccessible,
+                    tags: b_tags,
+                    is_subscription: b_is_subscription,
+                    directive_invocations: b_directive_invocations,
+                    ..
+                },
+            ) => {
+                a_description == b_description
+                    && all_eq(a_fields.values(), b_fields.values(), field_eq)
+                    && a_cache_control == b_cache_control
+                    && a_extends == b_extends
+                    && a_shareable == b_shareable
+                    && a_keys == b_keys
+                    && visible_eq(*a_visible, *b_visible)
+                    && a_inaccessible == b_inaccessible
+                    && a_tags == b_tags
+                    && a_is_subscription == b_is_subscription
+                    && a_directive_invocations == b_directive_invocations
+            }
+            (
+                MetaType::Interface {
+                    description: a_description,
+                    fields: a_fields,
+                    possible_types: a_possible_types,
+                    extends: a_extends,
+                    keys: a_keys,
+                    visible: a_visible,
+                    inaccessible: a_inaccessible,
+                    tags: a_tags,
+                    directive_invocations: a_directive_invocations,
+                    ..
+                },
+                MetaType::Interface {
+                    description: b_description,
+                    fields: b_fields,
+                    possible_types: b_possible_types,
+                    extends: b_extends,
+                    keys: b_keys,
+                    visible: b_visible,
+                    inaccessible: b_inaccessible,
+                    tags: b_tags,
+                    directive_invocations: b_directive_invocations,
+                    ..
+                },
+            ) => {
+                a_description == b_description
+                    && all_eq(a_fields.values(), b_fields.values(), field_eq)
+                    && a_possible_types == b_possible_types
+                    && a_extends == b_extends
+                    && a_keys == b_keys
+                    && visible_eq(*a_visible, *b_visible)
+                    && a_inaccessible == b_inaccessible
+                    && a_tags == b_tags
+                    && a_directive_invocations == b_directive_invocations
+            }
+            (
+                MetaType::Union {
+                    description: a_description,
+                    possible_types: a_possible_types,
+                    possible_type_tags: a_possible_type_tags,
+                    visible: a_visible,
+                    inaccessible: a_inaccessible,
+                    tags: a_tags,
+                    ..
+                },
+                MetaType::Union {
+                    description: b_description,
+                    possible_types: b_possible_types,
+                    possible_type_tags: b_possible_type_tags,
+                    visible: b_visible,
+                    inaccessible: b_inaccessible,
+                    tags: b_tags,
+                    ..
+                },
+            ) => {
+                a_description == b_description
+                    && a_possible_types == b_possible_types
+                    && a_possible_type_tags == b_possible_type_tags
+                    && visible_eq(*a_visible, *b_visible)
+                    && a_inaccessible == b_inaccessible
+                    && a_tags == b_tags
+            }
+            (
+                MetaType::Enum {
+                    description: a_description,
+                    enum_values: a_enum_values,
+                    visible: a_visible,
+                    inaccessible: a_inaccessible,
+                    tags: a_tags,
+                    ..
+                },
+                MetaType::Enum {
+                    description: b_description,
+                    enum_values: b_enum_values,
+                    visible: b_visible,
+                    inaccessible: b_inaccessible,
+                    tags: b_tags,
+                    ..
+                },
+            ) => {
+                a_description == b_description
+                    && all_eq(
+                        a_enum_values.values(),
+                        b_enum_values.values(),
+                        enum_value_eq,
+                    )
+                    && visible_eq(*a_visible, *b_visible)
+                    && a_inaccessible == b_inaccessible
+                    && a_tags == b_tags
+            }
+            (
+                MetaType::InputObject {
+                    description: a_description,
+                    input_fields: a_input_fields,
+                    visible: a_visible,
+                    inaccessible: a_inaccessible,
+                    tags: a_tags,
+                    oneof: a_oneof,
+                    ..
+                },
+                MetaType::InputObject {
+                    description: b_description,
+                    input_fields: b_input_fields,
+                    visible: b_visible,
+                    inaccessible: b_inaccessible,
+                    tags: b_tags,
+                    oneof: b_oneof,
+                    ..
+                },
+            ) => {
+                a_description == b_description
+                    && all_eq(
+                        a_input_fields.values(),
+                        b_input_fields.values(),
+                        input_value_eq,
+                    )
+                    && visible_eq(*a_visible, *b_visible)
+                    && a_inaccessible == b_inaccessible
+                    && a_tags == b_tags
+                    && a_oneof == b_oneof
+            }
+            _ => false,
+        }
+    }
+}
+
+fn hash_deprecation(deprecation: &Deprecation, hasher: &mut StableHasher) {
+    match deprecation {
+        Deprecation::NoDeprecated => hasher.write_u8(0),
+        Deprecation::Deprecated { reason } => {
+            hasher.write_u8(1);
+            hasher.write_opt_str(*reason);
+        }
+    }
+}
+
+fn hash_tags(tags: &[&str], hasher: &mut StableHasher) {
+    hasher.write_strs(tags.iter().copied());
+}
+
+fn hash_keys(keys: &Option<Vec<MetaKey>>, hasher: &mut StableHasher) {
+    match keys {
+        Some(keys) => {
+            hasher.write_u8(1);
+            hasher.write_len(keys.len());
+            for key in keys {
+                hasher.write_str(&key.fields);
+                hasher.write_bool(key.resolvable);
+            }
+        }
+        None => hasher.write_u8(0),
+    }
+}
+
+fn hash_input_value(value: &MetaInputValue, hasher: &mut StableHasher) {
+    hasher.write_str(&value.name);
+    hasher.write_str(&value.ty);
+    hasher.write_opt_str(value.default_value.as_deref());
+    hash_deprecation(&value.deprecation, hasher);
+    hasher.write_bool(value.inaccessible);
+    hash_tags(value.tags, hasher);
+}
+
+fn hash_field(field: &MetaField, hasher: &mut StableHasher) {
+    hasher.write_str(&field.name);
+    hasher.write_str(&field.ty);
+    hasher.write_len(field.args.len());
+    for arg in field.args.values() {
+        hash_input_value(arg, hasher);
+    }
+    hash_deprecation(&field.deprecation, hasher);
+    hasher.write_bool(field.inaccessible);
+    hash_tags(field.tags, hasher);
+}
+
+fn hash_fields<'a>(
+    fields: impl ExactSizeIterator<Item = &'a MetaField>,
+    hasher: &mut StableHasher,
+) {
+    hasher.write_len(fields.len());
+    for field in fields {
+        hash_field(field, hasher);
+    }
+}
+
+fn hash_possible_types(possible_types: &IndexSet<String>, hasher: &mut StableHasher) {
+    // Possible types are compared as sets.
+    let mut possible_types = possible_types
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    possible_types.sort_unstable();
+    hasher.write_strs(possible_types.into_iter());
+}
+
+impl MetaType {
+    /// Returns a fingerprint of the structure of this type, suitable for
+    /// content-addressed storage.
+    ///
+    /// The kind, the names, types, default values, deprecations and tags of
+    /// the members, the possible types and the keys participate in the
+    /// fingerprint. The name of the type itself, the descriptions, the Rust
+    /// type name and the functions are ignored, so structurally equal types
+    /// (see [`MetaType::structurally_eq`]) always have the same fingerprint.
+    ///
+    /// The fingerprint is a 64-bit FNV-1a hash over a canonical encoding of
+    /// these parts, so it is stable across builds and platforms and can be
+    /// persisted.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_u8(match self.type_id() {
+            MetaTypeId::Scalar => 0,
+            MetaTypeId::Object => 1,
+            MetaTypeId::Interface => 2,
+            MetaTypeId::Union => 3,
+            MetaTypeId::Enum => 4,
+            MetaTypeId::InputObject => 5,
+        });
+
+        match self {
+            MetaType::Scalar {
+                inaccessible,
+                tags,
+                specified_by_url,
+                ..
+            } => {
+                hasher.write_bool(*inaccessible);
+                hash_tags(tags, &mut hasher);
+                hasher.write_opt_str(*specified_by_url);
+            }
+            MetaType::Object {
+                fields,
+                keys,
+                inaccessible,
+                tags,
+                ..
+            } => {
+                hash_fields(fields.values(), &mut hasher);
+                hash_keys(keys, &mut hasher);
+                hasher.write_bool(*inaccessible);
+                hash_tags(tags, &mut hasher);
+            }
+            MetaType::Interface {
+                fields,
+                possible_types,
+                keys,
+                inaccessible,
+                tags,
+                ..
+            } => {
+                hash_fields(fields.values(), &mut hasher);
+                hash_possible_types(possible_types, &mut hasher);
+                hash_keys(keys, &mut hasher);
+                hasher.write_bool(*inaccessible);
+                hash_tags(tags, &mut hasher);
+            }
+            MetaType::Union {
+                possible_types,
+                inaccessible,
+                tags,
+                ..
+            } => {
+                hash_possible_types(possible_types, &mut hasher);
+                hasher.write_bool(*inaccessible);
+                hash_tags(tags, &mut hasher);
+            }
+            MetaType::Enum {
+                enum_values,
+                inaccessible,
+                tags,
+                ..
+            } => {
+                hasher.write_len(enum_values.len());
+                for value in enum_values.values() {
+                    hasher.write_str(&value.name);
+                    hash_deprecation(&value.deprecation, &mut hasher);
+                    hasher.write_bool(value.inaccessible);
+                    hash_tags(value.tags, &mut hasher);
+                }
+                hasher.write_bool(*inaccessible);
+                hash_tags(tags, &mut hasher);
+            }
+            MetaType::InputObject {
+                input_fields,
+                inaccessible,
+                tags,
+                oneof,
+                ..
+            } => {
+                hasher.write_len(input_fields.len());
+                for field in input_fields.values() {
+                    hash_input_value(field, &mut hasher);
+                }
+                hasher.write_bool(*inaccessible);
+                hash_tags(tags, &mut hasher);
+                hasher.write_bool(*oneof);
+            }
+        }
+
+        hasher.finish()
+    }
+}
+
+/// Rewrites the concrete type name of a (possibly wrapped) type reference such
+/// as `[Old!]!`, returning `None` if it is not renamed.
+fn rename_type_ref(ty: &str, renames: &HashMap<String, String>) -> Option<String> {
+    let concrete = MetaTypeName::concrete_typename(ty);
+    let new_name = renames.get(concrete)?;
+    let start = ty.len() - ty.trim_start_matches('[').len();
+    Some(format!(
+        "{}{}{}",
+        &ty[..start],
+        new_name,
+        &ty[start + concrete.len()..]
+    ))
+}
+
+fn rename_type_refs<'a>(
+    types: impl IntoIterator<Item = &'a mut String>,
+    renames: &HashMap<String, String>,
+) {
+    for ty in types {
+        if let Some(new_ty) = rename_type_ref(ty, renames) {
+            *ty = new_ty;
+        }
+    }
+}
+
+fn rename_possible_types(possible_types: &mut IndexSet<String>, renames: &HashMap<String, String>) {
+    *possible_types = possible_types
+        .iter()
+        .map(|name| renames.get(name).unwrap_or(name).clone())
+        .collect();
+}
+
+/// Rekeys the tags of the members of a union, merging the tags of members that
+/// are renamed to the same type.
+fn rename_possible_type_tags(
+    possible_type_tags: &mut IndexMap<String, Vec<&'static str>>,
+    renames: &HashMap<String, String>,
+) {
+    *possible_type_tags = std::mem::take(possible_type_tags).into_iter().fold(
+        IndexMap::new(),
+        |mut possible_type_tags, (name, tags)| {
+            let name = renames.get(&name).cloned().unwrap_or(name);
+            let member_tags = possible_type_tags.entry(name).or_default();
+            for tag in tags {
+                if !member_tags.contains(&tag) {
+                    member_tags.push(tag);
+                }
+            }
+            possible_type_tags
+        },
+    );
+}
+
+impl Registry {
+    /// Rewrites every reference to the renamed types. The renamed types
+    /// themselves are left untouched.
+    pub(crate) fn rename_type_references(&mut self, renames: &HashMap<String, String>) {
+        for ty in self.types.values_mut() {
+            match ty {
+                MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => {
+                    for field in fields.values_mut() {
+                        rename_type_refs(
+                            Some(&mut field.ty)
+                                .into_iter()
+                                .chain(field.args.values_mut().map(|arg| &mut arg.ty)),
+                            renames,
+                        );
+                    }
+                }
+                MetaType::InputObject { input_fields, .. } => rename_type_refs(
+                    input_fields.values_mut().map(|field| &mut field.ty),
+                    renames,
+                ),
+                _ => {}
+            }
+
+            if let MetaType::Interface { possible_types, .. }
+            | MetaType::Union { possible_types, .. } = ty
+            {
+                rename_possible_types(possible_types, renames);
+            }
+            if let MetaType::Union {
+                possible_type_tags, ..
+            } = ty
+            {
+                rename_possible_type_tags(possible_type_tags, renames);
+            }
+        }
+
+        for directive in self.directives.values_mut() {
+            rename_type_refs(directive.args.values_mut().map(|arg| &mut arg.ty), renames);
+        }
+
+        self.implements = std::mem::take(&mut self.implements).into_iter().fold(
+            HashMap::new(),
+            |mut implements, (name, interfaces)| {
+                let name = renames.get(&name).cloned().unwrap_or(name);
+                implements.entry(name).or_default().extend(
+                    interfaces
+                        .into_iter()
+                        .map(|name| renames.get(&name).cloned().unwrap_or(name)),
+                );
+                implements
+            },
+        );
+
+        for (old_name, new_name) in renames {
+            if self.reference_stubs.remove(old_name) {
+                self.reference_stubs.insert(new_name.clone());
+            }
+        }
+    }
+
+    /// Merges groups of structurally identical types (see
+    /// [`MetaType::structurally_eq`]) into a single type.
+    ///
+    /// The alphabetically first type of each group is kept, all references to
+    /// the other types are rewritten to it and the other types are removed.
+    /// Scalars, introspection types, federation types and the root types are
+    /// never merged.
+    ///
+    /// Returns the performed merges as `(removed, kept)` pairs.
+    pub fn deduplicate_identical_types(&mut self) -> Vec<(String, String)> {
+        let mut merges = Vec::new();
+
+        // Merging types can make the types referencing them identical, so repeat
+        // until nothing changes.
+        loop {
+            let candidates = self
+                .types
+                .values()
+                .filter(|ty| {
+                    let name = ty.name();
+                    !matches!(ty, MetaType::Scalar { .. })
+                        && !name.starts_with("__")
+                        && !matches!(name, "_Any" | "_Entity" | "_Service")
+                        && name != self.query_type
+                        && Some(name) != self.mutation_type.as_deref()
+                        && Some(name) != self.subscription_type.as_deref()
+                })
+                .collect::<Vec<_>>();
+
+            let fingerprints = candidates
+                .iter()
+                .map(|ty| ty.fingerprint())
+                .collect::<Vec<_>>();
+
+            let mut renames = HashMap::new();
+            let mut merged = HashSet::new();
+            for (idx, canonical) in candidates.iter().enumerate() {
+                if merged.contains(canonical.name()) {
+                    continue;
+                }
+                for (duplicate_idx, duplicate) in candidates.iter().enumerate().skip(idx + 1) {
+                    if !merged.contains(duplicate.name())
+                        && fingerprints[idx] == fingerprints[duplicate_idx]
+                        && canonical.structurally_eq(duplicate)
+                        && self.implements.get(canonical.name())
+                            == self.implements.get(duplicate.name())
+                    {
+                        merged.insert(duplicate.name());
+                        renames.insert(duplicate.name().to_string(), canonical.name().to_string());
+                    }
+                }
+            }
+
+            if renames.is_empty() {
+                break;
+            }
+
+            for name in renames.keys() {
+                self.types.remove(name);
+            }
+            self.rename_type_references(&renames);
+
+            let mut renames = renames.into_iter().collect::<Vec<_>>();
+            renames.sort();
+            merges.extend(renames);
+        }
+
+        merges
+    }
+}
@@ -0,0 +1,546 @@
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+};
+
+use indexmap::IndexMap;
+
+use crate::registry::{
+    is_builtin_scalar, Deprecation, MetaDirective, MetaField, MetaInputValue, MetaType,
+    MetaTypeName, Registry,
+};
+
+/// A change between two registries, found by [`Registry::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// The schema coordinate of the changed element, such as `User`,
+    /// `User.age`, `Query.users(first:)`, `Role.ADMIN` or `@auth(role:)`.
+    ///
+    /// Changes of the root types are located at `schema`.
+    pub location: String,
+    /// A description of the change.
+    pub message: String,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// The changes between two registries, returned by [`Registry::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDiff {
+    /// Changes that can break existing clients, such as removing a field,
+    /// narrowing the type of an argument or removing an enum value.
+    pub breaking: Vec<Change>,
+    /// Changes that do not invalidate existing operations but can change
+    /// their results, such as adding an enum value, adding a member to a
+    /// union or changing the default value of an argument.
+    pub dangerous: Vec<Change>,
+    /// Changes that are always safe, such as adding a type or a field, or
+    /// changing a description.
+    pub safe: Vec<Change>,
+}
+
+impl SchemaDiff {
+    /// Returns `true` if the registries are identical.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.breaking.is_empty() && self.dangerous.is_empty() && self.safe.is_empty()
+    }
+
+    /// Returns `true` if there is at least one breaking change.
+    #[inline]
+    pub fn is_breaking(&self) -> bool {
+        !self.breaking.is_empty()
+    }
+
+    fn breaking(&mut self, location: impl Into<String>, message: String) {
+        self.breaking.push(Change {
+            location: location.into(),
+            message,
+        });
+    }
+
+    fn dangerous(&mut self, location: impl Into<String>, message: String) {
+        self.dangerous.push(Change {
+            location: location.into(),
+            message,
+        });
+    }
+
+    fn safe(&mut self, location: impl Into<String>, message: String) {
+        self.safe.push(Change {
+            location: location.into(),
+            message,
+        });
+    }
+
+    fn root_type(&mut self, operation: &str, old: Option<&str>, new: Option<&str>) {
+        match (old, new) {
+            (Some(old), Some(new)) if old != new => self.breaking(
+                "schema",
+                format!(
+                    "The {} root type changed from `{}` to `{}`",
+                    operation, old, new
+                ),
+            ),
+            (Some(old), None) => self.breaking(
+                "schema",
+                format!("The {} root type `{}` was removed", operation, old),
+            ),
+            (None, Some(new)) => self.safe(
+                "schema",
+                format!("The {} root type `{}` was added", operation, new),
+            ),
+            _ => {}
+        }
+    }
+
+    fn description(&mut self, location: &str, old: Option<&str>, new: Option<&str>) {
+        if old != new {
+            self.safe(
+                location,
+                format!("The description of `{}` changed", location),
+            );
+        }
+    }
+
+    fn deprecation(&mut self, location: &str, old: &Deprecation, new: &Deprecation) {
+        match (old.is_deprecated(), new.is_deprecated()) {
+            (false, true) => self.safe(location, format!("`{}` was deprecated", location)),
+            (true, false) => self.safe(location, format!("`{}` is no longer deprecated", location)),
+            _ => {}
+        }
+    }
+
+    fn meta_type(
+        &mut self,
+        old_registry: &Registry,
+        new_registry: &Registry,
+        old: &MetaType,
+        new: &MetaType,
+    ) {
+        let name = old.name();
+        if old.type_id() != new.type_id() {
+            self.breaking(
+                name,
+                format!(
+                    "The kind of `{}` changed from {} to {}",
+                    name,
+                    old.type_id(),
+                    new.type_id()
+                ),
+            );
+            return;
+        }
+
+        self.description(name, old.description(), new.description());
+
+        match (old, new) {
+            (
+                MetaType::Object {
+                    fields: old_fields, ..
+                },
+                MetaType::Object {
+                    fields: new_fields, ..
+                },
+            )
+            | (
+                MetaType::Interface {
+                    fields: old_fields, ..
+                },
+                MetaType::Interface {
+                    fields: new_fields, ..
+                },
+            ) => {
+                self.interfaces(name, old_registry, new_registry);
+                self.fields(name, old_fields, new_fields);
+            }
+            (
+                MetaType::Union {
+                    possible_types: old_members,
+                    ..
+                },
+                MetaType::Union {
+                    possible_types: new_members,
+                    ..
+                },
+            ) => {
+                for member in old_members.difference(new_members) {
+                    self.breaking(
+                        name,
+                        format!("`{}` was removed from the union `{}`", member, name),
+                    );
+                }
+                for member in new_members.difference(old_members) {
+                    self.dangerous(
+                        name,
+                        format!("`{}` was added to the union `{}`", member, name),
+                    );
+                }
+            }
+            (
+                MetaType::Enum {
+                    enum_values: old_values,
+                    ..
+                },
+                MetaType::Enum {
+                    enum_values: new_values,
+                    ..
+                },
+            ) => {
+                for (value_name, old_value) in old_values {
+                    let location = format!("{}.{}", name, value_name);
+                    match new_values.get(value_name) {
+                        Some(new_value) => {
+                            self.description(
+                                &location,
+                                old_value.description.as_deref(),
+                                new_value.description.as_deref(),
+                            );
+                            self.deprecation(
+                                &location,
+                                &old_value.deprecation,
+                                &new_value.deprecation,
+                            );
+                        }
+                        None => {
+                            let message = format!("Enum value `{}` was removed", location);
+                            self.breaking(location, message);
+                        }
+                    }
+                }
+                for value_name in new_values.keys() {
+                    if !old_values.contains_key(value_name) {
+                        let location = format!("{}.{}", name, value_name);
+                        let message = format!("Enum value `{}` was added", location);
+                        self.dangerous(location, message);
+                    }
+                }
+            }
+            (
+                MetaType::InputObject {
+                    input_fields: old_fields,
+                    oneof: old_oneof,
+                    ..
+                },
+                MetaType::InputObject {
+                    input_fields: new_fields,
+                    oneof: new_oneof,
+                    ..
+                },
+            ) => {
+                match (old_oneof, new_oneof) {
+                    (false, true) => {
+                        self.breaking(name, format!("`{}` became a oneOf input object", name))
+                    }
+                    (true, false) => self.safe(
+                        name,
+                        format!("`{}` is no longer a oneOf input object", name),
+                    ),
+                    _ => {}
+                }
+                self.input_values("Input field", old_fields, new_fields, |field| {
+                    format!("{}.{}", name, field)
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn interfaces(&mut self, name: &str, old_registry: &Registry, new_registry: &Registry) {
+        let empty = BTreeSet::new();
+        let old_interfaces = old_registry.implements.get(name).unwrap_or(&empty);
+        let new_interfaces = new_registry.implements.get(name).unwrap_or(&empty);
+
+        for interface in old_interfaces.difference(new_interfaces) {
+            self.breaking(
+                name,
+                format!("`{}` no longer implements `{}`", name, interface),
+            );
+        }
+
+        for interface in new_interfaces.difference(old_interfaces) {
+            self.dangerous(name, format!("`{}` now implements `{}`", name, interface));
+        }
+    }
+
+    fn fields(
+        &mut self,
+        type_name: &str,
+        old: &IndexMap<String, MetaField>,
+        new: &IndexMap<String, MetaField>,
+    ) {
+        for (name, old_field) in old.iter().filter(|(_, field)| !field.is_introspection()) {
+            let location = format!("{}.{}", type_name, name);
+            let new_field = match new.get(name) {
+                Some(new_field) => new_field,
+                None => {
+                    let message = format!("Field `{}` was removed", location);
+                    self.breaking(location, message);
+                    continue;
+                }
+            };
+
+            if old_field.ty != new_field.ty {
+                let message = format!(
+                    "The type of `{}` changed from `{}` to `{}`",
+                    location, old_field.ty, new_field.ty
+                );
+                // The new type of an output field must be a subtype of the old one.
+                if MetaTypeName::create(&old_field.ty)
+                    .is_subtype(&MetaTypeName::create(&new_field.ty))
+                {
+                    self.safe(location.clone(), message);
+                } else {
+                    self.breaking(location.clone(), message);
+                }
+            }
+            self.description(
+                &location,
+                old_field.description.as_deref(),
+                new_field.description.as_deref(),
+            );
+            self.deprecation(&location, &old_field.deprecation, &new_field.deprecation);
+            self.input_values("Argument", &old_field.args, &new_field.args, |arg| {
+                format!("{}.{}({}:)", type_name, name, arg)
+            });
+        }
+
+        for name in new.keys() {
+            if !old.contains_key(name) && !name.starts_with("__") {
+                let location = format!("{}.{}", type_name, name);
+                let message = format!("Field `{}` was added", location);
+                self.safe(location, message);
+            }
+        }
+    }
+
+    fn input_values(
+        &mut self,
+        kind: &str,
+        old: &IndexMap<String, MetaInputValue>,
+        new: &IndexMap<String, MetaInputValue>,
+        location: impl Fn(&str) -> String,
+    ) {
+        for (name, old_value) in old {
+            let location = location(name);
+            let new_value = match new.get(name) {
+                Some(new_value) => new_value,
+                None => {
+                    let message = format!("{} `{}` was removed", kind, location);
+                    self.breaking(location, message);
+                    continue;
+                }
+            };
+
+            if old_value.ty != new_value.ty {
+                let message = format!(
+                    "The type of `{}` changed from `{}` to `{}`",
+                    location, old_value.ty, new_value.ty
+                );
+                // The old type of an input value must be a subtype of the new one.
+                if MetaTypeName::create(&new_value.ty)
+                    .is_subtype(&MetaTypeName::create(&old_value.ty))
+                {
+                    self.safe(location.clone(), message);
+                } else {
+                    self.breaking(location.clone(), message);
+                }
+            }
+            if old_value.default_value != new_value.default_value {
+                match (&old_value.default_value, &new_value.default_value) {
+                    (Some(_), None) if new_value.is_required() => {
+                        let message = format!(
+                            "The default value of `{}` was removed, making it required",
+                            location
+                        );
+                        self.breaking(location.clone(), message);
+                    }
+                    (old_default, new_default) => {
+                        let message = format!(
+                            "The default value of `{}` changed from `{}` to `{}`",
+                            location,
+                            old_default.as_deref().unwrap_or("null"),
+                            new_default.as_deref().unwrap_or("null")
+                        );
+                        self.dangerous(location.clone(), message);
+                    }
+                }
+            }
+            self.description(
+                &location,
+                old_value.description.as_deref(),
+                new_value.description.as_deref(),
+            );
+            self.deprecation(&location, &old_value.deprecation, &new_value.deprecation);
+        }
+
+        for (name, new_value) in new {
+            if old.contains_key(name) {
+                continue;
+            }
+            let location = location(name);
+            if new_value.is_required() {
+                let message = format!("Required {} `{}` was added", kind.to_lowercase(), location);
+                self.breaking(location, message);
+            } else {
+                let message = format!("{} `{}` was added", kind, location);
+                self.dangerous(location, message);
+            }
+        }
+    }
+
+    fn directive(&mut self, old: &MetaDirective, new: &MetaDirective) {
+        let location = format!("@{}", old.name);
+
+        self.description(
+            &location,
+            old.description.as_deref(),
+            new.description.as_deref(),
+        );
+        self.deprecation(&location, &old.deprecation, &new.deprecation);
+
+        for directive_location in &old.locations {
+            if !new.locations.contains(directive_location) {
+                self.breaking(
+                    location.clone(),
+                    format!(
+                        "`{}` can no longer be used on {:?}",
+                        location, directive_location
+                    ),
+                );
+            }
+        }
+        for directive_location in &new.locations {
+            if !old.locations.contains(directive_location) {
+                self.safe(
+                    location.clone(),
+                    format!("`{}` can now be used on {:?}", location, directive_location),
+                );
+            }
+        }
+
+        match (old.is_repeatable, new.is_repeatable) {
+            (true, false) => self.breaking(
+                location.clone(),
+                format!("`{}` is no longer repeatable", location),
+            ),
+            (false, true) => self.safe(
+                location.clone(),
+                format!("`{}` became repeatable", location),
+            ),
+            _ => {}
+        }
+
+        self.input_values("Argument", &old.args, &new.args, |arg| {
+            format!("@{}({}:)", old.name, arg)
+        });
+    }
+}
+
+impl Registry {
+    /// Compares this registry, the old schema, with `other`, the new schema,
+    /// and classifies every change as breaking, dangerous or safe.
+    ///
+    /// The classification follows the usual GraphQL rules: removing or
+    /// narrowing anything a client can select or pass is breaking, as is
+    /// adding a required argument or input field. Adding an enum value, a
+    /// union member, an interface to an object or an optional argument, or
+    /// changing a default value, is dangerous. Everything else, including
+    /// descriptions and deprecations, is safe.
+    ///
+    /// Introspection types and fields are ignored. Changes are grouped by
+    /// type, in type name order, followed by the directives.
+    pub fn diff(&self, other: &Registry) -> SchemaDiff {
+        let mut diff = SchemaDiff::default();
+
+        diff.root_type("query", Some(&self.query_type), Some(&other.query_type));
+        diff.root_type(
+            "mutation",
+            self.mutation_type.as_deref(),
+            other.mutation_type.as_deref(),
+        );
+        diff.root_type(
+            "subscription",
+            self.subscription_type.as_deref(),
+            other.subscription_type.as_deref(),
+        );
+
+        for (name, old_ty) in self
+            .types
+            .iter()
+            .filter(|(name, _)| !name.starts_with("__"))
+        {
+            match other.types.get(name) {
+                Some(new_ty) => diff.meta_type(self, other, old_ty, new_ty),
+                None => diff.breaking(name.clone(), format!("Type `{}` was removed", name)),
+            }
+        }
+        for name in other.types.keys() {
+            if !name.starts_with("__") && !self.types.contains_key(name) {
+                diff.safe(name.clone(), format!("Type `{}` was added", name));
+            }
+        }
+
+        let mut old_directives = self.directives.values().collect::<Vec<_>>();
+        old_directives.sort_by_key(|directive| directive.name);
+        for old_directive in old_directives {
+            match other.directives.get(old_directive.name) {
+                Some(new_directive) => diff.directive(old_directive, new_directive),
+                None => diff.breaking(
+                    format!("@{}", old_directive.name),
+                    format!("Directive `@{}` was removed", old_directive.name),
+                ),
+            }
+        }
+        let mut new_directives = other
+            .directives
+            .keys()
+            .filter(|name| !self.directives.contains_key(*name))
+            .collect::<Vec<_>>();
+        new_directives.sort();
+        for name in new_directives {
+            diff.safe(
+                format!("@{}", name),
+                format!("Directive `@{}` was added", name),
+            );
+        }
+
+        diff
+    }
+
+    /// Checks that this registry, the new schema, has no breaking changes
+    /// compared to `old_sdl`, the SDL of the old schema, for example in a
+    /// test that guards a published schema.
+    ///
+    /// The old schema is loaded with [`Registry::from_sdl`] and compared with
+    /// [`Registry::diff`]. Returns the breaking changes if there are any,
+    /// dangerous and safe changes are allowed. The built-in scalars and
+    /// directives are not declared in SDL, so a registry without them is
+    /// compared as if it had them. If `old_sdl` cannot be loaded,
+    /// the [`FromSdlError`](crate::registry::FromSdlError) is returned as a
+    /// single breaking change located at `schema`.
+    pub fn assert_no_breaking_changes_from(&self, old_sdl: &str) -> Result<(), Vec<Change>> {
+        let mut old = Registry::from_sdl(old_sdl).map_err(|err| {
+            vec![Change {
+                location: "schema".to_string(),
+                message: format!("The old SDL cannot be loaded: {}", err),
+            }]
+        })?;
+        // `from_sdl` always adds the built-in scalars and the `@include` and
+        // `@skip` directives, which the SDL never declares.
+        old.types
+            .retain(|name, _| !is_builtin_scalar(name) || self.types.contains_key(name));
+        old.directives.retain(|name, _| {
+            !matches!(name.as_str(), "include" | "skip") || self.directives.contains_key(name)
+        });
+        let diff = old.diff(self);
+        if !diff.breaking.is_empty() {
+            return Err(diff.breaking);
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,20 @@
-use std::fmt::Write;
+use std::{collections::HashSet, fmt::Write};
 
-use crate::registry::{Deprecation, MetaField, MetaInputValue, MetaType, Registry};
+use indexmap::IndexMap;
+
+use crate::{
+    registry::{
+        CacheControl, Deprecation, FederationFeature, FederationVersion, MetaDirective,
+        MetaDirectiveInvocation, MetaField, MetaInputValue, MetaKey, MetaType, MetaTypeName,
+        Registry, SchemaError,
+    },
+    Name, Value,
+};
 
 const SYSTEM_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
 const FEDERATION_SCALARS: &[&str] = &["Any"];
+pub(crate) const SPEC_DIRECTIVES: &[&str] =
+    &["include", "skip", "deprecated", "specifiedBy", "oneOf"];
 
 /// Options for SDL export
 #[derive(Debug, Copy, Clone, Default)]
@@ -12,8 +23,16 @@ pub struct SDLExportOptions {
     sorted_arguments: bool,
     sorted_enum_values: bool,
     federation: bool,
+    federation_version: FederationVersion,
     prefer_single_line_descriptions: bool,
     include_specified_by: bool,
+    union_line_width: Option<usize>,
+    include_cache_control: bool,
+    include_cost: bool,
+    include_directives: bool,
+    group_by_kind: bool,
+    compact: bool,
+    always_emit_schema_definition: bool,
 }
 
 impl SDLExportOptions {
@@ -24,6 +43,11 @@ impl SDLExportOptions {
     }
 
     /// Export sorted fields
+    ///
+    /// The fields of objects, interfaces and input objects, as well as the
+    /// arguments of every field, are sorted alphabetically. Enum values keep
+    /// their declaration order, use
+    /// [`sorted_enum_items`](Self::sorted_enum_items) to sort them too.
     #[inline]
     #[must_use]
     pub fn sorted_fields(self) -> Self {
@@ -63,7 +87,32 @@ impl SDLExportOptions {
         }
     }
 
+    /// Set the version of the federation SDL, federation 2 by default
+    ///
+    /// Federation 1 SDL has no `@link` directive, and the directives that
+    /// only exist in federation 2 (`@shareable`, `@inaccessible` and
+    /// `@override`) are omitted. Use
+    /// [`Registry::unsupported_federation_directives`] to find out whether
+    /// the schema uses them.
+    #[inline]
+    #[must_use]
+    pub fn federation_version(self, version: FederationVersion) -> Self {
+        Self {
+            federation_version: version,
+            ..self
+        }
+    }
+
+    /// Returns `true` if the directives introduced by federation 2 are
+    /// exported.
+    fn federation_v2(&self) -> bool {
+        self.federation_version == FederationVersion::V2
+    }
+
     /// When possible, write one-line instead of three-line descriptions
+    ///
+    /// Descriptions containing a line break or a quote are always written as
+    /// block strings.
     #[inline]
     #[must_use]
     pub fn prefer_single_line_descriptions(self) -> Self {
@@ -80,22 +129,171 @@ impl SDLExportOptions {
             ..self
         }
     }
+
+    /// Includes Apollo `@cacheControl` directives in SDL
+    ///
+    /// Object types are annotated with their own cache control, and fields
+    /// with the combination of their own cache control and the cache control
+    /// of the object type they return. Types and fields without cache hints
+    /// are not annotated.
+    #[inline]
+    #[must_use]
+    pub fn include_cache_control(self) -> Self {
+        Self {
+            include_cache_control: true,
+            ..self
+        }
+    }
+
+    /// Includes `@cost` directives in SDL
+    ///
+    /// Fields annotated with `#[graphql(cost = N)]` are exported with
+    /// `@cost(weight: N)`.
+    #[inline]
+    #[must_use]
+    pub fn include_cost(self) -> Self {
+        Self {
+            include_cost: true,
+            ..self
+        }
+    }
+
+    /// Includes the definitions of custom directives, and the directives
+    /// applied to types, fields and enum values, in SDL
+    ///
+    /// Directives without arguments are written without parentheses, such as
+    /// `@internal`.
+    #[inline]
+    #[must_use]
+    pub fn include_directives(self) -> Self {
+        Self {
+            include_directives: true,
+            ..self
+        }
+    }
+
+    /// Export types grouped by kind (scalars, enums, interfaces, objects,
+    /// unions and input objects), sorted alphabetically within each group
+    ///
+    /// By default, all types are sorted alphabetically.
+    #[inline]
+    #[must_use]
+    pub fn group_by_kind(self, enabled: bool) -> Self {
+        Self {
+            group_by_kind: enabled,
+            ..self
+        }
+    }
+
+    /// Export compact SDL, without descriptions and without blank lines
+    /// between types
+    ///
+    /// Objects, interfaces, enums and input objects are written on a single
+    /// line, such as `enum Color { RED GREEN BLUE }`, unless one of their
+    /// fields or values carries a directive. The schema definition and the
+    /// `@link` directives are written on a single line too.
+    #[inline]
+    #[must_use]
+    pub fn compact(self) -> Self {
+        Self {
+            compact: true,
+            ..self
+        }
+    }
+
+    /// Write the members of a union on separate lines when the union
+    /// definition would be longer than `max_width` characters
+    ///
+    /// ```graphql
+    /// union SearchResult =
+    ///     Article
+    ///   | Image
+    ///   | Video
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn wrap_long_unions(self, max_width: usize) -> Self {
+        Self {
+            union_line_width: Some(max_width),
+            ..self
+        }
+    }
+
+    /// Always write the `schema { ... }` definition
+    ///
+    /// By default, it is only written when a root type does not use its
+    /// default name (`Query`, `Mutation` or `Subscription`), or when a type
+    /// that is not a root type uses one of these names.
+    #[inline]
+    #[must_use]
+    pub fn always_emit_schema_definition(self) -> Self {
+        Self {
+            always_emit_schema_definition: true,
+            ..self
+        }
+    }
 }
 
 impl Registry {
+    /// Export the SDL, like [`Schema::sdl_with_options`](crate::Schema::sdl_with_options),
+    /// but first check that every emitted directive application is allowed
+    /// at its location when [`SDLExportOptions::include_directives`] is
+    /// set, see [`Registry::validate_directive_locations`].
+    pub fn try_export_sdl(&self, options: SDLExportOptions) -> Result<String, Vec<SchemaError>> {
+        if options.include_directives {
+            self.validate_directive_locations()?;
+        }
+        Ok(self.export_sdl(options))
+    }
+
     pub(crate) fn export_sdl(&self, options: SDLExportOptions) -> String {
         let mut sdl = String::new();
 
-        let has_oneof = self
-            .types
-            .values()
-            .any(|ty| matches!(ty, MetaType::InputObject { oneof: true, .. }));
-
-        if has_oneof {
+        if self
+            .used_feature_directives()
+            .contains(&FederationFeature::OneOf)
+        {
             sdl.write_str("directive @oneOf on INPUT_OBJECT\n\n").ok();
         }
 
-        for ty in self.types.values() {
+        if options.include_cache_control {
+            sdl.write_str("enum CacheControlScope {\n\tPUBLIC\n\tPRIVATE\n}\n\n")
+                .ok();
+            sdl.write_str("directive @cacheControl(maxAge: Int, scope: CacheControlScope, inheritMaxAge: Boolean) on FIELD_DEFINITION | OBJECT | INTERFACE | UNION\n\n").ok();
+        }
+
+        if options.include_cost {
+            sdl.write_str("directive @cost(weight: Int!) on FIELD_DEFINITION\n\n")
+                .ok();
+        }
+
+        self.export_directives(&mut sdl, &options, |directive| {
+            options.include_directives
+                // The clients need the definitions of the incremental delivery directives.
+                || (self.enable_incremental_delivery
+                    && matches!(directive.name, "defer" | "stream"))
+                // The definitions of the composed directives are required by the composition.
+                || (options.federation
+                    && options.federation_v2()
+                    && self
+                        .compose_directives
+                        .iter()
+                        .any(|name| name == directive.name))
+        });
+
+        let mut types = self.types.values().collect::<Vec<_>>();
+        if options.group_by_kind {
+            types.sort_by_key(|ty| match ty {
+                MetaType::Scalar { .. } => 0,
+                MetaType::Enum { .. } => 1,
+                MetaType::Interface { .. } => 2,
+                MetaType::Object { .. } => 3,
+                MetaType::Union { .. } => 4,
+                MetaType::InputObject { .. } => 5,
+            });
+        }
+
+        for ty in types {
             if ty.name().starts_with("__") {
                 continue;
             }
@@ -108,33 +306,164 @@ impl Registry {
             }
 
             self.export_type(ty, &mut sdl, &options);
-            writeln!(sdl).ok();
+            if !options.compact {
+                writeln!(sdl).ok();
+            }
         }
 
         if options.federation {
-            if self.enable_apollo_link {
-                writeln!(sdl, "extend schema @link(").ok();
-                writeln!(sdl, "\turl: \"https://specs.apollo.dev/federation/v2.0\",").ok();
-                writeln!(sdl, "\timport: [\"@key\", \"@tag\", \"@shareable\", \"@inaccessible\", \"@override\", \"@external\", \"@provides\", \"@requires\"]").ok();
-                writeln!(sdl, ")").ok();
+            let links = if options.federation_v2() {
+                self.links()
+            } else {
+                Vec::new()
+            };
+            let (open, indent, separator, close) = if options.compact {
+                ("(", "", ", ", ")\n")
+            } else {
+                ("(\n", "\t", ",\n", "\n)\n")
+            };
+            for link in links {
+                let mut args = vec![format!("url: \"{}\"", escape_string(&link.url))];
+                if let Some(alias) = &link.alias {
+                    args.push(format!("as: \"{}\"", escape_string(alias)));
+                }
+                args.push(format!(
+                    "import: [{}]",
+                    link.import
+                        .iter()
+                        .map(|directive| format!("\"{}\"", escape_string(directive)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+                write!(sdl, "extend schema @link{}", open).ok();
+                for (i, arg) in args.iter().enumerate() {
+                    if i != 0 {
+                        sdl.push_str(separator);
+                    }
+                    write!(sdl, "{}{}", indent, arg).ok();
+                }
+                sdl.push_str(close);
             }
-        } else {
-            writeln!(sdl, "schema {{").ok();
-            writeln!(sdl, "\tquery: {}", self.query_type).ok();
+            if options.federation_v2() {
+                for name in &self.compose_directives {
+                    if self.directives.contains_key(name) {
+                        writeln!(sdl, "extend schema @composeDirective(name: \"@{}\")", name).ok();
+                    }
+                }
+            }
+        } else if options.always_emit_schema_definition || !self.uses_default_root_names() {
+            let (open, indent, line_end, close) = if options.compact {
+                ("schema {", " ", "", " }")
+            } else {
+                ("schema {\n", "\t", "\n", "}")
+            };
+            sdl.push_str(open);
+            write!(sdl, "{}query: {}{}", indent, self.query_type, line_end).ok();
             if let Some(mutation_type) = self.mutation_type.as_deref() {
-                writeln!(sdl, "\tmutation: {}", mutation_type).ok();
+                write!(sdl, "{}mutation: {}{}", indent, mutation_type, line_end).ok();
             }
             if let Some(subscription_type) = self.subscription_type.as_deref() {
-                writeln!(sdl, "\tsubscription: {}", subscription_type).ok();
+                write!(
+                    sdl,
+                    "{}subscription: {}{}",
+                    indent, subscription_type, line_end
+                )
+                .ok();
             }
-            writeln!(sdl, "}}").ok();
+            writeln!(sdl, "{}", close).ok();
         }
 
         sdl
     }
 
+    /// Export the SDL together with a JSON object that maps each GraphQL type
+    /// name to the name of the Rust type implementing it.
+    ///
+    /// Scalars (which have no Rust type name) and introspection types are not
+    /// included in the mapping.
+    pub fn export_with_rust_mapping(
+        &self,
+        options: SDLExportOptions,
+    ) -> (String, serde_json::Value) {
+        let mapping = self
+            .types
+            .values()
+            .filter(|ty| !ty.name().starts_with("__"))
+            .filter_map(|ty| {
+                ty.rust_typename().map(|rust_typename| {
+                    (
+                        ty.name().to_string(),
+                        serde_json::Value::String(rust_typename.to_string()),
+                    )
+                })
+            })
+            .collect::<serde_json::Map<_, _>>();
+        (self.export_sdl(options), serde_json::Value::Object(mapping))
+    }
+
+    /// Returns `true` if the schema definition can be omitted, that is if
+    /// every root type uses its default name and no other type uses one of
+    /// these names.
+    fn uses_default_root_names(&self) -> bool {
+        [
+            ("Query", Some(self.query_type.as_str())),
+            ("Mutation", self.mutation_type.as_deref()),
+            ("Subscription", self.subscription_type.as_deref()),
+        ]
+        .into_iter()
+        .all(|(default_name, root)| match root {
+            Some(root) => root == default_name,
+            None => !self.types.contains_key(default_name),
+        })
+    }
+
+    fn export_directives(
+        &self,
+        sdl: &mut String,
+        options: &SDLExportOptions,
+        filter: impl Fn(&MetaDirective) -> bool,
+    ) {
+        let directives = self
+            .directives()
+            .filter(|directive| !SPEC_DIRECTIVES.contains(&directive.name) && filter(directive));
+
+        for directive in directives {
+            if let Some(description) = &directive.description {
+                export_description(sdl, options, true, description);
+            }
+
+            write!(sdl, "directive @{}", directive.name).ok();
+            if !directive.args.is_empty() {
+                let args = directive
+                    .args
+                    .values()
+                    .map(|arg| self.export_input_value(arg))
+                    .collect::<Vec<_>>();
+                write!(sdl, "({})", args.join(", ")).ok();
+            }
+            if directive.is_repeatable {
+                write!(sdl, " repeatable").ok();
+            }
+            let locations = directive
+                .locations
+                .iter()
+                .map(|location| format!("{:?}", location))
+                .collect::<Vec<_>>();
+            writeln!(sdl, " on {}", locations.join(" | ")).ok();
+            if !options.compact {
+                writeln!(sdl).ok();
+            }
+        }
+    }
+
+    /// Writes the fields of an object or an interface, between braces.
+    ///
+    /// In compact mode, the fields are written on a single line unless one of
+    /// them carries a directive.
     fn export_fields<'a, I: Iterator<Item = &'a MetaField>>(
+        &self,
         sdl: &mut String,
+        type_name: &str,
         it: I,
         options: &SDLExportOptions,
     ) {
@@ -144,6 +473,9 @@ impl Registry {
             fields.sort_by(|a, b| a.name.cmp(&b.name));
         }
 
+        let mut lines = Vec::new();
+        let mut has_directives = false;
+
         for field in fields {
             if field.name.starts_with("__")
                 || (options.federation && matches!(&*field.name, "_service" | "_entities"))
@@ -151,69 +483,186 @@ impl Registry {
                 continue;
             }
 
-            if let Some(description) = field.description {
-                export_description(sdl, options, false, description);
-            }
+            let mut line = String::new();
 
             if !field.args.is_empty() {
-                write!(sdl, "\t{}(", field.name).ok();
+                write!(line, "{}(", field.name).ok();
 
                 let mut args = field.args.values().collect::<Vec<_>>();
-                if options.sorted_arguments {
-                    args.sort_by(|a, b| a.name.cmp(b.name));
+                if options.sorted_fields || options.sorted_arguments {
+                    args.sort_by(|a, b| a.name.cmp(&b.name));
                 }
 
                 for (i, arg) in args.into_iter().enumerate() {
                     if i != 0 {
-                        sdl.push_str(", ");
+                        line.push_str(", ");
                     }
-                    sdl.push_str(&export_input_value(arg));
+                    line.push_str(&self.export_input_value(arg));
+                    has_directives |= arg.deprecation.is_deprecated();
 
                     if options.federation {
-                        if arg.inaccessible {
-                            write!(sdl, " @inaccessible").ok();
+                        if arg.inaccessible && options.federation_v2() {
+                            write!(line, " @inaccessible").ok();
+                            has_directives = true;
                         }
 
                         for tag in arg.tags {
-                            write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
+                            write!(line, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
+                            has_directives = true;
                         }
                     }
                 }
-                write!(sdl, "): {}", field.ty).ok();
+                write!(line, "): {}", field.ty).ok();
             } else {
-                write!(sdl, "\t{}: {}", field.name, field.ty).ok();
+                write!(line, "{}: {}", field.name, field.ty).ok();
+            }
+
+            let len = line.len();
+
+            write_deprecated(&mut line, &field.deprecation);
+
+            if options.include_cache_control {
+                let field_cache_control =
+                    self.effective_field_cache_control(type_name, &field.name);
+                let cache_control = match self.concrete_type_by_name(&field.ty) {
+                    Some(MetaType::Object { cache_control, .. }) => {
+                        field_cache_control.merge(cache_control)
+                    }
+                    _ => field_cache_control,
+                };
+                write_cache_control(&mut line, &cache_control);
             }
 
-            write_deprecated(sdl, &field.deprecation);
+            if options.include_cost {
+                if let Some(cost) = field.cost {
+                    write!(line, " @cost(weight: {})", cost).ok();
+                }
+            }
 
             if options.federation {
                 if field.external {
-                    write!(sdl, " @external").ok();
+                    write!(line, " @external").ok();
                 }
                 if let Some(requires) = field.requires {
-                    write!(sdl, " @requires(fields: \"{}\")", requires).ok();
+                    write!(line, " @requires(fields: \"{}\")", requires).ok();
                 }
                 if let Some(provides) = field.provides {
-                    write!(sdl, " @provides(fields: \"{}\")", provides).ok();
+                    write!(line, " @provides(fields: \"{}\")", provides).ok();
                 }
-                if field.shareable {
-                    write!(sdl, " @shareable").ok();
+                if field.shareable && options.federation_v2() {
+                    write!(line, " @shareable").ok();
                 }
-                if field.inaccessible {
-                    write!(sdl, " @inaccessible").ok();
+                if field.inaccessible && options.federation_v2() {
+                    write!(line, " @inaccessible").ok();
                 }
                 for tag in field.tags {
-                    write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
+                    write!(line, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
+                }
+                if let Some(from) = field.override_from.filter(|_| options.federation_v2()) {
+                    write!(line, " @override(from: \"{}\")", from).ok();
                 }
-                if let Some(from) = field.override_from {
-                    write!(sdl, " @override(from: \"{}\")", from).ok();
+            }
+
+            if options.include_directives {
+                write_directive_invocations(&mut line, &field.directive_invocations);
+            }
+
+            has_directives |= line.len() != len;
+            lines.push((field.description.as_deref(), line));
+        }
+
+        if options.compact && !has_directives {
+            sdl.push_str(" {");
+            for (_, line) in lines {
+                write!(sdl, " {}", line).ok();
+            }
+            writeln!(sdl, " }}").ok();
+        } else {
+            writeln!(sdl, " {{").ok();
+            for (description, line) in lines {
+                if let Some(description) = description {
+                    export_description(sdl, options, false, description);
                 }
+                writeln!(sdl, "\t{}", line).ok();
             }
+            writeln!(sdl, "}}").ok();
+        }
+    }
 
-            writeln!(sdl).ok();
+    fn export_type_fields(
+        &self,
+        sdl: &mut String,
+        name: &str,
+        fields: &IndexMap<String, MetaField>,
+        keys: Option<&[MetaKey]>,
+        extends: bool,
+        options: &SDLExportOptions,
+    ) {
+        match keys {
+            Some(keys)
+                if options.federation
+                    && !keys.is_empty()
+                    && self.reference_stubs.contains(name) =>
+            {
+                // A reference stub only declares the key fields, which are owned by another
+                // subgraph.
+                let key_fields = keys
+                    .iter()
+                    .flat_map(|key| key_field_names(&key.fields))
+                    .collect::<HashSet<_>>();
+                let stub_fields = fields
+                    .values()
+                    .filter(|field| key_fields.contains(field.name.as_str()))
+                    .map(|field| MetaField {
+                        external: true,
+                        ..field.clone()
+                    })
+                    .collect::<Vec<_>>();
+                self.export_fields(sdl, name, stub_fields.iter(), options);
+            }
+            _ if options.federation && extends => {
+                // An extension only declares its local fields, and the external fields that
+                // are referenced by a key, a `@requires` or a `@provides`.
+                let referenced_fields = self.referenced_external_fields(name, fields, keys);
+                self.export_fields(
+                    sdl,
+                    name,
+                    fields.values().filter(|field| {
+                        !field.external || referenced_fields.contains(field.name.as_str())
+                    }),
+                    options,
+                );
+            }
+            _ => self.export_fields(sdl, name, fields.values(), options),
         }
     }
 
+    /// Returns the names of the fields of the specified type that are
+    /// referenced by its keys, by the `@requires` of its fields or by the
+    /// `@provides` of the fields returning it.
+    fn referenced_external_fields<'a>(
+        &'a self,
+        name: &str,
+        fields: &'a IndexMap<String, MetaField>,
+        keys: Option<&'a [MetaKey]>,
+    ) -> HashSet<&'a str> {
+        let provides = self
+            .types
+            .values()
+            .filter_map(|ty| ty.fields())
+            .flat_map(|fields| fields.values())
+            .filter(|field| MetaTypeName::concrete_typename(&field.ty) == name)
+            .filter_map(|field| field.provides);
+
+        keys.into_iter()
+            .flatten()
+            .map(|key| key.fields.as_str())
+            .chain(fields.values().filter_map(|field| field.requires))
+            .chain(provides)
+            .flat_map(key_field_names)
+            .collect()
+    }
+
     fn export_type(&self, ty: &MetaType, sdl: &mut String, options: &SDLExportOptions) {
         match ty {
             MetaType::Scalar {
@@ -239,14 +688,14 @@ impl Registry {
                             write!(
                                 sdl,
                                 " @specifiedBy(url: \"{}\")",
-                                specified_by_url.replace('"', "\\\"")
+                                escape_string(specified_by_url)
                             )
                             .ok();
                         }
                     }
 
                     if options.federation {
-                        if *inaccessible {
+                        if *inaccessible && options.federation_v2() {
                             write!(sdl, " @inaccessible").ok();
                         }
                         for tag in *tags {
@@ -265,6 +714,8 @@ impl Registry {
                 shareable,
                 inaccessible,
                 tags,
+                cache_control,
+                directive_invocations,
                 ..
             } => {
                 if Some(name.as_str()) == self.subscription_type.as_deref()
@@ -302,28 +753,34 @@ impl Registry {
                 write!(sdl, "type {}", name).ok();
                 self.write_implements(sdl, name);
 
+                if options.include_cache_control {
+                    write_cache_control(sdl, cache_control);
+                }
+
                 if options.federation {
                     if let Some(keys) = keys {
                         for key in keys {
-                            write!(sdl, " @key(fields: \"{}\")", key).ok();
+                            write_key(sdl, key);
                         }
                     }
-                    if *shareable {
+                    if *shareable && options.federation_v2() {
                         write!(sdl, " @shareable").ok();
                     }
 
-                    if *inaccessible {
+                    if *inaccessible && options.federation_v2() {
                         write!(sdl, " @inaccessible").ok();
                     }
 
-                    for tag in *tags {
+                    for tag in self.object_tags(name, tags) {
                         write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
                     }
                 }
 
-                writeln!(sdl, " {{").ok();
-                Self::export_fields(sdl, fields.values(), options);
-                writeln!(sdl, "}}").ok();
+                if options.include_directives {
+                    write_directive_invocations(sdl, directive_invocations);
+                }
+
+                self.export_type_fields(sdl, name, fields, keys.as_deref(), *extends, options);
             }
             MetaType::Interface {
                 name,
@@ -333,6 +790,7 @@ impl Registry {
                 description,
                 inaccessible,
                 tags,
+                directive_invocations,
                 ..
             } => {
                 if let Some(description) = description {
@@ -347,10 +805,10 @@ impl Registry {
                 if options.federation {
                     if let Some(keys) = keys {
                         for key in keys {
-                            write!(sdl, " @key(fields: \"{}\")", key).ok();
+                            write_key(sdl, key);
                         }
                     }
-                    if *inaccessible {
+                    if *inaccessible && options.federation_v2() {
                         write!(sdl, " @inaccessible").ok();
                     }
 
@@ -360,9 +818,11 @@ impl Registry {
                 }
                 self.write_implements(sdl, name);
 
-                writeln!(sdl, " {{").ok();
-                Self::export_fields(sdl, fields.values(), options);
-                writeln!(sdl, "}}").ok();
+                if options.include_directives {
+                    write_directive_invocations(sdl, directive_invocations);
+                }
+
+                self.export_type_fields(sdl, name, fields, keys.as_deref(), *extends, options);
             }
             MetaType::Enum {
                 name,
@@ -378,26 +838,40 @@ impl Registry {
 
                 write!(sdl, "enum {}", name).ok();
                 if options.federation {
-                    if *inaccessible {
+                    if *inaccessible && options.federation_v2() {
                         write!(sdl, " @inaccessible").ok();
                     }
                     for tag in *tags {
                         write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
                     }
                 }
-                writeln!(sdl, " {{").ok();
 
                 let mut values = enum_values.values().collect::<Vec<_>>();
                 if options.sorted_enum_values {
                     values.sort_by(|a, b| a.name.cmp(&b.name));
                 }
 
+                let single_line = options.compact
+                    && values.iter().all(|value| {
+                        !value.deprecation.is_deprecated()
+                            && !(options.federation
+                                && (value.inaccessible || !value.tags.is_empty()))
+                            && (!options.include_directives
+                                || value.directive_invocations.is_empty())
+                    });
+                let (open, indent, line_end, close) = if single_line {
+                    (" {", " ", "", " }")
+                } else {
+                    (" {\n", "\t", "\n", "}")
+                };
+                sdl.push_str(open);
+
                 for value in values {
-                    write!(sdl, "\t{}", value.name).ok();
+                    write!(sdl, "{}{}", indent, value.name).ok();
                     write_deprecated(sdl, &value.deprecation);
 
                     if options.federation {
-                        if value.inaccessible {
+                        if value.inaccessible && options.federation_v2() {
                             write!(sdl, " @inaccessible").ok();
                         }
 
@@ -405,10 +879,14 @@ impl Registry {
                             write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
                         }
                     }
-                    writeln!(sdl).ok();
+
+                    if options.include_directives {
+                        write_directive_invocations(sdl, &value.directive_invocations);
+                    }
+                    sdl.push_str(line_end);
                 }
 
-                writeln!(sdl, "}}").ok();
+                writeln!(sdl, "{}", close).ok();
             }
             MetaType::InputObject {
                 name,
@@ -429,37 +907,49 @@ impl Registry {
                     write!(sdl, " @oneOf").ok();
                 }
                 if options.federation {
-                    if *inaccessible {
+                    if *inaccessible && options.federation_v2() {
                         write!(sdl, " @inaccessible").ok();
                     }
                     for tag in *tags {
                         write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
                     }
                 }
-                writeln!(sdl, " {{").ok();
 
                 let mut fields = input_fields.values().collect::<Vec<_>>();
                 if options.sorted_fields {
-                    fields.sort_by(|a, b| a.name.cmp(b.name));
+                    fields.sort_by(|a, b| a.name.cmp(&b.name));
                 }
 
+                let single_line = options.compact
+                    && fields.iter().all(|field| {
+                        !field.deprecation.is_deprecated()
+                            && !(options.federation
+                                && (field.inaccessible || !field.tags.is_empty()))
+                    });
+                let (open, indent, line_end, close) = if single_line {
+                    (" {", " ", "", " }")
+                } else {
+                    (" {\n", "\t", "\n", "}")
+                };
+                sdl.push_str(open);
+
                 for field in fields {
-                    if let Some(description) = field.description {
+                    if let Some(description) = &field.description {
                         export_description(sdl, options, false, description);
                     }
-                    write!(sdl, "\t{}", export_input_value(&field)).ok();
+                    write!(sdl, "{}{}", indent, self.export_input_value(&field)).ok();
                     if options.federation {
-                        if field.inaccessible {
+                        if field.inaccessible && options.federation_v2() {
                             write!(sdl, " @inaccessible").ok();
                         }
                         for tag in field.tags {
                             write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
                         }
                     }
-                    writeln!(sdl).ok();
+                    sdl.push_str(line_end);
                 }
 
-                writeln!(sdl, "}}").ok();
+                writeln!(sdl, "{}", close).ok();
             }
             MetaType::Union {
                 name,
@@ -473,29 +963,60 @@ impl Registry {
                     export_description(sdl, options, true, description);
                 }
 
-                write!(sdl, "union {}", name).ok();
+                let mut definition = format!("union {}", name);
                 if options.federation {
-                    if *inaccessible {
-                        write!(sdl, " @inaccessible").ok();
+                    if *inaccessible && options.federation_v2() {
+                        write!(definition, " @inaccessible").ok();
                     }
                     for tag in *tags {
-                        write!(sdl, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
+                        write!(definition, " @tag(name: \"{}\")", tag.replace('"', "\\\"")).ok();
                     }
                 }
-                write!(sdl, " =").ok();
-
+                write!(definition, " =").ok();
+
+                let members_len = possible_types
+                    .iter()
+                    .map(|ty| ty.len() + 3)
+                    .sum::<usize>()
+                    .saturating_sub(2);
+                let wrap = matches!(
+                    options.union_line_width,
+                    Some(max_width) if definition.len() + members_len > max_width
+                );
+
+                sdl.push_str(&definition);
                 for (idx, ty) in possible_types.iter().enumerate() {
-                    if idx == 0 {
-                        write!(sdl, " {}", ty).ok();
-                    } else {
-                        write!(sdl, " | {}", ty).ok();
-                    }
+                    match (wrap, idx) {
+                        (true, 0) => write!(sdl, "\n    {}", ty).ok(),
+                        (true, _) => write!(sdl, "\n  | {}", ty).ok(),
+                        (false, 0) => write!(sdl, " {}", ty).ok(),
+                        (false, _) => write!(sdl, " | {}", ty).ok(),
+                    };
                 }
                 writeln!(sdl).ok();
             }
         }
     }
 
+    /// Returns the tags of an object, followed by the tags given to it as a
+    /// member of unions.
+    fn object_tags(&self, name: &str, tags: &[&'static str]) -> Vec<&'static str> {
+        let mut object_tags = tags.to_vec();
+        for ty in self.types.values() {
+            if let MetaType::Union {
+                possible_type_tags, ..
+            } = ty
+            {
+                for tag in possible_type_tags.get(name).into_iter().flatten() {
+                    if !object_tags.contains(tag) {
+                        object_tags.push(*tag);
+                    }
+                }
+            }
+        }
+        object_tags
+    }
+
     fn write_implements(&self, sdl: &mut String, name: &str) {
         if let Some(implements) = self.implements.get(name) {
             if !implements.is_empty() {
@@ -512,6 +1033,65 @@ impl Registry {
             }
         }
     }
+
+    fn export_input_value(&self, input_value: &MetaInputValue) -> String {
+        let default_value = input_value
+            .default_value_lenient()
+            .map(|value| self.coerce_default_value(value, &input_value.ty));
+        let mut sdl = if let Some(default_value) = default_value {
+            format!(
+                "{}: {} = {}",
+                input_value.name, input_value.ty, default_value
+            )
+        } else {
+            format!("{}: {}", input_value.name, input_value.ty)
+        };
+        write_deprecated(&mut sdl, &input_value.deprecation);
+        sdl
+    }
+
+    /// Coerces a default value to the literal of its type, so that an enum
+    /// value stored as a string is exported unquoted and a string stored as
+    /// an enum value is exported quoted.
+    fn coerce_default_value(&self, value: Value, ty: &str) -> Value {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => self.coerce_default_value(value, ty),
+            MetaTypeName::List(ty) => match value {
+                Value::List(items) => Value::List(
+                    items
+                        .into_iter()
+                        .map(|item| self.coerce_default_value(item, ty))
+                        .collect(),
+                ),
+                value => self.coerce_default_value(value, ty),
+            },
+            MetaTypeName::Named(ty) => match (self.types.get(ty), value) {
+                (Some(MetaType::Enum { enum_values, .. }), Value::String(s))
+                    if enum_values.contains_key(&s) =>
+                {
+                    Value::Enum(Name::new(s))
+                }
+                (Some(MetaType::Scalar { .. }), Value::Enum(name)) => {
+                    Value::String(name.to_string())
+                }
+                (Some(MetaType::InputObject { input_fields, .. }), Value::Object(fields)) => {
+                    Value::Object(
+                        fields
+                            .into_iter()
+                            .map(|(name, value)| {
+                                let value = match input_fields.get(name.as_str()) {
+                                    Some(field) => self.coerce_default_value(value, &field.ty),
+                                    None => value,
+                                };
+                                (name, value)
+                            })
+                            .collect(),
+                    )
+                }
+                (_, value) => value,
+            },
+        }
+    }
 }
 
 fn export_description(
@@ -520,26 +1100,97 @@ fn export_description(
     top_level: bool,
     description: &str,
 ) {
-    if options.prefer_single_line_descriptions && !description.contains('\n') {
+    if options.compact {
+        return;
+    }
+
+    // Block strings are used for descriptions containing a line break or a
+    // quote, which are not readable in a string literal.
+    if options.prefer_single_line_descriptions && !description.contains(['\n', '\r', '"']) {
         let tab = if top_level { "" } else { "\t" };
-        let description = description.replace('"', r#"\""#);
-        writeln!(sdl, "{}\"{}\"", tab, description).ok();
+        writeln!(sdl, "{}\"{}\"", tab, escape_string(description)).ok();
     } else if top_level {
-        writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description).ok();
+        writeln!(sdl, "\"\"\"\n{}\n\"\"\"", escape_block_string(description)).ok();
     } else {
-        let description = description.replace('\n', "\n\t");
+        let description = escape_block_string(description).replace('\n', "\n\t");
         writeln!(sdl, "\t\"\"\"\n\t{}\n\t\"\"\"", description).ok();
     }
 }
 
-fn export_input_value(input_value: &MetaInputValue) -> String {
-    if let Some(default_value) = &input_value.default_value {
-        format!(
-            "{}: {} = {}",
-            input_value.name, input_value.ty, default_value
-        )
-    } else {
-        format!("{}: {}", input_value.name, input_value.ty)
+/// Escapes the content of a block string, in which only `"""` has to be
+/// escaped, and normalizes its line breaks.
+fn escape_block_string(s: &str) -> String {
+    s.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace(r#"""""#, r#"\""""#)
+}
+
+/// Returns the top-level field names of a `@key` field set, e.g. `id` and
+/// `organization` for `id organization { id }`.
+fn key_field_names(key: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut depth = 0usize;
+    let mut start = None;
+
+    for (idx, c) in key.char_indices() {
+        if c.is_alphanumeric() || c == '_' {
+            if start.is_none() {
+                start = Some(idx);
+            }
+            continue;
+        }
+
+        if let Some(start) = start.take() {
+            if depth == 0 {
+                names.push(&key[start..idx]);
+            }
+        }
+
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    if let Some(start) = start {
+        if depth == 0 {
+            names.push(&key[start..]);
+        }
+    }
+
+    names
+}
+
+fn write_key(sdl: &mut String, key: &MetaKey) {
+    write!(sdl, " @key(fields: \"{}\"", key.fields).ok();
+    if !key.resolvable {
+        write!(sdl, ", resolvable: false").ok();
+    }
+    write!(sdl, ")").ok();
+}
+
+fn write_cache_control(sdl: &mut String, cache_control: &CacheControl) {
+    let mut args = Vec::new();
+    match cache_control.max_age {
+        0 => {}
+        -1 => args.push("maxAge: 0".to_string()),
+        max_age => args.push(format!("maxAge: {}", max_age)),
+    }
+    if !cache_control.public {
+        args.push("scope: PRIVATE".to_string());
+    }
+    if !args.is_empty() {
+        write!(sdl, " @cacheControl({})", args.join(", ")).ok();
+    }
+}
+
+fn write_directive_invocations(
+    sdl: &mut String,
+    directive_invocations: &[MetaDirectiveInvocation],
+) {
+    for directive in directive_invocations {
+        write!(sdl, " {}", directive.sdl()).ok();
     }
 }
 
@@ -557,6 +1208,7 @@ fn escape_string(s: &str) -> String {
 
     for c in s.chars() {
         let ec = match c {
+            '"' => Some("\\\""),
             '\\' => Some("\\\\"),
             '\x08' => Some("\\b"),
             '\x0c' => Some("\\f"),
@@ -582,11 +1234,29 @@ fn escape_string(s: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_key_field_names() {
+        assert_eq!(key_field_names("id"), vec!["id"]);
+        assert_eq!(key_field_names("id sku"), vec!["id", "sku"]);
+        assert_eq!(
+            key_field_names("id organization { id name }"),
+            vec!["id", "organization"]
+        );
+    }
+
     #[test]
     fn test_escape_string() {
         assert_eq!(
-            escape_string("1\\\x08d\x0c3\n4\r5\t6"),
-            "1\\\\\\bd\\f3\\n4\\r5\\t6"
+            escape_string("1\\\x08d\x0c3\n4\r5\t6\"7"),
+            "1\\\\\\bd\\f3\\n4\\r5\\t6\\\"7"
+        );
+    }
+
+    #[test]
+    fn test_escape_block_string() {
+        assert_eq!(
+            escape_block_string("1\r\n2\r3 \"\"\" 4 \\n"),
+            "1\n2\n3 \\\"\"\" 4 \\n"
         );
     }
 }
@@ -0,0 +1,586 @@
+use std::collections::BTreeSet;
+
+use serde_json::json;
+use thiserror::Error;
+
+use crate::{
+    parser::types::OperationType,
+    registry::{MetaField, MetaInputValue, MetaKey, MetaType, Registry, SchemaError},
+};
+
+/// The federation directives supported by this crate, in the order in which
+/// they are imported by the federation 2 `@link` directive.
+const FEDERATION_DIRECTIVES: &[&str] = &[
+    "@key",
+    "@tag",
+    "@shareable",
+    "@inaccessible",
+    "@override",
+    "@external",
+    "@provides",
+    "@requires",
+    "@composeDirective",
+];
+
+/// The federation directives that only exist in federation 2.
+const FEDERATION_V2_DIRECTIVES: &[&str] = &[
+    "@shareable",
+    "@inaccessible",
+    "@override",
+    "@composeDirective",
+];
+
+/// The URL of the federation 2 specification linked by `@link`.
+const FEDERATION_SPEC_URL: &str = "https://specs.apollo.dev/federation/v2.0";
+
+/// The URL of the federation 2.1 specification, which introduced
+/// `@composeDirective`.
+const FEDERATION_V2_1_SPEC_URL: &str = "https://specs.apollo.dev/federation/v2.1";
+
+/// A version of the Apollo Federation specification, see
+/// [`SDLExportOptions::federation_version`](crate::SDLExportOptions::federation_version).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FederationVersion {
+    /// Federation 1, without `@link` and the directives introduced by
+    /// federation 2.
+    V1,
+    /// Federation 2.
+    V2,
+}
+
+impl Default for FederationVersion {
+    fn default() -> Self {
+        FederationVersion::V2
+    }
+}
+
+/// A feature used by a subgraph, see [`Registry::used_feature_directives`].
+///
+/// Every feature except `OneOf` is a federation directive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FederationFeature {
+    /// Entity keys, `@key`.
+    Key,
+    /// `@tag`.
+    Tag,
+    /// `@shareable`.
+    Shareable,
+    /// `@inaccessible`.
+    Inaccessible,
+    /// `@override`.
+    Override,
+    /// `@external`.
+    External,
+    /// `@provides`.
+    Provides,
+    /// `@requires`.
+    Requires,
+    /// `@composeDirective`.
+    ComposeDirective,
+    /// `@oneOf` input objects.
+    OneOf,
+}
+
+impl FederationFeature {
+    /// Returns the name of the directive of this feature, such as `@key`.
+    pub fn directive_name(self) -> &'static str {
+        match self {
+            FederationFeature::Key => "@key",
+            FederationFeature::Tag => "@tag",
+            FederationFeature::Shareable => "@shareable",
+            FederationFeature::Inaccessible => "@inaccessible",
+            FederationFeature::Override => "@override",
+            FederationFeature::External => "@external",
+            FederationFeature::Provides => "@provides",
+            FederationFeature::Requires => "@requires",
+            FederationFeature::ComposeDirective => "@composeDirective",
+            FederationFeature::OneOf => "@oneOf",
+        }
+    }
+}
+
+/// A specification linked to the schema with
+/// `extend schema @link(url: ..., as: ..., import: [...])`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkSpec {
+    /// The URL of the specification.
+    pub url: String,
+    /// The namespace of the specification, exported as the `as` argument.
+    pub alias: Option<String>,
+    /// The imported directives and types.
+    pub import: Vec<String>,
+}
+
+impl LinkSpec {
+    /// Create a `LinkSpec` for the specification at `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            alias: None,
+            import: Vec::new(),
+        }
+    }
+
+    /// Set the namespace of the specification.
+    #[must_use]
+    pub fn alias(self, alias: impl Into<String>) -> Self {
+        Self {
+            alias: Some(alias.into()),
+            ..self
+        }
+    }
+
+    /// Import a directive or a type from the specification.
+    #[must_use]
+    pub fn import(mut self, name: impl Into<String>) -> Self {
+        self.import.push(name.into());
+        self
+    }
+}
+
+/// An error found while validating the `@link` imports of a [`Registry`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LinkError {
+    /// A federation directive is used but not imported.
+    #[error("The directive `{0}` is used but not imported by `@link`")]
+    NotImported(String),
+
+    /// A directive to compose with `@composeDirective` does not exist.
+    #[error("The composed directive `@{0}` does not exist")]
+    UnknownComposedDirective(String),
+}
+
+impl Registry {
+    /// Returns the directives imported by the federation 2 `@link` directive.
+    ///
+    /// Unless the imports have been set explicitly, only the federation
+    /// directives used in the registry are imported.
+    pub fn imported_link_directives(&self) -> Vec<&str> {
+        match &self.link_imports {
+            Some(imports) => imports.iter().map(String::as_str).collect(),
+            None => {
+                let used = self.used_federation_directives();
+                FEDERATION_DIRECTIVES
+                    .iter()
+                    .copied()
+                    .filter(|directive| used.contains(directive))
+                    .collect()
+            }
+        }
+    }
+
+    /// Link an additional specification to the schema.
+    pub fn add_link_spec(&mut self, spec: LinkSpec) {
+        self.link_specs.push(spec);
+    }
+
+    /// Compose the custom directive `name`, with or without the leading `@`,
+    /// in the federation 2 supergraph, see
+    /// [`SchemaBuilder::compose_directive`](crate::SchemaBuilder::compose_directive).
+    pub fn add_compose_directive(&mut self, name: &str) {
+        let name = name.strip_prefix('@').unwrap_or(name);
+        if !self
+            .compose_directives
+            .iter()
+            .any(|composed| composed == name)
+        {
+            self.compose_directives.push(name.to_string());
+        }
+    }
+
+    /// Check that every directive composed with `@composeDirective` is
+    /// registered.
+    pub fn validate_compose_directives(&self) -> Result<(), Vec<LinkError>> {
+        let errors = self
+            .compose_directives
+            .iter()
+            .filter(|name| !self.directives.contains_key(*name))
+            .map(|name| LinkError::UnknownComposedDirective(name.clone()))
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns all the specifications linked to the schema: the federation 2
+    /// specification if the `@link` directive is enabled, followed by the
+    /// specifications added with [`Registry::add_link_spec`].
+    ///
+    /// The federation 2.1 specification is linked if a directive is composed
+    /// with `@composeDirective`.
+    pub fn links(&self) -> Vec<LinkSpec> {
+        let federation = if self.enable_apollo_link {
+            Some(LinkSpec {
+                url: if self.compose_directives.is_empty() {
+                    FEDERATION_SPEC_URL
+                } else {
+                    FEDERATION_V2_1_SPEC_URL
+                }
+                .to_string(),
+                alias: None,
+                import: self
+                    .imported_link_directives()
+                    .into_iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            })
+        } else {
+            None
+        };
+
+        federation
+            .into_iter()
+            .chain(self.link_specs.iter().cloned())
+            .collect()
+    }
+
+    /// Check that every federation directive used in the registry is imported
+    /// by the federation 2 `@link` directive.
+    pub fn validate_link_imports(&self) -> Result<(), Vec<LinkError>> {
+        let imports = self.imported_link_directives();
+        let errors = self
+            .used_federation_directives()
+            .into_iter()
+            .filter(|directive| !imports.contains(directive))
+            .map(|directive| LinkError::NotImported(directive.to_string()))
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns the federation directives used in the registry that the
+    /// specified federation version does not support, such as `@override`
+    /// in federation 1.
+    ///
+    /// These directives are omitted when exporting SDL for that version.
+    pub fn unsupported_federation_directives(&self, version: FederationVersion) -> Vec<&str> {
+        match version {
+            FederationVersion::V1 => self
+                .used_federation_directives()
+                .into_iter()
+                .filter(|directive| FEDERATION_V2_DIRECTIVES.contains(directive))
+                .collect(),
+            FederationVersion::V2 => Vec::new(),
+        }
+    }
+
+    /// Check that no accessible field, argument or input field references an
+    /// `@inaccessible` type.
+    ///
+    /// A field is accessible if neither it nor the type defining it is
+    /// `@inaccessible`, and an argument is accessible if it, its field and
+    /// the type defining the field are all accessible.
+    pub fn validate_inaccessible(&self) -> Result<(), Vec<SchemaError>> {
+        let mut errors = Vec::new();
+
+        let mut check = |type_name: &str, field_name: &str, target: String, ty: &str| {
+            if let Some(referenced) = self.concrete_type_by_name(ty) {
+                if referenced.is_inaccessible() {
+                    errors.push(SchemaError {
+                        field_name: Some(field_name.to_string()),
+                        ..SchemaError::new(
+                            type_name,
+                            format!(
+                                "{} is accessible, but its type `{}` is `@inaccessible`",
+                                target,
+                                referenced.name()
+                            ),
+                        )
+                    });
+                }
+            }
+        };
+
+        for ty in self.types.values() {
+            if ty.name().starts_with("__") || ty.is_inaccessible() {
+                continue;
+            }
+
+            match ty {
+                MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => {
+                    for field in fields.values() {
+                        if field.inaccessible
+                            || field.name.starts_with("__")
+                            || matches!(&*field.name, "_service" | "_entities")
+                        {
+                            continue;
+                        }
+                        check(
+                            ty.name(),
+                            &field.name,
+                            format!("The field `{}.{}`", ty.name(), field.name),
+                            &field.ty,
+                        );
+                        for arg in field.args.values().filter(|arg| !arg.inaccessible) {
+                            check(
+                                ty.name(),
+                                &field.name,
+                                format!(
+                                    "The argument `{}.{}({}:)`",
+                                    ty.name(),
+                                    field.name,
+                                    arg.name
+                                ),
+                                &arg.ty,
+                            );
+                        }
+                    }
+                }
+                MetaType::InputObject { input_fields, .. } => {
+                    for field in input_fields.values().filter(|field| !field.inaccessible) {
+                        check(
+                            ty.name(),
+                            &field.name,
+                            format!("The input field `{}.{}`", ty.name(), field.name),
+                            &field.ty,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns the name and the keys of all federation entities, that is the
+    /// objects and interfaces with at least one `@key`, sorted by name.
+    pub fn entities(&self) -> Vec<(&str, &[MetaKey])> {
+        self.types
+            .values()
+            .filter_map(|ty| match ty.keys() {
+                Some(keys) if !keys.is_empty() => Some((ty.name(), keys)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns all federation entities, see [`Registry::entities`].
+    pub fn entity_types(&self) -> Vec<&MetaType> {
+        self.entities()
+            .into_iter()
+            .map(|(name, _)| &self.types[name])
+            .collect()
+    }
+
+    /// Returns the names of the fields of the root type of the specified
+    /// operation type, excluding introspection and federation fields.
+    pub fn root_field_names(&self, operation: OperationType) -> Vec<&str> {
+        let type_name = match operation {
+            OperationType::Query => Some(self.query_type.as_str()),
+            OperationType::Mutation => self.mutation_type.as_deref(),
+            OperationType::Subscription => self.subscription_type.as_deref(),
+        };
+
+        type_name
+            .and_then(|type_name| self.types.get(type_name))
+            .and_then(|ty| ty.fields())
+            .map(|fields| {
+                fields
+                    .keys()
+                    .map(String::as_str)
+                    .filter(|name| {
+                        !name.starts_with("__") && !matches!(*name, "_service" | "_entities")
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the federation directives (such as `@key` or `@shareable`)
+    /// used anywhere in the registry.
+    pub fn used_federation_directives(&self) -> BTreeSet<&'static str> {
+        self.used_feature_directives()
+            .into_iter()
+            .filter(|feature| *feature != FederationFeature::OneOf)
+            .map(FederationFeature::directive_name)
+            .collect()
+    }
+
+    /// Returns the federation features used anywhere in the registry, such
+    /// as entity keys or `@shareable` fields.
+    ///
+    /// This includes `@oneOf` input objects, which are not a federation
+    /// directive but must be supported by the gateway.
+    pub fn used_feature_directives(&self) -> BTreeSet<FederationFeature> {
+        let mut directives = BTreeSet::new();
+
+        if !self.compose_directives.is_empty() {
+            directives.insert(FederationFeature::ComposeDirective);
+        }
+
+        fn add_tags(
+            directives: &mut BTreeSet<FederationFeature>,
+            inaccessible: bool,
+            tags: &[&str],
+        ) {
+            if inaccessible {
+                directives.insert(FederationFeature::Inaccessible);
+            }
+            if !tags.is_empty() {
+                directives.insert(FederationFeature::Tag);
+            }
+        }
+
+        fn add_input_values<'a>(
+            directives: &mut BTreeSet<FederationFeature>,
+            values: impl IntoIterator<Item = &'a MetaInputValue>,
+        ) {
+            for value in values {
+                add_tags(directives, value.inaccessible, value.tags);
+            }
+        }
+
+        fn add_fields<'a>(
+            directives: &mut BTreeSet<FederationFeature>,
+            fields: impl IntoIterator<Item = &'a MetaField>,
+        ) {
+            for field in fields {
+                if field.external {
+                    directives.insert(FederationFeature::External);
+                }
+                if field.requires.is_some() {
+                    directives.insert(FederationFeature::Requires);
+                }
+                if field.provides.is_some() {
+                    directives.insert(FederationFeature::Provides);
+                }
+                if field.shareable {
+                    directives.insert(FederationFeature::Shareable);
+                }
+                if field.override_from.is_some() {
+                    directives.insert(FederationFeature::Override);
+                }
+                add_tags(directives, field.inaccessible, field.tags);
+                add_input_values(directives, field.args.values());
+            }
+        }
+
+        for ty in self.types.values() {
+            if ty.name().starts_with("__") {
+                continue;
+            }
+
+            if matches!(ty.keys(), Some(keys) if !keys.is_empty()) {
+                directives.insert(FederationFeature::Key);
+                if self.reference_stubs.contains(ty.name()) {
+                    // The key fields of reference stubs are exported as `@external`.
+                    directives.insert(FederationFeature::External);
+                }
+            }
+
+            match ty {
+                MetaType::Scalar {
+                    inaccessible, tags, ..
+                } => add_tags(&mut directives, *inaccessible, tags),
+                MetaType::Union {
+                    inaccessible,
+                    tags,
+                    possible_type_tags,
+                    ..
+                } => {
+                    add_tags(&mut directives, *inaccessible, tags);
+                    for tags in possible_type_tags.values() {
+                        add_tags(&mut directives, false, tags);
+                    }
+                }
+                MetaType::Object {
+                    fields,
+                    shareable,
+                    inaccessible,
+                    tags,
+                    ..
+                } => {
+                    if *shareable {
+                        directives.insert(FederationFeature::Shareable);
+                    }
+                    add_tags(&mut directives, *inaccessible, tags);
+                    add_fields(&mut directives, fields.values());
+                }
+                MetaType::Interface {
+                    fields,
+                    inaccessible,
+                    tags,
+                    ..
+                } => {
+                    add_tags(&mut directives, *inaccessible, tags);
+                    add_fields(&mut directives, fields.values());
+                }
+                MetaType::Enum {
+                    enum_values,
+                    inaccessible,
+                    tags,
+                    ..
+                } => {
+                    add_tags(&mut directives, *inaccessible, tags);
+                    for value in enum_values.values() {
+                        add_tags(&mut directives, value.inaccessible, value.tags);
+                    }
+                }
+                MetaType::InputObject {
+                    input_fields,
+                    inaccessible,
+                    tags,
+                    oneof,
+                    ..
+                } => {
+                    if *oneof {
+                        directives.insert(FederationFeature::OneOf);
+                    }
+                    add_tags(&mut directives, *inaccessible, tags);
+                    add_input_values(&mut directives, input_fields.values());
+                }
+            }
+        }
+
+        directives
+    }
+
+    /// Export a JSON manifest of this subgraph for gateway configuration.
+    ///
+    /// The manifest lists the entity types with their keys, the root field
+    /// names of each operation type and the federation directives used by
+    /// the schema. Keys of reference stubs (see
+    /// [`SchemaBuilder::export_reference_stub`](crate::SchemaBuilder::export_reference_stub)),
+    /// and keys added with `resolvable: false`, are not resolvable by this
+    /// subgraph.
+    pub fn federation_manifest(&self) -> serde_json::Value {
+        let entities = self
+            .entity_types()
+            .into_iter()
+            .map(|ty| {
+                let resolvable = !self.reference_stubs.contains(ty.name());
+                let keys = ty
+                    .keys()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|key| {
+                        json!({ "fields": key.fields, "resolvable": resolvable && key.resolvable })
+                    })
+                    .collect::<Vec<_>>();
+                json!({ "name": ty.name(), "keys": keys })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "entities": entities,
+            "rootFields": {
+                "query": self.root_field_names(OperationType::Query),
+                "mutation": self.root_field_names(OperationType::Mutation),
+                "subscription": self.root_field_names(OperationType::Subscription),
+            },
+            "directives": self.used_federation_directives(),
+        })
+    }
+}
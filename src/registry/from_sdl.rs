@@ -0,0 +1,632 @@
+use std::{collections::HashSet, sync::Mutex};
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use thiserror::Error;
+
+use crate::{
+    model::{__DirectiveLocation, __Schema},
+    parser::{
+        self,
+        types::{
+            ConstDirective, DirectiveDefinition, DirectiveLocation, FieldDefinition,
+            InputValueDefinition, SchemaDefinition, TypeDefinition, TypeKind, TypeSystemDefinition,
+        },
+        Positioned,
+    },
+    registry::{
+        Deprecation, MetaDirective, MetaDirectiveInvocation, MetaEnumValue, MetaField,
+        MetaInputValue, MetaKey, MetaType, MetaTypeId, Registry,
+    },
+    InputType, Name, OutputType, Value, ID,
+};
+
+/// The directives that are converted into properties of the registry, instead
+/// of directive applications.
+const BUILTIN_DIRECTIVES: &[&str] = &[
+    "deprecated",
+    "specifiedBy",
+    "oneOf",
+    "key",
+    "external",
+    "requires",
+    "provides",
+    "shareable",
+    "inaccessible",
+    "override",
+];
+
+/// An error returned by [`Registry::from_sdl`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FromSdlError {
+    /// The SDL is not a valid GraphQL service document.
+    #[error("{0}")]
+    Parse(#[from] parser::Error),
+
+    /// A type is defined more than once.
+    #[error("The type `{0}` is defined more than once")]
+    DuplicateType(String),
+
+    /// A directive is defined more than once.
+    #[error("The directive `@{0}` is defined more than once")]
+    DuplicateDirective(String),
+
+    /// A type extension does not have the kind of the type it extends.
+    #[error("The type `{name}` is a `{existing}`, but it is extended as a `{extension}`")]
+    ExtensionKindMismatch {
+        /// The name of the type.
+        name: String,
+        /// The kind of the extended type.
+        existing: MetaTypeId,
+        /// The kind of the extension.
+        extension: MetaTypeId,
+    },
+}
+
+/// Returns a static copy of `s`, which only leaks one string per distinct
+/// value.
+fn intern(s: &str) -> &'static str {
+    static INTERNED: Lazy<Mutex<HashSet<&'static str>>> = Lazy::new(Default::default);
+
+    let mut interned = INTERNED.lock().unwrap();
+    match interned.get(s) {
+        Some(s) => s,
+        None => {
+            let s: &'static str = Box::leak(s.to_string().into_boxed_str());
+            interned.insert(s);
+            s
+        }
+    }
+}
+
+fn find_directive<'a>(
+    directives: &'a [Positioned<ConstDirective>],
+    name: &str,
+) -> Option<&'a ConstDirective> {
+    directives
+        .iter()
+        .map(|directive| &directive.node)
+        .find(|directive| directive.name.node == name)
+}
+
+fn has_directive(directives: &[Positioned<ConstDirective>], name: &str) -> bool {
+    find_directive(directives, name).is_some()
+}
+
+/// Returns the string argument `arg` of the directive `name`.
+fn directive_str(
+    directives: &[Positioned<ConstDirective>],
+    name: &str,
+    arg: &str,
+) -> Option<&'static str> {
+    match find_directive(directives, name)?
+        .get_argument(arg)
+        .map(|value| &value.node)
+    {
+        Some(Value::String(s)) => Some(intern(s)),
+        _ => None,
+    }
+}
+
+fn deprecation(directives: &[Positioned<ConstDirective>]) -> Deprecation {
+    if has_directive(directives, "deprecated") {
+        Deprecation::Deprecated {
+            reason: directive_str(directives, "deprecated", "reason"),
+        }
+    } else {
+        Deprecation::NoDeprecated
+    }
+}
+
+fn directive_invocations(
+    directives: &[Positioned<ConstDirective>],
+) -> Vec<MetaDirectiveInvocation> {
+    directives
+        .iter()
+        .map(|directive| &directive.node)
+        .filter(|directive| !BUILTIN_DIRECTIVES.contains(&directive.name.node.as_str()))
+        .map(|directive| MetaDirectiveInvocation {
+            name: directive.name.node.to_string(),
+            args: directive
+                .arguments
+                .iter()
+                .map(|(name, value)| (name.node.to_string(), value.node.clone()))
+                .collect(),
+        })
+        .collect()
+}
+
+fn keys(directives: &[Positioned<ConstDirective>]) -> Option<Vec<MetaKey>> {
+    let keys = directives
+        .iter()
+        .map(|directive| &directive.node)
+        .filter(|directive| directive.name.node == "key")
+        .filter_map(|directive| match directive.get_argument("fields") {
+            Some(Positioned {
+                node: Value::String(fields),
+                ..
+            }) => Some(MetaKey {
+                fields: fields.clone(),
+                resolvable: !matches!(
+                    directive.get_argument("resolvable"),
+                    Some(Positioned {
+                        node: Value::Boolean(false),
+                        ..
+                    })
+                ),
+            }),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if keys.is_empty() {
+        None
+    } else {
+        Some(keys)
+    }
+}
+
+fn input_values(
+    definitions: &[Positioned<InputValueDefinition>],
+) -> IndexMap<String, MetaInputValue> {
+    definitions
+        .iter()
+        .map(|definition| {
+            let definition = &definition.node;
+            (
+                definition.name.node.to_string(),
+                MetaInputValue {
+                    name: definition.name.node.to_string(),
+                    description: definition.description.as_ref().map(|s| s.node.clone()),
+                    ty: definition.ty.node.to_string(),
+                    default_value: definition
+                        .default_value
+                        .as_ref()
+                        .map(|value| value.node.to_string()),
+                    deprecation: deprecation(&definition.directives),
+                    visible: None,
+                    inaccessible: has_directive(&definition.directives, "inaccessible"),
+                    tags: &[],
+                    is_secret: false,
+                },
+            )
+        })
+        .collect()
+}
+
+fn fields(definitions: &[Positioned<FieldDefinition>]) -> IndexMap<String, MetaField> {
+    definitions
+        .iter()
+        .map(|definition| {
+            let definition = &definition.node;
+            let directives = &definition.directives;
+            (
+                definition.name.node.to_string(),
+                MetaField {
+                    description: definition.description.as_ref().map(|s| s.node.clone()),
+                    args: input_values(&definition.arguments),
+                    deprecation: deprecation(directives),
+                    external: has_directive(directives, "external"),
+                    requires: directive_str(directives, "requires", "fields"),
+                    provides: directive_str(directives, "provides", "fields"),
+                    shareable: has_directive(directives, "shareable"),
+                    inaccessible: has_directive(directives, "inaccessible"),
+                    override_from: directive_str(directives, "override", "from"),
+                    directive_invocations: directive_invocations(directives),
+                    ..MetaField::new(
+                        definition.name.node.as_str(),
+                        definition.ty.node.to_string(),
+                    )
+                },
+            )
+        })
+        .collect()
+}
+
+fn directive_location(location: DirectiveLocation) -> __DirectiveLocation {
+    match location {
+        DirectiveLocation::Query => __DirectiveLocation::QUERY,
+        DirectiveLocation::Mutation => __DirectiveLocation::MUTATION,
+        DirectiveLocation::Subscription => __DirectiveLocation::SUBSCRIPTION,
+        DirectiveLocation::Field => __DirectiveLocation::FIELD,
+        DirectiveLocation::FragmentDefinition => __DirectiveLocation::FRAGMENT_DEFINITION,
+        DirectiveLocation::FragmentSpread => __DirectiveLocation::FRAGMENT_SPREAD,
+        DirectiveLocation::InlineFragment => __DirectiveLocation::INLINE_FRAGMENT,
+        DirectiveLocation::VariableDefinition => __DirectiveLocation::VARIABLE_DEFINITION,
+        DirectiveLocation::Schema => __DirectiveLocation::SCHEMA,
+        DirectiveLocation::Scalar => __DirectiveLocation::SCALAR,
+        DirectiveLocation::Object => __DirectiveLocation::OBJECT,
+        DirectiveLocation::FieldDefinition => __DirectiveLocation::FIELD_DEFINITION,
+        DirectiveLocation::ArgumentDefinition => __DirectiveLocation::ARGUMENT_DEFINITION,
+        DirectiveLocation::Interface => __DirectiveLocation::INTERFACE,
+        DirectiveLocation::Union => __DirectiveLocation::UNION,
+        DirectiveLocation::Enum => __DirectiveLocation::ENUM,
+        DirectiveLocation::EnumValue => __DirectiveLocation::ENUM_VALUE,
+        DirectiveLocation::InputObject => __DirectiveLocation::INPUT_OBJECT,
+        DirectiveLocation::InputFieldDefinition => __DirectiveLocation::INPUT_FIELD_DEFINITION,
+    }
+}
+
+fn directive(definition: &DirectiveDefinition) -> MetaDirective {
+    MetaDirective {
+        name: intern(&definition.name.node),
+        description: definition.description.as_ref().map(|s| s.node.clone()),
+        locations: definition
+            .locations
+            .iter()
+            .map(|location| directive_location(location.node))
+            .collect(),
+        args: input_values(&definition.arguments),
+        is_repeatable: definition.is_repeatable,
+        visible: None,
+        deprecation: Deprecation::NoDeprecated,
+    }
+}
+
+/// Converts a type definition, `extends` is `true` for a type extension
+/// without a definition in the document.
+fn meta_type(definition: &TypeDefinition, extends: bool) -> MetaType {
+    let name = definition.name.node.to_string();
+    let description = definition.description.as_ref().map(|s| s.node.clone());
+    let directives = &definition.directives;
+    let inaccessible = has_directive(directives, "inaccessible");
+
+    match &definition.kind {
+        TypeKind::Scalar => MetaType::Scalar {
+            name,
+            description,
+            is_valid: |_| true,
+            round_trip: None,
+            visible: None,
+            inaccessible,
+            tags: &[],
+            specified_by_url: directive_str(directives, "specifiedBy", "url"),
+        },
+        TypeKind::Object(object) => MetaType::Object {
+            name,
+            description,
+            fields: fields(&object.fields),
+            cache_control: Default::default(),
+            extends,
+            shareable: has_directive(directives, "shareable"),
+            keys: keys(directives),
+            visible: None,
+            inaccessible,
+            tags: &[],
+            is_subscription: false,
+            rust_typename: "",
+            directive_invocations: directive_invocations(directives),
+        },
+        TypeKind::Interface(interface) => MetaType::Interface {
+            name,
+            description,
+            fields: fields(&interface.fields),
+            possible_types: Default::default(),
+            extends,
+            keys: keys(directives),
+            visible: None,
+            inaccessible,
+            tags: &[],
+            rust_typename: "",
+            directive_invocations: directive_invocations(directives),
+        },
+        TypeKind::Union(union) => MetaType::Union {
+            name,
+            description,
+            possible_types: union
+                .members
+                .iter()
+                .map(|member| member.node.to_string())
+                .collect(),
+            possible_type_tags: Default::default(),
+            visible: None,
+            inaccessible,
+            tags: &[],
+            rust_typename: "",
+        },
+        TypeKind::Enum(enum_type) => MetaType::Enum {
+            name,
+            description,
+            enum_values: enum_type
+                .values
+                .iter()
+                .map(|value| {
+                    let value = &value.node;
+                    (
+                        value.value.node.to_string(),
+                        MetaEnumValue {
+                            name: value.value.node.to_string(),
+                            description: value.description.as_ref().map(|s| s.node.clone()),
+                            deprecation: deprecation(&value.directives),
+                            visible: None,
+                            inaccessible: has_directive(&value.directives, "inaccessible"),
+                            tags: &[],
+                            directive_invocations: directive_invocations(&value.directives),
+                        },
+                    )
+                })
+                .collect(),
+            visible: None,
+            inaccessible,
+            tags: &[],
+            rust_typename: "",
+        },
+        TypeKind::InputObject(input_object) => MetaType::InputObject {
+            name,
+            description,
+            input_fields: input_values(&input_object.fields),
+            visible: None,
+            inaccessible,
+            tags: &[],
+            rust_typename: "",
+            oneof: has_directive(directives, "oneOf"),
+        },
+    }
+}
+
+/// Merges the type extension `extension` into `ty`.
+fn extend_type(ty: &mut MetaType, extension: MetaType) -> Result<(), FromSdlError> {
+    match (ty, extension) {
+        (
+            MetaType::Scalar {
+                inaccessible,
+                specified_by_url,
+                ..
+            },
+            MetaType::Scalar {
+                inaccessible: extension_inaccessible,
+                specified_by_url: extension_specified_by_url,
+                ..
+            },
+        ) => {
+            *inaccessible |= extension_inaccessible;
+            if extension_specified_by_url.is_some() {
+                *specified_by_url = extension_specified_by_url;
+            }
+        }
+        (
+            MetaType::Object {
+                fields,
+                shareable,
+                keys,
+                inaccessible,
+                directive_invocations,
+                ..
+            },
+            MetaType::Object {
+                fields: extension_fields,
+                shareable: extension_shareable,
+                keys: extension_keys,
+                inaccessible: extension_inaccessible,
+                directive_invocations: extension_directive_invocations,
+                ..
+            },
+        ) => {
+            fields.extend(extension_fields);
+            *shareable |= extension_shareable;
+            if let Some(extension_keys) = extension_keys {
+                keys.get_or_insert_with(Vec::new).extend(extension_keys);
+            }
+            *inaccessible |= extension_inaccessible;
+            directive_invocations.extend(extension_directive_invocations);
+        }
+        (
+            MetaType::Interface {
+                fields,
+                keys,
+                inaccessible,
+                directive_invocations,
+                ..
+            },
+            MetaType::Interface {
+                fields: extension_fields,
+                keys: extension_keys,
+                inaccessible: extension_inaccessible,
+                directive_invocations: extension_directive_invocations,
+                ..
+            },
+        ) => {
+            fields.extend(extension_fields);
+            if let Some(extension_keys) = extension_keys {
+                keys.get_or_insert_with(Vec::new).extend(extension_keys);
+            }
+            *inaccessible |= extension_inaccessible;
+            directive_invocations.extend(extension_directive_invocations);
+        }
+        (
+            MetaType::Union {
+                possible_types,
+                inaccessible,
+                ..
+            },
+            MetaType::Union {
+                possible_types: extension_possible_types,
+                inaccessible: extension_inaccessible,
+                ..
+            },
+        ) => {
+            possible_types.extend(extension_possible_types);
+            *inaccessible |= extension_inaccessible;
+        }
+        (
+            MetaType::Enum {
+                enum_values,
+                inaccessible,
+                ..
+            },
+            MetaType::Enum {
+                enum_values: extension_enum_values,
+                inaccessible: extension_inaccessible,
+                ..
+            },
+        ) => {
+            enum_values.extend(extension_enum_values);
+            *inaccessible |= extension_inaccessible;
+        }
+        (
+            MetaType::InputObject {
+                input_fields,
+                inaccessible,
+                oneof,
+                ..
+            },
+            MetaType::InputObject {
+                input_fields: extension_input_fields,
+                inaccessible: extension_inaccessible,
+                oneof: extension_oneof,
+                ..
+            },
+        ) => {
+            input_fields.extend(extension_input_fields);
+            *inaccessible |= extension_inaccessible;
+            *oneof |= extension_oneof;
+        }
+        (ty, extension) => {
+            return Err(FromSdlError::ExtensionKindMismatch {
+                name: ty.name().to_string(),
+                existing: ty.type_id(),
+                extension: extension.type_id(),
+            })
+        }
+    }
+    Ok(())
+}
+
+impl Registry {
+    /// Builds a registry from a GraphQL SDL document, for introspection and
+    /// tooling.
+    ///
+    /// The registry contains the types and directives defined by the
+    /// document, the built-in scalars and the introspection types. Type
+    /// extensions are merged into the type they extend, an object or
+    /// interface that is only extended is marked as such, like a federation
+    /// `extend type`. The root types are taken from the `schema` definition,
+    /// or are the types named `Query`, `Mutation` and `Subscription`.
+    ///
+    /// The scalars defined by the document accept any value. The
+    /// `@deprecated`, `@specifiedBy` and `@oneOf` directives, and the
+    /// federation directives, are converted into the corresponding
+    /// properties, other directives applied to objects, interfaces, fields
+    /// and enum values are kept as directive applications. The directives of
+    /// the `schema` definition are ignored.
+    pub fn from_sdl(sdl: &str) -> Result<Registry, FromSdlError> {
+        let document = parser::parse_schema(sdl)?;
+
+        let mut registry = Registry::default();
+        registry.add_include_and_skip_directives();
+        <bool as InputType>::create_type_info(&mut registry);
+        <i32 as InputType>::create_type_info(&mut registry);
+        <f32 as InputType>::create_type_info(&mut registry);
+        <String as InputType>::create_type_info(&mut registry);
+        <ID as InputType>::create_type_info(&mut registry);
+        <__Schema as OutputType>::create_type_info(&mut registry);
+
+        let mut schema = SchemaDefinition {
+            extend: false,
+            directives: Vec::new(),
+            query: None,
+            mutation: None,
+            subscription: None,
+        };
+        let mut extensions = Vec::new();
+        let mut defined = HashSet::new();
+        let mut defined_directives = HashSet::new();
+
+        for definition in &document.definitions {
+            match definition {
+                TypeSystemDefinition::Schema(definition) => {
+                    let definition = &definition.node;
+                    schema.query = definition.query.clone().or(schema.query);
+                    schema.mutation = definition.mutation.clone().or(schema.mutation);
+                    schema.subscription = definition.subscription.clone().or(schema.subscription);
+                }
+                TypeSystemDefinition::Type(definition) if definition.node.extend => {
+                    extensions.push(&definition.node);
+                }
+                TypeSystemDefinition::Type(definition) => {
+                    let name = definition.node.name.node.as_str();
+                    if super::is_builtin_scalar(name) {
+                        continue;
+                    }
+                    if !defined.insert(name) {
+                        return Err(FromSdlError::DuplicateType(name.to_string()));
+                    }
+                    registry
+                        .types
+                        .insert(name.to_string(), meta_type(&definition.node, false));
+                }
+                TypeSystemDefinition::Directive(definition) => {
+                    let name = definition.node.name.node.as_str();
+                    if !defined_directives.insert(name) {
+                        return Err(FromSdlError::DuplicateDirective(name.to_string()));
+                    }
+                    registry.add_directive(directive(&definition.node));
+                }
+            }
+        }
+
+        for extension in extensions {
+            let name = extension.name.node.as_str();
+            match registry.types.get_mut(name) {
+                Some(ty) => extend_type(ty, meta_type(extension, false))?,
+                None => {
+                    registry
+                        .types
+                        .insert(name.to_string(), meta_type(extension, true));
+                }
+            }
+        }
+
+        for definition in &document.definitions {
+            if let TypeSystemDefinition::Type(definition) = definition {
+                let implements = match &definition.node.kind {
+                    TypeKind::Object(object) => &object.implements,
+                    TypeKind::Interface(interface) => &interface.implements,
+                    _ => continue,
+                };
+                for interface in implements {
+                    registry.add_implements(&definition.node.name.node, &interface.node);
+                }
+            }
+        }
+
+        for (name, interfaces) in &registry.implements {
+            if !matches!(registry.types.get(name), Some(MetaType::Object { .. })) {
+                continue;
+            }
+            for interface in interfaces {
+                if let Some(MetaType::Interface { possible_types, .. }) =
+                    registry.types.get_mut(interface)
+                {
+                    possible_types.insert(name.clone());
+                }
+            }
+        }
+
+        let root_type = |name: Option<Positioned<Name>>, default: &str| {
+            name.map(|name| name.node.to_string()).or_else(|| {
+                registry
+                    .types
+                    .contains_key(default)
+                    .then(|| default.to_string())
+            })
+        };
+        let query_type = root_type(schema.query, "Query").unwrap_or_default();
+        let mutation_type = root_type(schema.mutation, "Mutation");
+        let subscription_type = root_type(schema.subscription, "Subscription");
+        registry.query_type = query_type;
+        registry.mutation_type = mutation_type;
+        registry.subscription_type = subscription_type;
+
+        if let Some(MetaType::Object {
+            is_subscription, ..
+        }) = registry
+            .subscription_type
+            .as_ref()
+            .and_then(|name| registry.types.get_mut(name))
+        {
+            *is_subscription = true;
+        }
+
+        Ok(registry)
+    }
+}
@@ -0,0 +1,216 @@
+use serde_json::{json, Value as JsonValue};
+
+use crate::{
+    registry::{MetaDirective, MetaField, MetaInputValue, MetaType, MetaTypeName, Registry},
+    schema::IntrospectionMode,
+    InputType,
+};
+
+fn type_kind(ty: &MetaType) -> &'static str {
+    match ty {
+        MetaType::Scalar { .. } => "SCALAR",
+        MetaType::Object { .. } => "OBJECT",
+        MetaType::Interface { .. } => "INTERFACE",
+        MetaType::Union { .. } => "UNION",
+        MetaType::Enum { .. } => "ENUM",
+        MetaType::InputObject { .. } => "INPUT_OBJECT",
+    }
+}
+
+impl Registry {
+    /// Serializes this registry into the result of the standard introspection
+    /// query, `{"data": {"__schema": {...}}}`, without executing a query.
+    ///
+    /// Deprecated fields, arguments, input fields and enum values are only
+    /// included if `include_deprecated` is `true`. Since there is no request
+    /// context, the `visible` functions are not evaluated and everything is
+    /// considered visible.
+    ///
+    /// Returns `None` if introspection is disabled.
+    pub fn to_json_introspection(&self, include_deprecated: bool) -> Option<JsonValue> {
+        if self.introspection_mode == IntrospectionMode::Disabled {
+            return None;
+        }
+
+        let root_type = |name: Option<&String>| match name {
+            Some(name) if self.types.contains_key(name) => json!({ "name": name }),
+            _ => JsonValue::Null,
+        };
+
+        let directives = self
+            .directives()
+            .map(|directive| self.directive_json(directive, include_deprecated))
+            .collect::<Vec<_>>();
+
+        Some(json!({
+            "data": {
+                "__schema": {
+                    "queryType": root_type(Some(&self.query_type)),
+                    "mutationType": root_type(self.mutation_type.as_ref()),
+                    "subscriptionType": root_type(self.subscription_type.as_ref()),
+                    "types": self
+                        .types
+                        .values()
+                        .map(|ty| self.full_type_json(ty, include_deprecated))
+                        .collect::<Vec<_>>(),
+                    "directives": directives,
+                }
+            }
+        }))
+    }
+
+    fn type_ref_json(&self, ty: &str) -> JsonValue {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => {
+                json!({ "kind": "NON_NULL", "name": null, "ofType": self.type_ref_json(ty) })
+            }
+            MetaTypeName::List(ty) => {
+                json!({ "kind": "LIST", "name": null, "ofType": self.type_ref_json(ty) })
+            }
+            MetaTypeName::Named(name) => json!({
+                "kind": self.types.get(name).map(type_kind),
+                "name": name,
+                "ofType": null,
+            }),
+        }
+    }
+
+    fn input_values_json<'a>(
+        &self,
+        input_values: impl IntoIterator<Item = &'a MetaInputValue>,
+        include_deprecated: bool,
+    ) -> Vec<JsonValue> {
+        input_values
+            .into_iter()
+            .filter(|input_value| include_deprecated || !input_value.deprecation.is_deprecated())
+            .map(|input_value| {
+                json!({
+                    "name": input_value.name,
+                    "description": input_value.description,
+                    "type": self.type_ref_json(&input_value.ty),
+                    "defaultValue": input_value.default_value,
+                    "isDeprecated": input_value.deprecation.is_deprecated(),
+                    "deprecationReason": input_value.deprecation.reason(),
+                })
+            })
+            .collect()
+    }
+
+    fn field_json(&self, field: &MetaField, include_deprecated: bool) -> JsonValue {
+        json!({
+            "name": field.name,
+            "description": field.description,
+            "args": self.input_values_json(field.args.values(), include_deprecated),
+            "type": self.type_ref_json(&field.ty),
+            "isDeprecated": field.deprecation.is_deprecated(),
+            "deprecationReason": field.deprecation.reason(),
+        })
+    }
+
+    fn full_type_json(&self, ty: &MetaType, include_deprecated: bool) -> JsonValue {
+        let (description, specified_by_url) = match ty {
+            MetaType::Scalar {
+                description,
+                specified_by_url,
+                ..
+            } => (description, *specified_by_url),
+            MetaType::Object { description, .. }
+            | MetaType::Interface { description, .. }
+            | MetaType::Union { description, .. }
+            | MetaType::Enum { description, .. }
+            | MetaType::InputObject { description, .. } => (description, None),
+        };
+
+        let fields = ty.fields().map(|fields| {
+            fields
+                .values()
+                .filter(|field| {
+                    (include_deprecated || !field.deprecation.is_deprecated())
+                        && !field.name.starts_with("__")
+                })
+                .map(|field| self.field_json(field, include_deprecated))
+                .collect::<Vec<_>>()
+        });
+
+        let input_fields = match ty {
+            MetaType::InputObject { input_fields, .. } => {
+                Some(self.input_values_json(input_fields.values(), include_deprecated))
+            }
+            _ => None,
+        };
+
+        let interfaces = match ty {
+            MetaType::Object { name, .. } => Some(
+                self.implements
+                    .get(name)
+                    .into_iter()
+                    .flatten()
+                    .map(|name| self.type_ref_json(name))
+                    .collect::<Vec<_>>(),
+            ),
+            _ => None,
+        };
+
+        let enum_values = match ty {
+            MetaType::Enum { enum_values, .. } => Some(
+                enum_values
+                    .values()
+                    .filter(|value| include_deprecated || !value.deprecation.is_deprecated())
+                    .map(|value| {
+                        json!({
+                            "name": value.name,
+                            "description": value.description,
+                            "isDeprecated": value.deprecation.is_deprecated(),
+                            "deprecationReason": value.deprecation.reason(),
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            _ => None,
+        };
+
+        let possible_types = match ty {
+            MetaType::Interface { possible_types, .. } | MetaType::Union { possible_types, .. } => {
+                Some(
+                    possible_types
+                        .iter()
+                        .map(|name| self.type_ref_json(name))
+                        .collect::<Vec<_>>(),
+                )
+            }
+            _ => None,
+        };
+
+        let one_of = match ty {
+            MetaType::InputObject { oneof, .. } => Some(*oneof),
+            _ => None,
+        };
+
+        json!({
+            "kind": type_kind(ty),
+            "name": ty.name(),
+            "description": description,
+            "specifiedByURL": specified_by_url,
+            "fields": fields,
+            "inputFields": input_fields,
+            "interfaces": interfaces,
+            "enumValues": enum_values,
+            "possibleTypes": possible_types,
+            "oneOf": one_of,
+        })
+    }
+
+    fn directive_json(&self, directive: &MetaDirective, include_deprecated: bool) -> JsonValue {
+        json!({
+            "name": directive.name,
+            "description": directive.description,
+            "locations": directive
+                .locations
+                .iter()
+                .map(|location| location.to_value().into_json().unwrap_or_default())
+                .collect::<Vec<_>>(),
+            "args": self.input_values_json(directive.args.values(), include_deprecated),
+            "isRepeatable": directive.is_repeatable,
+        })
+    }
+}
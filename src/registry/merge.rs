@@ -0,0 +1,274 @@
+use std::collections::{HashMap, HashSet};
+
+use thiserror::Error;
+
+use crate::registry::{
+    deduplicate::{directive_eq, field_eq},
+    normalize::set_type_name,
+    MetaField, MetaType, MetaTypeId, Registry,
+};
+
+/// An error returned by [`Registry::merge`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MergeError {
+    /// Both registries define a type with the same name but a different kind.
+    #[error("The type `{name}` is a `{existing}` in one registry and a `{other}` in the other")]
+    KindMismatch {
+        /// The name of the type.
+        name: String,
+        /// The kind of the type in the registry being merged into.
+        existing: MetaTypeId,
+        /// The kind of the type in the merged registry.
+        other: MetaTypeId,
+    },
+
+    /// Both registries define a field with the same name but a different
+    /// definition.
+    #[error("The field `{type_name}.{field_name}` is defined differently in both registries")]
+    FieldConflict {
+        /// The name of the type.
+        type_name: String,
+        /// The name of the field.
+        field_name: String,
+    },
+
+    /// Both registries define a scalar, union, enum or input object with the
+    /// same name but a different definition.
+    #[error("The type `{name}` is defined differently in both registries")]
+    TypeConflict {
+        /// The name of the type.
+        name: String,
+    },
+
+    /// A root type of the merged registry would be renamed to the root type
+    /// name of the registry being merged into, but the merged registry
+    /// already defines another type with that name.
+    #[error("The merged registry already defines a type named `{name}`")]
+    RootTypeConflict {
+        /// The name of the root type.
+        name: String,
+    },
+
+    /// Both registries define a directive with the same name but a different
+    /// definition.
+    #[error("The directive `@{name}` is defined differently in both registries")]
+    DirectiveConflict {
+        /// The name of the directive.
+        name: String,
+    },
+}
+
+/// Checks that the fields defined by both types are identical.
+fn check_fields<'a>(
+    type_name: &str,
+    fields: impl Iterator<Item = &'a MetaField>,
+    other_fields: &HashMap<&str, &MetaField>,
+) -> Result<(), MergeError> {
+    for field in fields {
+        if field.name.starts_with("__") {
+            continue;
+        }
+        match other_fields.get(field.name.as_str()) {
+            Some(other_field) if !field_eq(field, other_field) => {
+                return Err(MergeError::FieldConflict {
+                    type_name: type_name.to_string(),
+                    field_name: field.name.clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+impl Registry {
+    /// Merges the types, directives and interface implementations of `other`
+    /// into this registry.
+    ///
+    /// Objects and interfaces defined by both registries get the union of
+    /// their fields, fields defined by both must be identical. Other types
+    /// defined by both registries must be structurally identical (see
+    /// [`MetaType::structurally_eq`]). The root types of `other` are merged
+    /// into the root types of this registry, even if their names differ.
+    /// Types whose names are in `ignore_name_conflicts` of either registry are
+    /// never checked, the definition of this registry is kept. Directives
+    /// defined by both registries must be identical.
+    ///
+    /// Visibility functions are compared by address, so fields using the same
+    /// function are identical, but the same function may have a different
+    /// address in different codegen units and then be reported as a conflict.
+    ///
+    /// The registry is left unchanged if an error is returned.
+    pub fn merge(&mut self, mut other: Registry) -> Result<(), MergeError> {
+        // Use the root type names of this registry.
+        let mut renames = HashMap::new();
+        for (name, other_name) in [
+            (Some(&self.query_type), Some(&other.query_type)),
+            (self.mutation_type.as_ref(), other.mutation_type.as_ref()),
+            (
+                self.subscription_type.as_ref(),
+                other.subscription_type.as_ref(),
+            ),
+        ] {
+            if let (Some(name), Some(other_name)) = (name, other_name) {
+                if !name.is_empty() && !other_name.is_empty() && name != other_name {
+                    renames.insert(other_name.clone(), name.clone());
+                }
+            }
+        }
+        for name in renames.values() {
+            if other.types.contains_key(name) && !renames.contains_key(name) {
+                return Err(MergeError::RootTypeConflict { name: name.clone() });
+            }
+        }
+        if !renames.is_empty() {
+            other.types = std::mem::take(&mut other.types)
+                .into_iter()
+                .map(|(name, mut ty)| match renames.get(&name) {
+                    Some(new_name) => {
+                        set_type_name(&mut ty, new_name.clone());
+                        (new_name.clone(), ty)
+                    }
+                    None => (name, ty),
+                })
+                .collect();
+            other.rename_type_references(&renames);
+        }
+
+        let ignored = self
+            .ignore_name_conflicts
+            .union(&other.ignore_name_conflicts)
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        for (name, other_ty) in &other.types {
+            let ty = match self.types.get(name) {
+                Some(ty) if !ignored.contains(name) => ty,
+                _ => continue,
+            };
+
+            if ty.type_id() != other_ty.type_id() {
+                return Err(MergeError::KindMismatch {
+                    name: name.clone(),
+                    existing: ty.type_id(),
+                    other: other_ty.type_id(),
+                });
+            }
+
+            match (ty.fields(), other_ty) {
+                (
+                    Some(fields),
+                    MetaType::Object {
+                        fields: other_fields,
+                        ..
+                    }
+                    | MetaType::Interface {
+                        fields: other_fields,
+                        ..
+                    },
+                ) => check_fields(
+                    name,
+                    fields.values(),
+                    &other_fields
+                        .values()
+                        .map(|field| (field.name.as_str(), field))
+                        .collect(),
+                )?,
+                _ => {
+                    if !ty.structurally_eq(other_ty) {
+                        return Err(MergeError::TypeConflict { name: name.clone() });
+                    }
+                }
+            }
+        }
+
+        for (name, other_directive) in &other.directives {
+            match self.directives.get(name) {
+                Some(directive) if !directive_eq(directive, other_directive) => {
+                    return Err(MergeError::DirectiveConflict { name: name.clone() });
+                }
+                _ => {}
+            }
+        }
+
+        for (name, other_ty) in other.types {
+            match self.types.get_mut(&name) {
+                Some(ty) => {
+                    if ignored.contains(&name) {
+                        continue;
+                    }
+                    match (ty, other_ty) {
+                        (
+                            MetaType::Object { fields, .. },
+                            MetaType::Object {
+                                fields: other_fields,
+                                ..
+                            },
+                        ) => {
+                            for (field_name, field) in other_fields {
+                                fields.entry(field_name).or_insert(field);
+                            }
+                        }
+                        (
+                            MetaType::Interface {
+                                fields,
+                                possible_types,
+                                ..
+                            },
+                            MetaType::Interface {
+                                fields: other_fields,
+                                possible_types: other_possible_types,
+                                ..
+                            },
+                        ) => {
+                            for (field_name, field) in other_fields {
+                                fields.entry(field_name).or_insert(field);
+                            }
+                            possible_types.extend(other_possible_types);
+                        }
+                        _ => {}
+                    }
+                }
+                None => {
+                    self.types.insert(name, other_ty);
+                }
+            }
+        }
+
+        for (name, directive) in other.directives {
+            self.directives.entry(name).or_insert(directive);
+        }
+
+        for (name, interfaces) in other.implements {
+            self.implements.entry(name).or_default().extend(interfaces);
+        }
+
+        if self.query_type.is_empty() {
+            self.query_type = other.query_type;
+        }
+        if self.mutation_type.is_none() {
+            self.mutation_type = other.mutation_type;
+        }
+        if self.subscription_type.is_none() {
+            self.subscription_type = other.subscription_type;
+        }
+
+        self.ignore_name_conflicts
+            .extend(other.ignore_name_conflicts);
+        for (name, typenames) in other.name_conflict_typenames {
+            let existing = self.name_conflict_typenames.entry(name).or_default();
+            for typename in typenames {
+                if !existing.contains(&typename) {
+                    existing.push(typename);
+                }
+            }
+        }
+        self.reference_stubs.extend(other.reference_stubs);
+        for name in other.compose_directives {
+            self.add_compose_directive(&name);
+        }
+        self.enable_federation |= other.enable_federation;
+
+        Ok(())
+    }
+}
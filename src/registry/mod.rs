@@ -1,19 +1,40 @@
+mod builder;
 mod cache_control;
+mod deduplicate;
+mod diff;
 mod export_sdl;
+mod federation;
+mod from_sdl;
+mod introspection;
+mod merge;
+mod normalize;
+mod stable_hash;
 mod stringify_exec_doc;
+mod validate;
 
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::{self, Display, Formatter},
 };
 
+pub use builder::{MetaTypeBuilder, RegistryBuilder};
 pub use cache_control::CacheControl;
+pub use diff::{Change, SchemaDiff};
 pub use export_sdl::SDLExportOptions;
+pub use federation::{FederationFeature, FederationVersion, LinkError, LinkSpec};
+pub use from_sdl::FromSdlError;
 use indexmap::{map::IndexMap, set::IndexSet};
+pub use merge::MergeError;
+pub use normalize::{NameKind, RenameError};
+use stable_hash::StableHasher;
+pub use validate::{SchemaError, ValidateOptions};
 
 pub use crate::model::__DirectiveLocation;
 use crate::{
-    parser::types::{BaseType as ParsedBaseType, Field, Type as ParsedType, VariableDefinition},
+    parser::{
+        self,
+        types::{BaseType as ParsedBaseType, Field, Type as ParsedType, VariableDefinition},
+    },
     schema::IntrospectionMode,
     Any, Context, InputType, OutputType, Positioned, ServerResult, SubscriptionType, Value,
     VisitorContext,
@@ -77,13 +98,22 @@ impl<'a> MetaTypeName<'a> {
         }
     }
 
+    /// Returns `true` if a value of type `sub` can be used where `self` is
+    /// expected, following the `AreTypesCompatible` rules of the GraphQL spec.
+    ///
+    /// Non-null types are subtypes of their nullable forms, and lists are
+    /// covariant in their item type, so `[Int!]!` is a subtype of `[Int]`
+    /// but `[Int]` is not a subtype of `[Int!]`.
     #[inline]
     pub fn is_subtype(&self, sub: &MetaTypeName<'_>) -> bool {
         match (self, sub) {
-            (MetaTypeName::NonNull(super_type), MetaTypeName::NonNull(sub_type))
-            | (MetaTypeName::Named(super_type), MetaTypeName::NonNull(sub_type)) => {
+            (MetaTypeName::NonNull(super_type), MetaTypeName::NonNull(sub_type)) => {
                 MetaTypeName::create(super_type).is_subtype(&MetaTypeName::create(sub_type))
             }
+            // A non-null type is a subtype of its nullable form, including non-null lists.
+            (_, MetaTypeName::NonNull(sub_type)) => {
+                self.is_subtype(&MetaTypeName::create(sub_type))
+            }
             (MetaTypeName::Named(super_type), MetaTypeName::Named(sub_type)) => {
                 super_type == sub_type
             }
@@ -99,23 +129,87 @@ impl<'a> MetaTypeName<'a> {
         match self {
             MetaTypeName::List(_) => true,
             MetaTypeName::NonNull(ty) => MetaTypeName::create(ty).is_list(),
-            MetaTypeName::Named(name) => name.ends_with(']'),
+            MetaTypeName::Named(name) => match MetaTypeName::create(name) {
+                MetaTypeName::Named(_) => false,
+                ty => ty.is_list(),
+            },
+        }
+    }
+
+    /// Returns the element type of a list type, unwrapping an outer non-null
+    /// wrapper first, so that `[T!]!` yields `T!`.
+    ///
+    /// Returns `None` if the type is not a list.
+    pub fn list_item_type(&self) -> Option<MetaTypeName<'a>> {
+        match *self {
+            MetaTypeName::List(ty) => Some(MetaTypeName::create(ty)),
+            MetaTypeName::NonNull(ty) => MetaTypeName::create(ty).list_item_type(),
+            MetaTypeName::Named(name) => match MetaTypeName::create(name) {
+                MetaTypeName::Named(_) => None,
+                ty => ty.list_item_type(),
+            },
         }
     }
+
+    /// Returns `true` if the type is a list whose elements may be null.
+    pub fn is_nullable_list_item(&self) -> bool {
+        self.list_item_type()
+            .map(|ty| !ty.is_non_null())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Clone)]
 pub struct MetaInputValue {
-    pub name: &'static str,
-    pub description: Option<&'static str>,
+    pub name: String,
+    pub description: Option<String>,
     pub ty: String,
     pub default_value: Option<String>,
+    pub deprecation: Deprecation,
     pub visible: Option<MetaVisibleFn>,
     pub inaccessible: bool,
     pub tags: &'static [&'static str],
     pub is_secret: bool,
 }
 
+impl MetaInputValue {
+    /// Returns `true` if a value must be provided for this input value, that
+    /// is if it is non-null and has no default value.
+    #[inline]
+    pub fn is_required(&self) -> bool {
+        MetaTypeName::create(&self.ty).is_non_null() && self.default_value.is_none()
+    }
+
+    /// Parses the default value, which is stored as GraphQL text.
+    ///
+    /// Returns `None` if there is no default value, and an error if the
+    /// default value is not a valid GraphQL constant value.
+    pub fn parse_default(&self) -> Option<Result<Value, parser::Error>> {
+        self.default_value
+            .as_deref()
+            .map(parser::parse_value_literal)
+    }
+
+    /// Returns the default value re-serialized as a valid GraphQL constant
+    /// value, such as `[1,2,3]` or `{x: 1}`.
+    ///
+    /// A default value stored as JSON is converted to GraphQL, and `None` is
+    /// returned if there is no default value or if it cannot be parsed.
+    pub fn normalized_default(&self) -> Option<String> {
+        self.default_value_lenient().map(|value| value.to_string())
+    }
+
+    /// Parses the default value as GraphQL, or else as JSON.
+    pub(crate) fn default_value_lenient(&self) -> Option<Value> {
+        match self.parse_default()? {
+            Ok(value) => Some(value),
+            Err(_) => serde_json::from_str::<serde_json::Value>(self.default_value.as_deref()?)
+                .ok()
+                .and_then(|value| Value::try_from(value).ok()),
+        }
+    }
+}
+
 type ComputeComplexityFn = fn(
     &VisitorContext<'_>,
     &[Positioned<VariableDefinition>],
@@ -127,9 +221,16 @@ type ComputeComplexityFn = fn(
 pub enum ComplexityType {
     Const(usize),
     Fn(ComputeComplexityFn),
+    /// The complexity of the children multiplied by the value of the integer
+    /// argument `arg`, or by `default` if the argument is absent or is an
+    /// unbound variable.
+    Multiplier {
+        arg: &'static str,
+        default: usize,
+    },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Deprecation {
     NoDeprecated,
     Deprecated { reason: Option<&'static str> },
@@ -156,14 +257,35 @@ impl Deprecation {
     }
 }
 
+/// A federation key of an entity, exported as `@key`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MetaKey {
+    /// The field set of the key, such as `id` or `id organization { id }`.
+    pub fields: String,
+    /// Whether this subgraph resolves the entity by this key, `false` for
+    /// entities that are only referenced.
+    pub resolvable: bool,
+}
+
+impl MetaKey {
+    /// Create a resolvable key with the specified field set.
+    pub fn new(fields: impl Into<String>) -> Self {
+        Self {
+            fields: fields.into(),
+            resolvable: true,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct MetaField {
     pub name: String,
-    pub description: Option<&'static str>,
+    pub description: Option<String>,
     pub args: IndexMap<String, MetaInputValue>,
     pub ty: String,
     pub deprecation: Deprecation,
     pub cache_control: CacheControl,
+    pub vary: &'static [&'static str],
     pub external: bool,
     pub requires: Option<&'static str>,
     pub provides: Option<&'static str>,
@@ -173,21 +295,102 @@ pub struct MetaField {
     pub tags: &'static [&'static str],
     pub override_from: Option<&'static str>,
     pub compute_complexity: Option<ComplexityType>,
+    pub cost: Option<usize>,
+    pub serial: bool,
+    pub directive_invocations: Vec<MetaDirectiveInvocation>,
+}
+
+impl MetaField {
+    /// Creates a field with the specified name and type, without arguments,
+    /// and with the default value of every other property.
+    pub fn new(name: impl Into<String>, ty: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            args: Default::default(),
+            ty: ty.into(),
+            deprecation: Deprecation::NoDeprecated,
+            cache_control: Default::default(),
+            vary: &[],
+            external: false,
+            requires: None,
+            provides: None,
+            visible: None,
+            shareable: false,
+            inaccessible: false,
+            tags: &[],
+            override_from: None,
+            compute_complexity: None,
+            cost: None,
+            serial: false,
+            directive_invocations: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if this is an introspection field, such as `__schema`
+    /// or `__type`.
+    ///
+    /// Names starting with `__` are reserved for the introspection system by
+    /// the GraphQL specification, so they never clash with user fields.
+    #[inline]
+    pub fn is_introspection(&self) -> bool {
+        self.name.starts_with("__")
+    }
+
+    /// Returns the argument named `name`.
+    #[inline]
+    pub fn arg(&self, name: &str) -> Option<&MetaInputValue> {
+        self.args.get(name)
+    }
+
+    /// Returns `true` if this field has arguments.
+    #[inline]
+    pub fn has_args(&self) -> bool {
+        !self.args.is_empty()
+    }
+
+    /// Returns a signature of the structural parts of this field, suitable for
+    /// use as a cache key.
+    ///
+    /// Only the field name, the field type and the names and types of the
+    /// arguments (in declaration order) participate in the signature.
+    /// Descriptions, deprecations, cache control, federation flags,
+    /// visibility and complexity functions are ignored, so changing them
+    /// does not change the signature.
+    ///
+    /// The signature is a 64-bit FNV-1a hash, so it is stable across builds
+    /// and platforms and can be persisted.
+    pub fn signature(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_str(&self.name);
+        hasher.write_str(&self.ty);
+        hasher.write_len(self.args.len());
+        for arg in self.args.values() {
+            hasher.write_str(&arg.name);
+            hasher.write_str(&arg.ty);
+        }
+        hasher.finish()
+    }
 }
 
 #[derive(Clone)]
 pub struct MetaEnumValue {
-    pub name: &'static str,
-    pub description: Option<&'static str>,
+    pub name: String,
+    pub description: Option<String>,
     pub deprecation: Deprecation,
     pub visible: Option<MetaVisibleFn>,
     pub inaccessible: bool,
     pub tags: &'static [&'static str],
+    pub directive_invocations: Vec<MetaDirectiveInvocation>,
 }
 
 type MetaVisibleFn = fn(&Context<'_>) -> bool;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Parses a value with `ScalarType::parse` and serializes the result with
+/// `ScalarType::to_value`, returning the error message if parsing fails.
+pub type ScalarRoundTripFn = fn(Value) -> Result<Value, String>;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum MetaTypeId {
     Scalar,
     Object,
@@ -214,8 +417,9 @@ impl Display for MetaTypeId {
 pub enum MetaType {
     Scalar {
         name: String,
-        description: Option<&'static str>,
+        description: Option<String>,
         is_valid: fn(value: &Value) -> bool,
+        round_trip: Option<ScalarRoundTripFn>,
         visible: Option<MetaVisibleFn>,
         inaccessible: bool,
         tags: &'static [&'static str],
@@ -223,34 +427,38 @@ pub enum MetaType {
     },
     Object {
         name: String,
-        description: Option<&'static str>,
+        description: Option<String>,
         fields: IndexMap<String, MetaField>,
         cache_control: CacheControl,
         extends: bool,
         shareable: bool,
-        keys: Option<Vec<String>>,
+        keys: Option<Vec<MetaKey>>,
         visible: Option<MetaVisibleFn>,
         inaccessible: bool,
         tags: &'static [&'static str],
         is_subscription: bool,
         rust_typename: &'static str,
+        directive_invocations: Vec<MetaDirectiveInvocation>,
     },
     Interface {
         name: String,
-        description: Option<&'static str>,
+        description: Option<String>,
         fields: IndexMap<String, MetaField>,
         possible_types: IndexSet<String>,
         extends: bool,
-        keys: Option<Vec<String>>,
+        keys: Option<Vec<MetaKey>>,
         visible: Option<MetaVisibleFn>,
         inaccessible: bool,
         tags: &'static [&'static str],
         rust_typename: &'static str,
+        directive_invocations: Vec<MetaDirectiveInvocation>,
     },
     Union {
         name: String,
-        description: Option<&'static str>,
+        description: Option<String>,
         possible_types: IndexSet<String>,
+        /// The tags given to the possible types as members of this union.
+        possible_type_tags: IndexMap<String, Vec<&'static str>>,
         visible: Option<MetaVisibleFn>,
         inaccessible: bool,
         tags: &'static [&'static str],
@@ -258,8 +466,8 @@ pub enum MetaType {
     },
     Enum {
         name: String,
-        description: Option<&'static str>,
-        enum_values: IndexMap<&'static str, MetaEnumValue>,
+        description: Option<String>,
+        enum_values: IndexMap<String, MetaEnumValue>,
         visible: Option<MetaVisibleFn>,
         inaccessible: bool,
         tags: &'static [&'static str],
@@ -267,7 +475,7 @@ pub enum MetaType {
     },
     InputObject {
         name: String,
-        description: Option<&'static str>,
+        description: Option<String>,
         input_fields: IndexMap<String, MetaInputValue>,
         visible: Option<MetaVisibleFn>,
         inaccessible: bool,
@@ -278,6 +486,11 @@ pub enum MetaType {
 }
 
 impl MetaType {
+    /// Returns a builder for an object type with the specified name.
+    pub fn object(name: impl Into<String>) -> MetaTypeBuilder {
+        MetaTypeBuilder::new(name.into())
+    }
+
     #[inline]
     pub fn type_id(&self) -> MetaTypeId {
         match self {
@@ -295,6 +508,15 @@ impl MetaType {
         self.fields().and_then(|fields| fields.get(name))
     }
 
+    /// Returns the named input field of an input object.
+    #[inline]
+    pub fn input_field_by_name(&self, name: &str) -> Option<&MetaInputValue> {
+        match self {
+            MetaType::InputObject { input_fields, .. } => input_fields.get(name),
+            _ => None,
+        }
+    }
+
     #[inline]
     pub fn fields(&self) -> Option<&IndexMap<String, MetaField>> {
         match self {
@@ -304,6 +526,26 @@ impl MetaType {
         }
     }
 
+    /// Returns the fields of this type if it is an object or an interface,
+    /// for modification.
+    #[inline]
+    pub fn fields_mut(&mut self) -> Option<&mut IndexMap<String, MetaField>> {
+        match self {
+            MetaType::Object { fields, .. } => Some(fields),
+            MetaType::Interface { fields, .. } => Some(fields),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of this type if it is an enum.
+    #[inline]
+    pub fn enum_values(&self) -> Option<&IndexMap<String, MetaEnumValue>> {
+        match self {
+            MetaType::Enum { enum_values, .. } => Some(enum_values),
+            _ => None,
+        }
+    }
+
     #[inline]
     pub fn is_visible(&self, ctx: &Context<'_>) -> bool {
         let visible = match self {
@@ -329,6 +571,32 @@ impl MetaType {
         }
     }
 
+    /// Returns the description of this type.
+    #[inline]
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            MetaType::Scalar { description, .. }
+            | MetaType::Object { description, .. }
+            | MetaType::Interface { description, .. }
+            | MetaType::Union { description, .. }
+            | MetaType::Enum { description, .. }
+            | MetaType::InputObject { description, .. } => description.as_deref(),
+        }
+    }
+
+    /// Returns `true` if this type is marked `@inaccessible`.
+    #[inline]
+    pub fn is_inaccessible(&self) -> bool {
+        match self {
+            MetaType::Scalar { inaccessible, .. }
+            | MetaType::Object { inaccessible, .. }
+            | MetaType::Interface { inaccessible, .. }
+            | MetaType::Union { inaccessible, .. }
+            | MetaType::Enum { inaccessible, .. }
+            | MetaType::InputObject { inaccessible, .. } => *inaccessible,
+        }
+    }
+
     #[inline]
     pub fn is_composite(&self) -> bool {
         matches!(
@@ -374,8 +642,18 @@ impl MetaType {
         }
     }
 
+    /// Returns the federation key field sets of an object or interface.
+    #[inline]
+    pub fn keys(&self) -> Option<&[MetaKey]> {
+        match self {
+            MetaType::Object { keys, .. } => keys.as_deref(),
+            MetaType::Interface { keys, .. } => keys.as_deref(),
+            _ => None,
+        }
+    }
+
     pub fn type_overlap(&self, ty: &MetaType) -> bool {
-        if std::ptr::eq(self, ty) {
+        if std::ptr::eq(self, ty) || self.name() == ty.name() {
             return true;
         }
 
@@ -406,18 +684,83 @@ impl MetaType {
 
 pub struct MetaDirective {
     pub name: &'static str,
-    pub description: Option<&'static str>,
+    pub description: Option<String>,
     pub locations: Vec<__DirectiveLocation>,
     pub args: IndexMap<String, MetaInputValue>,
     pub is_repeatable: bool,
     pub visible: Option<MetaVisibleFn>,
+    pub deprecation: Deprecation,
+}
+
+impl MetaDirective {
+    /// Returns `true` if this directive is deprecated.
+    ///
+    /// The GraphQL specification does not allow `@deprecated` on directive
+    /// definitions, so the deprecation of a directive is neither exported in
+    /// the SDL nor exposed by introspection.
+    #[inline]
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation.is_deprecated()
+    }
+
+    /// Returns `true` if this directive can be applied at `location`.
+    #[inline]
+    pub fn is_applicable_at(&self, location: __DirectiveLocation) -> bool {
+        self.locations.contains(&location)
+    }
+
+    /// Returns the argument named `name`.
+    #[inline]
+    pub fn arg(&self, name: &str) -> Option<&MetaInputValue> {
+        self.args.get(name)
+    }
+}
+
+/// A directive applied to a type or a field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetaDirectiveInvocation {
+    pub name: String,
+    pub args: IndexMap<String, Value>,
+}
+
+impl MetaDirectiveInvocation {
+    /// Returns the SDL of this directive application, such as
+    /// `@foo(bar: "baz")`, or `@foo` if it has no arguments.
+    pub fn sdl(&self) -> String {
+        if self.args.is_empty() {
+            return format!("@{}", self.name);
+        }
+        format!(
+            "@{}({})",
+            self.name,
+            self.args
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+/// A cycle between input objects, returned by
+/// [`Registry::input_object_dependency_order`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleInfo {
+    /// The input objects of the first cycle found, each depending on the
+    /// next one, the last one depending on the first one.
+    pub types: Vec<String>,
+    /// The input field ignored to break the cycle, as `(type name, field
+    /// name)`.
+    pub break_point: (String, String),
+    /// The input objects in dependency order, with every cycle broken.
+    pub order: Vec<String>,
 }
 
 #[derive(Default)]
 pub struct Registry {
     pub types: BTreeMap<String, MetaType>,
-    pub directives: HashMap<String, MetaDirective>,
-    pub implements: HashMap<String, HashSet<String>>,
+    pub directives: BTreeMap<String, MetaDirective>,
+    pub implements: HashMap<String, BTreeSet<String>>,
     pub query_type: String,
     pub mutation_type: Option<String>,
     pub subscription_type: Option<String>,
@@ -426,6 +769,17 @@ pub struct Registry {
     pub enable_apollo_link: bool,
     pub federation_subscription: bool,
     pub ignore_name_conflicts: HashSet<String>,
+    /// Record the GraphQL names used by multiple Rust types instead of
+    /// panicking, see [`Registry::name_conflicts`].
+    pub collect_name_conflicts: bool,
+    /// The Rust types registered for each GraphQL name used by multiple Rust
+    /// types, in registration order.
+    pub name_conflict_typenames: BTreeMap<String, Vec<String>>,
+    pub reference_stubs: HashSet<String>,
+    pub link_imports: Option<Vec<String>>,
+    pub link_specs: Vec<LinkSpec>,
+    pub compose_directives: Vec<String>,
+    pub enable_incremental_delivery: bool,
 }
 
 impl Registry {
@@ -487,6 +841,17 @@ impl Registry {
 
                     if rust_typename != prev_typename && !self.ignore_name_conflicts.contains(name)
                     {
+                        if self.collect_name_conflicts {
+                            let typenames = self
+                                .name_conflict_typenames
+                                .entry(name.to_string())
+                                .or_insert_with(|| vec![prev_typename.to_string()]);
+                            if !typenames.iter().any(|typename| typename == rust_typename) {
+                                typenames.push(rust_typename.to_string());
+                            }
+                            return;
+                        }
+
                         panic!(
                             "`{}` and `{}` have the same GraphQL name `{}`",
                             prev_typename, rust_typename, name,
@@ -506,29 +871,81 @@ impl Registry {
             None => {
                 // Inserting a fake type before calling the function allows recursive types to
                 // exist.
-                self.types.insert(
-                    name.to_string(),
-                    MetaType::Object {
-                        name: "".to_string(),
-                        description: None,
-                        fields: Default::default(),
-                        cache_control: Default::default(),
-                        extends: false,
-                        shareable: false,
-                        inaccessible: false,
-                        tags: Default::default(),
-                        keys: None,
-                        visible: None,
-                        is_subscription: false,
-                        rust_typename: "__fake_type__",
-                    },
-                );
+                self.types
+                    .insert(name.to_string(), Self::fake_type(type_id));
                 let ty = f(self);
                 *self.types.get_mut(name).unwrap() = ty;
             }
         }
     }
 
+    /// Returns the placeholder inserted while creating a type of the kind
+    /// `type_id`, so that recursive references see a type of the right kind.
+    fn fake_type(type_id: MetaTypeId) -> MetaType {
+        const RUST_TYPENAME: &str = "__fake_type__";
+
+        match type_id {
+            MetaTypeId::Interface => MetaType::Interface {
+                name: "".to_string(),
+                description: None,
+                fields: Default::default(),
+                possible_types: Default::default(),
+                extends: false,
+                keys: None,
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                rust_typename: RUST_TYPENAME,
+                directive_invocations: Vec::new(),
+            },
+            MetaTypeId::Union => MetaType::Union {
+                name: "".to_string(),
+                description: None,
+                possible_types: Default::default(),
+                possible_type_tags: Default::default(),
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                rust_typename: RUST_TYPENAME,
+            },
+            MetaTypeId::Enum => MetaType::Enum {
+                name: "".to_string(),
+                description: None,
+                enum_values: Default::default(),
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                rust_typename: RUST_TYPENAME,
+            },
+            MetaTypeId::InputObject => MetaType::InputObject {
+                name: "".to_string(),
+                description: None,
+                input_fields: Default::default(),
+                visible: None,
+                inaccessible: false,
+                tags: Default::default(),
+                rust_typename: RUST_TYPENAME,
+                oneof: false,
+            },
+            // Scalars cannot be recursive and have no Rust type name to mark them as fake.
+            MetaTypeId::Scalar | MetaTypeId::Object => MetaType::Object {
+                name: "".to_string(),
+                description: None,
+                fields: Default::default(),
+                cache_control: Default::default(),
+                extends: false,
+                shareable: false,
+                inaccessible: false,
+                tags: Default::default(),
+                keys: None,
+                visible: None,
+                is_subscription: false,
+                directive_invocations: Vec::new(),
+                rust_typename: RUST_TYPENAME,
+            },
+        }
+    }
+
     pub fn create_fake_output_type<T: OutputType>(&mut self) -> MetaType {
         T::create_type_info(self);
         self.types
@@ -553,11 +970,175 @@ impl Registry {
             .expect("You definitely encountered a bug!")
     }
 
+    /// Register `T` and use it as the query root type.
+    pub fn set_query_type<T: OutputType>(&mut self) {
+        T::create_type_info(self);
+        self.query_type = T::type_name().into_owned();
+    }
+
+    /// Register `T` and use it as the mutation root type.
+    pub fn set_mutation_type<T: OutputType>(&mut self) {
+        T::create_type_info(self);
+        self.mutation_type = Some(T::type_name().into_owned());
+    }
+
+    /// Register `T` and use it as the subscription root type.
+    pub fn set_subscription_type<T: SubscriptionType>(&mut self) {
+        T::create_type_info(self);
+        self.subscription_type = Some(T::type_name().into_owned());
+    }
+
     pub fn add_directive(&mut self, directive: MetaDirective) {
         self.directives
             .insert(directive.name.to_string(), directive);
     }
 
+    /// Returns the directive with the specified name.
+    #[inline]
+    pub fn directive(&self, name: &str) -> Option<&MetaDirective> {
+        self.directives.get(name)
+    }
+
+    /// Returns all the directives, sorted by name.
+    #[inline]
+    pub fn directives(&self) -> impl Iterator<Item = &MetaDirective> {
+        self.directives.values()
+    }
+
+    /// Register the `@include` and `@skip` directives.
+    pub(crate) fn add_include_and_skip_directives(&mut self) {
+        self.add_directive(MetaDirective {
+            name: "include",
+            description: Some("Directs the executor to include this field or fragment only when the `if` argument is true.".to_string()),
+            locations: vec![
+                __DirectiveLocation::FIELD,
+                __DirectiveLocation::FRAGMENT_SPREAD,
+                __DirectiveLocation::INLINE_FRAGMENT
+            ],
+            args: {
+                let mut args = IndexMap::new();
+                args.insert("if".to_string(), MetaInputValue {
+                    name: "if".to_string(),
+                    description: Some("Included when true.".to_string()),
+                    ty: "Boolean!".to_string(),
+                    default_value: None,
+                    deprecation: Default::default(),
+                    visible: None,
+                    inaccessible: false,
+                    tags: Default::default(),
+                    is_secret: false,
+                });
+                args
+            },
+            is_repeatable: false,
+            visible: None,
+            deprecation: Default::default(),
+        });
+
+        self.add_directive(MetaDirective {
+            name: "skip",
+            description: Some("Directs the executor to skip this field or fragment when the `if` argument is true.".to_string()),
+            locations: vec![
+                __DirectiveLocation::FIELD,
+                __DirectiveLocation::FRAGMENT_SPREAD,
+                __DirectiveLocation::INLINE_FRAGMENT
+            ],
+            args: {
+                let mut args = IndexMap::new();
+                args.insert("if".to_string(), MetaInputValue {
+                    name: "if".to_string(),
+                    description: Some("Skipped when true.".to_string()),
+                    ty: "Boolean!".to_string(),
+                    default_value: None,
+                    deprecation: Default::default(),
+                    visible: None,
+                    inaccessible: false,
+                    tags: Default::default(),
+                    is_secret: false,
+                });
+                args
+            },
+            is_repeatable: false,
+            visible: None,
+            deprecation: Default::default(),
+        });
+    }
+
+    /// Register the `@defer` and `@stream` directives of incremental delivery,
+    /// so that they are advertised by introspection and exported in SDL.
+    pub fn add_incremental_delivery_directives(&mut self) {
+        fn arg(
+            name: &str,
+            description: &str,
+            ty: &str,
+            default_value: Option<&str>,
+        ) -> (String, MetaInputValue) {
+            (
+                name.to_string(),
+                MetaInputValue {
+                    name: name.to_string(),
+                    description: Some(description.to_string()),
+                    ty: ty.to_string(),
+                    default_value: default_value.map(ToString::to_string),
+                    deprecation: Deprecation::NoDeprecated,
+                    visible: None,
+                    inaccessible: false,
+                    tags: &[],
+                    is_secret: false,
+                },
+            )
+        }
+
+        self.enable_incremental_delivery = true;
+
+        self.add_directive(MetaDirective {
+            name: "defer",
+            description: Some("Directs the executor to deliver this fragment incrementally, after the rest of the response.".to_string()),
+            locations: vec![
+                __DirectiveLocation::FRAGMENT_SPREAD,
+                __DirectiveLocation::INLINE_FRAGMENT,
+            ],
+            args: [
+                arg("label", "A unique label identifying the deferred payload.", "String", None),
+                arg("if", "Deferred when true.", "Boolean!", Some("true")),
+            ]
+            .into_iter()
+            .collect(),
+            is_repeatable: false,
+            visible: None,
+            deprecation: Default::default(),
+        });
+
+        self.add_directive(MetaDirective {
+            name: "stream",
+            description: Some(
+                "Directs the executor to deliver the items of this list field incrementally."
+                    .to_string(),
+            ),
+            locations: vec![__DirectiveLocation::FIELD],
+            args: [
+                arg(
+                    "label",
+                    "A unique label identifying the streamed payloads.",
+                    "String",
+                    None,
+                ),
+                arg("if", "Streamed when true.", "Boolean!", Some("true")),
+                arg(
+                    "initialCount",
+                    "The number of items delivered in the initial response.",
+                    "Int",
+                    Some("0"),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            is_repeatable: false,
+            visible: None,
+            deprecation: Default::default(),
+        });
+    }
+
     pub fn add_implements(&mut self, ty: &str, interface: &str) {
         self.implements
             .entry(ty.to_string())
@@ -565,22 +1146,32 @@ impl Registry {
                 interfaces.insert(interface.to_string());
             })
             .or_insert({
-                let mut interfaces = HashSet::new();
+                let mut interfaces = BTreeSet::new();
                 interfaces.insert(interface.to_string());
                 interfaces
             });
     }
 
     pub fn add_keys(&mut self, ty: &str, keys: &str) {
+        self.add_key_with(ty, keys, true);
+    }
+
+    /// Add the key `keys` to the entity `ty`, which is exported as
+    /// `@key(fields: "...", resolvable: false)` if `resolvable` is `false`.
+    pub fn add_key_with(&mut self, ty: &str, keys: &str, resolvable: bool) {
         let all_keys = match self.types.get_mut(ty) {
             Some(MetaType::Object { keys: all_keys, .. }) => all_keys,
             Some(MetaType::Interface { keys: all_keys, .. }) => all_keys,
             _ => return,
         };
+        let key = MetaKey {
+            fields: keys.to_string(),
+            resolvable,
+        };
         if let Some(all_keys) = all_keys {
-            all_keys.push(keys.to_string());
+            all_keys.push(key);
         } else {
-            *all_keys = Some(vec![keys.to_string()]);
+            *all_keys = Some(vec![key]);
         }
     }
 
@@ -596,15 +1187,7 @@ impl Registry {
     }
 
     pub(crate) fn has_entities(&self) -> bool {
-        self.types.values().any(|ty| match ty {
-            MetaType::Object {
-                keys: Some(keys), ..
-            }
-            | MetaType::Interface {
-                keys: Some(keys), ..
-            } => !keys.is_empty(),
-            _ => false,
-        })
+        !self.entities().is_empty()
     }
 
     /// Each type annotated with @key should be added to the _Entity union.
@@ -614,43 +1197,15 @@ impl Registry {
     /// [Reference](https://www.apollographql.com/docs/federation/federation-spec/#resolve-requests-for-entities).
     fn create_entity_type_and_root_field(&mut self) {
         let possible_types: IndexSet<String> = self
-            .types
-            .values()
-            .filter_map(|ty| match ty {
-                MetaType::Object {
-                    name,
-                    keys: Some(keys),
-                    ..
-                } if !keys.is_empty() => Some(name.clone()),
-                MetaType::Interface {
-                    name,
-                    keys: Some(keys),
-                    ..
-                } if !keys.is_empty() => Some(name.clone()),
-                _ => None,
-            })
+            .entities()
+            .into_iter()
+            .map(|(name, _)| name.to_string())
             .collect();
 
         if let MetaType::Object { fields, .. } = self.types.get_mut(&self.query_type).unwrap() {
             fields.insert(
                 "_service".to_string(),
-                MetaField {
-                    name: "_service".to_string(),
-                    description: None,
-                    args: Default::default(),
-                    ty: "_Service!".to_string(),
-                    deprecation: Default::default(),
-                    cache_control: Default::default(),
-                    external: false,
-                    requires: None,
-                    provides: None,
-                    shareable: false,
-                    inaccessible: false,
-                    tags: Default::default(),
-                    override_from: None,
-                    visible: None,
-                    compute_complexity: None,
-                },
+                MetaField::new("_service", "_Service!"),
             );
         }
 
@@ -661,6 +1216,7 @@ impl Registry {
                     name: "_Entity".to_string(),
                     description: None,
                     possible_types,
+                    possible_type_tags: Default::default(),
                     visible: None,
                     inaccessible: false,
                     tags: Default::default(),
@@ -672,17 +1228,16 @@ impl Registry {
                 fields.insert(
                     "_entities".to_string(),
                     MetaField {
-                        name: "_entities".to_string(),
-                        description: None,
                         args: {
                             let mut args = IndexMap::new();
                             args.insert(
                                 "representations".to_string(),
                                 MetaInputValue {
-                                    name: "representations",
+                                    name: "representations".to_string(),
                                     description: None,
                                     ty: "[_Any!]!".to_string(),
                                     default_value: None,
+                                    deprecation: Deprecation::NoDeprecated,
                                     visible: None,
                                     inaccessible: false,
                                     tags: Default::default(),
@@ -691,18 +1246,7 @@ impl Registry {
                             );
                             args
                         },
-                        ty: "[_Entity]!".to_string(),
-                        deprecation: Default::default(),
-                        cache_control: Default::default(),
-                        external: false,
-                        requires: None,
-                        provides: None,
-                        shareable: false,
-                        visible: None,
-                        inaccessible: false,
-                        tags: Default::default(),
-                        override_from: None,
-                        compute_complexity: None,
+                        ..MetaField::new("_entities", "[_Entity]!")
                     },
                 );
             }
@@ -714,50 +1258,19 @@ impl Registry {
 
         self.types.insert(
             "_Service".to_string(),
-            MetaType::Object {
-                name: "_Service".to_string(),
-                description: None,
-                fields: {
-                    let mut fields = IndexMap::new();
-                    fields.insert(
-                        "sdl".to_string(),
-                        MetaField {
-                            name: "sdl".to_string(),
-                            description: None,
-                            args: Default::default(),
-                            ty: "String".to_string(),
-                            deprecation: Default::default(),
-                            cache_control: Default::default(),
-                            external: false,
-                            requires: None,
-                            provides: None,
-                            shareable: false,
-                            visible: None,
-                            inaccessible: false,
-                            tags: Default::default(),
-                            override_from: None,
-                            compute_complexity: None,
-                        },
-                    );
-                    fields
-                },
-                cache_control: Default::default(),
-                extends: false,
-                shareable: false,
-                keys: None,
-                visible: None,
-                inaccessible: false,
-                tags: Default::default(),
-                is_subscription: false,
-                rust_typename: "async_graphql::federation::Service",
-            },
+            MetaType::object("_Service")
+                .field(MetaField::new("sdl", "String"))
+                .rust_typename("async_graphql::federation::Service")
+                .build(),
         );
 
         self.create_entity_type_and_root_field();
     }
 
+    /// Returns all the names of the schema (types, fields, arguments, enum
+    /// values, input fields and directives), sorted alphabetically.
     pub fn names(&self) -> Vec<String> {
-        let mut names = HashSet::new();
+        let mut names = BTreeSet::new();
 
         for d in self.directives.values() {
             names.insert(d.name.to_string());
@@ -800,49 +1313,720 @@ impl Registry {
         names.into_iter().collect()
     }
 
-    pub fn set_description(&mut self, name: &str, desc: &'static str) {
-        match self.types.get_mut(name) {
-            Some(MetaType::Scalar { description, .. }) => *description = Some(desc),
-            Some(MetaType::Object { description, .. }) => *description = Some(desc),
-            Some(MetaType::Interface { description, .. }) => *description = Some(desc),
-            Some(MetaType::Union { description, .. }) => *description = Some(desc),
-            Some(MetaType::Enum { description, .. }) => *description = Some(desc),
-            Some(MetaType::InputObject { description, .. }) => *description = Some(desc),
-            None => {}
-        }
+    /// Returns each GraphQL name used by multiple Rust types, sorted by name,
+    /// with the names of these Rust types in registration order.
+    ///
+    /// Name conflicts are only recorded if
+    /// [`collect_name_conflicts`](Registry::collect_name_conflicts) is set,
+    /// otherwise registering a conflicting type panics. Names in
+    /// `ignore_name_conflicts` are never reported.
+    pub fn name_conflicts(&self) -> Vec<(String, Vec<String>)> {
+        self.name_conflict_typenames
+            .iter()
+            .filter(|(name, _)| !self.ignore_name_conflicts.contains(*name))
+            .map(|(name, typenames)| (name.clone(), typenames.clone()))
+            .collect()
     }
 
-    pub fn remove_unused_types(&mut self) {
-        let mut used_types = BTreeSet::new();
-        let mut unused_types = BTreeSet::new();
+    /// Returns an iterator over all the types of the registry as
+    /// `(name, type)` pairs, ordered alphabetically by name.
+    pub fn iter_types(&self) -> impl Iterator<Item = (&str, &MetaType)> {
+        self.types.iter().map(|(name, ty)| (name.as_str(), ty))
+    }
 
-        fn traverse_field<'a>(
-            types: &'a BTreeMap<String, MetaType>,
-            used_types: &mut BTreeSet<&'a str>,
-            field: &'a MetaField,
-        ) {
-            traverse_type(
-                types,
-                used_types,
-                MetaTypeName::concrete_typename(&field.ty),
-            );
-            for arg in field.args.values() {
-                traverse_input_value(types, used_types, arg);
+    /// Returns an iterator over the types of the specified kind as
+    /// `(name, type)` pairs, ordered alphabetically by name.
+    pub fn iter_types_filtered(
+        &self,
+        type_id: MetaTypeId,
+    ) -> impl Iterator<Item = (&str, &MetaType)> {
+        self.iter_types()
+            .filter(move |(_, ty)| ty.type_id() == type_id)
+    }
+
+    /// Returns the number of types in the registry.
+    pub fn type_count(&self) -> usize {
+        self.types.len()
+    }
+
+    /// Returns the names of the interfaces implemented by the specified type,
+    /// sorted alphabetically.
+    pub fn interfaces_of(&self, type_name: &str) -> Vec<&str> {
+        self.implements
+            .get(type_name)
+            .map(|interfaces| interfaces.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the names of the types implementing the specified interface,
+    /// sorted alphabetically.
+    pub fn implementors_of(&self, interface_name: &str) -> Vec<&str> {
+        let mut implementors = self
+            .implements
+            .iter()
+            .filter(|(_, interfaces)| interfaces.contains(interface_name))
+            .map(|(type_name, _)| type_name.as_str())
+            .collect::<Vec<_>>();
+        implementors.sort_unstable();
+        implementors
+    }
+
+    /// Returns the object types that are possible types of the specified
+    /// interface or union, including the objects implementing the interfaces
+    /// that implement it, transitively.
+    ///
+    /// The types are collected depth-first, visiting the declared possible
+    /// types of each abstract type in declaration order, followed by the
+    /// other types implementing it, sorted by name.
+    /// Returns an empty set if the type does not exist or is not abstract.
+    pub fn possible_types_closure(&self, abstract_type: &str) -> IndexSet<String> {
+        let mut possible_types = IndexSet::new();
+        let mut visited = HashSet::new();
+        let mut pending = vec![abstract_type];
+
+        while let Some(name) = pending.pop() {
+            if !visited.insert(name) {
+                continue;
             }
+            let ty = match self.types.get(name) {
+                Some(ty) if ty.is_abstract() => ty,
+                Some(MetaType::Object { .. }) if name != abstract_type => {
+                    possible_types.insert(name.to_string());
+                    continue;
+                }
+                _ => continue,
+            };
+
+            let mut members = ty
+                .possible_types()
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            if let MetaType::Interface { .. } = ty {
+                members.extend(self.implementors_of(name));
+            }
+            // Pushed in reverse so that the members are visited in order.
+            pending.extend(members.into_iter().rev());
         }
 
-        fn traverse_input_value<'a>(
+        possible_types
+    }
+
+    /// Returns `true` if the two named types have at least one object type in
+    /// common, which is the condition for a fragment on one of them to be
+    /// spread in the other.
+    ///
+    /// Two abstract types overlap if they share a possible type, even if
+    /// neither is a subtype of the other. Returns `false` if either type
+    /// does not exist.
+    pub fn types_overlap(&self, a: &str, b: &str) -> bool {
+        match (self.types.get(a), self.types.get(b)) {
+            (Some(a), Some(b)) => a.type_overlap(b),
+            _ => false,
+        }
+    }
+
+    /// Returns the names of all custom scalars (everything except `Int`,
+    /// `Float`, `String`, `Boolean` and `ID`), sorted alphabetically.
+    pub fn custom_scalar_names(&self) -> Vec<&str> {
+        self.custom_scalars().map(|(name, _)| name).collect()
+    }
+
+    /// Returns all scalars as `(name, specified by URL)` pairs, ordered by
+    /// name.
+    pub fn scalars(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.types.values().filter_map(|ty| match ty {
+            MetaType::Scalar {
+                name,
+                specified_by_url,
+                ..
+            } => Some((name.as_str(), *specified_by_url)),
+            _ => None,
+        })
+    }
+
+    /// Returns the custom scalars (everything except `Int`, `Float`,
+    /// `String`, `Boolean`, `ID` and the introspection types) as
+    /// `(name, specified by URL)` pairs, ordered by name.
+    pub fn custom_scalars(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.scalars().filter(|(name, _)| !is_system_type(name))
+    }
+
+    /// Returns the names of the oneOf input objects, sorted alphabetically.
+    pub fn oneof_inputs(&self) -> Vec<&str> {
+        self.types
+            .values()
+            .filter(|ty| matches!(ty, MetaType::InputObject { oneof: true, .. }))
+            .map(MetaType::name)
+            .collect()
+    }
+
+    /// Returns the values of the specified enum, or `None` if the type does
+    /// not exist or is not an enum.
+    pub fn enum_values(&self, type_name: &str) -> Option<&IndexMap<String, MetaEnumValue>> {
+        self.types.get(type_name)?.enum_values()
+    }
+
+    /// Returns the specified value of the specified enum, or `None` if it
+    /// does not exist.
+    pub fn enum_value(&self, type_name: &str, value: &str) -> Option<&MetaEnumValue> {
+        self.enum_values(type_name)?.get(value)
+    }
+
+    /// Returns the member types of the specified union, sorted
+    /// alphabetically, or `None` if the type does not exist or is not a
+    /// union.
+    pub fn union_members(&self, type_name: &str) -> Option<Vec<&str>> {
+        match self.types.get(type_name)? {
+            MetaType::Union { possible_types, .. } => {
+                let mut members = possible_types
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>();
+                members.sort_unstable();
+                Some(members)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the fields of the specified interface, or `None` if the type
+    /// does not exist or is not an interface.
+    pub fn interface_fields(&self, type_name: &str) -> Option<&IndexMap<String, MetaField>> {
+        match self.types.get(type_name)? {
+            MetaType::Interface { fields, .. } => Some(fields),
+            _ => None,
+        }
+    }
+
+    /// Returns the deprecated values of all enums as `(enum name, value)`
+    /// pairs, ordered by enum name and then by value declaration order.
+    pub fn deprecated_enum_values(&self) -> Vec<(&str, &MetaEnumValue)> {
+        self.types
+            .values()
+            .filter_map(|ty| match ty {
+                MetaType::Enum {
+                    name, enum_values, ..
+                } => Some(
+                    enum_values
+                        .values()
+                        .map(move |value| (name.as_str(), value)),
+                ),
+                _ => None,
+            })
+            .flatten()
+            .filter(|(_, value)| value.deprecation.is_deprecated())
+            .collect()
+    }
+
+    /// Parses `value` as the scalar named `name` and serializes it back.
+    ///
+    /// Returns `None` if there is no such scalar, or if the scalar does not
+    /// support round-tripping.
+    pub fn scalar_round_trip(&self, name: &str, value: Value) -> Option<Result<Value, String>> {
+        match self.types.get(name) {
+            Some(MetaType::Scalar {
+                round_trip: Some(round_trip),
+                ..
+            }) => Some(round_trip(value)),
+            _ => None,
+        }
+    }
+
+    /// Returns the maximum nesting depth of any input object in the registry,
+    /// or `0` if there are no input objects.
+    ///
+    /// An input object whose fields are all leaf types has a depth of `1`,
+    /// every input object field adds one level. If an input object can
+    /// (directly or indirectly) contain itself, the depth is unbounded and
+    /// `usize::MAX` is returned.
+    pub fn max_input_nesting_depth(&self) -> usize {
+        fn input_depth<'a>(
             types: &'a BTreeMap<String, MetaType>,
-            used_types: &mut BTreeSet<&'a str>,
-            input_value: &'a MetaInputValue,
+            visiting: &mut HashSet<&'a str>,
+            depths: &mut HashMap<&'a str, usize>,
+            type_name: &'a str,
+        ) -> usize {
+            if let Some(depth) = depths.get(type_name) {
+                return *depth;
+            }
+
+            let input_fields = match types.get(type_name) {
+                Some(MetaType::InputObject { input_fields, .. }) => input_fields,
+                _ => return 0,
+            };
+
+            if !visiting.insert(type_name) {
+                // cyclic input object
+                return usize::MAX;
+            }
+
+            let depth = input_fields
+                .values()
+                .map(|field| {
+                    input_depth(
+                        types,
+                        visiting,
+                        depths,
+                        MetaTypeName::concrete_typename(&field.ty),
+                    )
+                })
+                .max()
+                .unwrap_or_default()
+                .saturating_add(1);
+
+            visiting.remove(type_name);
+            depths.insert(type_name, depth);
+            depth
+        }
+
+        let mut visiting = HashSet::new();
+        let mut depths = HashMap::new();
+        self.types
+            .keys()
+            .map(|type_name| input_depth(&self.types, &mut visiting, &mut depths, type_name))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Resolves a path made of a type name followed by field names, such as
+    /// `["Query", "user", "address", "zip"]`, to the concrete type of the
+    /// last field, unwrapping the list and non-null wrappers at each step.
+    ///
+    /// Returns `None` if the path is empty, if a type or a field does not
+    /// exist, or if a field is selected on a type that has no fields.
+    pub fn field_path_type(&self, path: &[&str]) -> Option<&MetaType> {
+        let (type_name, field_names) = path.split_first()?;
+        let mut ty = self.types.get(*type_name)?;
+        for field_name in field_names {
+            ty = self.concrete_type_by_name(&ty.field_by_name(field_name)?.ty)?;
+        }
+        Some(ty)
+    }
+
+    /// Returns the arguments of the specified field, or `None` if the field
+    /// does not exist.
+    pub fn field_arguments(
+        &self,
+        type_name: &str,
+        field_name: &str,
+    ) -> Option<&IndexMap<String, MetaInputValue>> {
+        Some(&self.types.get(type_name)?.field_by_name(field_name)?.args)
+    }
+
+    /// Returns the complexity declared for the specified field, or `None` if
+    /// the field does not exist or has the default complexity.
+    pub fn field_complexity(&self, type_name: &str, field_name: &str) -> Option<ComplexityType> {
+        self.types
+            .get(type_name)?
+            .field_by_name(field_name)?
+            .compute_complexity
+            .clone()
+    }
+
+    /// Returns the cache control of the specified field, combined with the
+    /// cache control of the same field on every interface implemented by the
+    /// type.
+    ///
+    /// The result is private if any of them is private, and has the minimum
+    /// max age, where `no_cache` wins and a max age of `0` means that the field
+    /// has no cache hint and is ignored, like during execution.
+    ///
+    /// Returns the field's own cache control if no interface declares the
+    /// field, and the default cache control if the field does not exist.
+    pub fn effective_field_cache_control(&self, type_name: &str, field_name: &str) -> CacheControl {
+        let cache_control = match self
+            .types
+            .get(type_name)
+            .and_then(|ty| ty.field_by_name(field_name))
+        {
+            Some(field) => field.cache_control,
+            None => return CacheControl::default(),
+        };
+
+        self.interfaces_of(type_name)
+            .into_iter()
+            .filter_map(|interface| self.types.get(interface)?.field_by_name(field_name))
+            .fold(cache_control, |cache_control, field| {
+                cache_control.merge(&field.cache_control)
+            })
+    }
+
+    /// Returns the sum of the constant complexities declared for the fields
+    /// of all the types, as an upper-bound estimate for documentation.
+    ///
+    /// Returns `None` if the complexity of any field depends on its
+    /// arguments, since it cannot be evaluated statically.
+    pub fn max_static_complexity(&self) -> Option<usize> {
+        self.types
+            .values()
+            .filter_map(MetaType::fields)
+            .flat_map(|fields| fields.values())
+            .try_fold(0usize, |sum, field| match &field.compute_complexity {
+                Some(ComplexityType::Const(n)) => Some(sum.saturating_add(*n)),
+                Some(ComplexityType::Fn(_) | ComplexityType::Multiplier { .. }) => None,
+                None => Some(sum),
+            })
+    }
+
+    /// Returns every field argument whose concrete type is `input_type`, as
+    /// `(type name, field name, argument name)` tuples, ordered by type name.
+    pub fn argument_usages<'a>(&'a self, input_type: &str) -> Vec<(&'a str, &'a str, &'a str)> {
+        self.types
+            .values()
+            .filter_map(|ty| ty.fields().map(|fields| (ty.name(), fields)))
+            .flat_map(|(type_name, fields)| {
+                fields.values().flat_map(move |field| {
+                    field
+                        .args
+                        .values()
+                        .map(move |arg| (type_name, field.name.as_str(), arg))
+                })
+            })
+            .filter(|(_, _, arg)| MetaTypeName::concrete_typename(&arg.ty) == input_type)
+            .map(|(type_name, field_name, arg)| (type_name, field_name, arg.name.as_str()))
+            .collect()
+    }
+
+    /// Returns the names of the input objects in dependency order, so that
+    /// every input object comes after the input objects used by its fields.
+    ///
+    /// Input objects are visited by name and their fields in declaration
+    /// order. If the input objects contain a cycle, such as a nullable
+    /// self-reference, the field closing the cycle is ignored and the first
+    /// cycle found is returned together with the resulting order.
+    pub fn input_object_dependency_order(&self) -> Result<Vec<String>, CycleInfo> {
+        fn is_input_object(registry: &Registry, name: &str) -> bool {
+            matches!(registry.types.get(name), Some(MetaType::InputObject { .. }))
+        }
+
+        fn visit<'a>(
+            registry: &'a Registry,
+            name: &'a str,
+            visited: &mut HashSet<&'a str>,
+            stack: &mut Vec<&'a str>,
+            order: &mut Vec<String>,
+            cycle: &mut Option<CycleInfo>,
         ) {
-            traverse_type(
-                types,
-                used_types,
-                MetaTypeName::concrete_typename(&input_value.ty),
-            );
+            visited.insert(name);
+            stack.push(name);
+            for reference in registry
+                .types
+                .get(name)
+                .into_iter()
+                .flat_map(type_references)
+            {
+                let dependency = reference.type_name();
+                if !is_input_object(registry, dependency) {
+                    continue;
+                }
+                if let Some(start) = stack.iter().position(|name| *name == dependency) {
+                    if cycle.is_none() {
+                        *cycle = Some(CycleInfo {
+                            types: stack[start..].iter().map(ToString::to_string).collect(),
+                            break_point: (name.to_string(), reference.member().unwrap_or_default()),
+                            order: Vec::new(),
+                        });
+                    }
+                } else if !visited.contains(dependency) {
+                    visit(registry, dependency, visited, stack, order, cycle);
+                }
+            }
+            stack.pop();
+            order.push(name.to_string());
         }
 
+        let mut visited = HashSet::new();
+        let mut order = Vec::new();
+        let mut cycle = None;
+        for name in self.types.keys() {
+            if is_input_object(self, name) && !visited.contains(name.as_str()) {
+                visit(
+                    self,
+                    name,
+                    &mut visited,
+                    &mut Vec::new(),
+                    &mut order,
+                    &mut cycle,
+                );
+            }
+        }
+
+        match cycle {
+            Some(cycle) => Err(CycleInfo { order, ..cycle }),
+            None => Ok(order),
+        }
+    }
+
+    /// Returns the names of all the input types (input objects, enums and
+    /// scalars) reachable from the arguments of the specified field, including
+    /// the types of the fields of the reachable input objects.
+    ///
+    /// Returns an empty set if the type or the field does not exist.
+    pub fn input_closure(&self, type_name: &str, field_name: &str) -> BTreeSet<String> {
+        let mut closure = BTreeSet::new();
+        let mut pending = match self
+            .types
+            .get(type_name)
+            .and_then(|ty| ty.field_by_name(field_name))
+        {
+            Some(field) => field
+                .args
+                .values()
+                .map(|arg| MetaTypeName::concrete_typename(&arg.ty))
+                .collect::<Vec<_>>(),
+            None => return closure,
+        };
+
+        while let Some(type_name) = pending.pop() {
+            if closure.contains(type_name) {
+                continue;
+            }
+            if let Some(ty) = self.types.get(type_name) {
+                closure.insert(type_name.to_string());
+                if let MetaType::InputObject { input_fields, .. } = ty {
+                    pending.extend(
+                        input_fields
+                            .values()
+                            .map(|field| MetaTypeName::concrete_typename(&field.ty)),
+                    );
+                }
+            }
+        }
+
+        closure
+    }
+
+    pub fn set_description(&mut self, name: &str, desc: impl Into<String>) {
+        let desc = desc.into();
+        match self.types.get_mut(name) {
+            Some(MetaType::Scalar { description, .. }) => *description = Some(desc),
+            Some(MetaType::Object { description, .. }) => *description = Some(desc),
+            Some(MetaType::Interface { description, .. }) => *description = Some(desc),
+            Some(MetaType::Union { description, .. }) => *description = Some(desc),
+            Some(MetaType::Enum { description, .. }) => *description = Some(desc),
+            Some(MetaType::InputObject { description, .. }) => *description = Some(desc),
+            None => {}
+        }
+    }
+
+    /// Set the description of the field `field_name` of the object or
+    /// interface `type_name`.
+    ///
+    /// Does nothing if the type does not exist, is not an object or an
+    /// interface, or has no such field.
+    pub fn set_field_description(
+        &mut self,
+        type_name: &str,
+        field_name: &str,
+        desc: impl Into<String>,
+    ) {
+        if let Some(field) = self
+            .types
+            .get_mut(type_name)
+            .and_then(MetaType::fields_mut)
+            .and_then(|fields| fields.get_mut(field_name))
+        {
+            field.description = Some(desc.into());
+        }
+    }
+
+    /// Rewrite every description in the registry (types, fields, arguments,
+    /// input fields, enum values and directives) with `f`.
+    ///
+    /// A description is replaced with the value returned by `f`, or removed if
+    /// `f` returns `None`.
+    pub fn transform_descriptions(&mut self, f: impl Fn(&str) -> Option<String>) {
+        let transform = |description: &mut Option<String>| {
+            if let Some(desc) = description.as_deref() {
+                *description = f(desc);
+            }
+        };
+
+        let transform_input_values = |values: &mut IndexMap<String, MetaInputValue>| {
+            for value in values.values_mut() {
+                transform(&mut value.description);
+            }
+        };
+
+        for ty in self.types.values_mut() {
+            match ty {
+                MetaType::Scalar { description, .. } | MetaType::Union { description, .. } => {
+                    transform(description)
+                }
+                MetaType::Object {
+                    description,
+                    fields,
+                    ..
+                }
+                | MetaType::Interface {
+                    description,
+                    fields,
+                    ..
+                } => {
+                    transform(description);
+                    for field in fields.values_mut() {
+                        transform(&mut field.description);
+                        transform_input_values(&mut field.args);
+                    }
+                }
+                MetaType::Enum {
+                    description,
+                    enum_values,
+                    ..
+                } => {
+                    transform(description);
+                    for value in enum_values.values_mut() {
+                        transform(&mut value.description);
+                    }
+                }
+                MetaType::InputObject {
+                    description,
+                    input_fields,
+                    ..
+                } => {
+                    transform(description);
+                    transform_input_values(input_fields);
+                }
+            }
+        }
+
+        for directive in self.directives.values_mut() {
+            transform(&mut directive.description);
+            transform_input_values(&mut directive.args);
+        }
+    }
+
+    /// Returns a copy of the registry that carries no executable logic, for
+    /// pure schema analysis such as SDL export, diffing and validation.
+    ///
+    /// Scalar validators accept every value, visibility functions are removed
+    /// so that everything is visible, and complexity functions are replaced
+    /// with a constant complexity of `1`.
+    pub fn schema_only(&self) -> Registry {
+        fn strip_input_values(values: &mut IndexMap<String, MetaInputValue>) {
+            for value in values.values_mut() {
+                value.visible = None;
+            }
+        }
+
+        fn strip_fields(fields: &mut IndexMap<String, MetaField>) {
+            for field in fields.values_mut() {
+                field.visible = None;
+                if let Some(ComplexityType::Fn(_)) = field.compute_complexity {
+                    field.compute_complexity = Some(ComplexityType::Const(1));
+                }
+                strip_input_values(&mut field.args);
+            }
+        }
+
+        let types = self
+            .types
+            .iter()
+            .map(|(name, ty)| {
+                let mut ty = ty.clone();
+                match &mut ty {
+                    MetaType::Scalar {
+                        is_valid,
+                        round_trip,
+                        visible,
+                        ..
+                    } => {
+                        *is_valid = |_| true;
+                        *round_trip = None;
+                        *visible = None;
+                    }
+                    MetaType::Object {
+                        fields, visible, ..
+                    }
+                    | MetaType::Interface {
+                        fields, visible, ..
+                    } => {
+                        *visible = None;
+                        strip_fields(fields);
+                    }
+                    MetaType::Union { visible, .. } => *visible = None,
+                    MetaType::Enum {
+                        enum_values,
+                        visible,
+                        ..
+                    } => {
+                        *visible = None;
+                        for value in enum_values.values_mut() {
+                            value.visible = None;
+                        }
+                    }
+                    MetaType::InputObject {
+                        input_fields,
+                        visible,
+                        ..
+                    } => {
+                        *visible = None;
+                        strip_input_values(input_fields);
+                    }
+                }
+                (name.clone(), ty)
+            })
+            .collect();
+
+        let directives = self
+            .directives
+            .iter()
+            .map(|(name, directive)| {
+                let mut args = directive.args.clone();
+                strip_input_values(&mut args);
+                (
+                    name.clone(),
+                    MetaDirective {
+                        name: directive.name,
+                        description: directive.description.clone(),
+                        locations: directive.locations.clone(),
+                        args,
+                        is_repeatable: directive.is_repeatable,
+                        visible: None,
+                        deprecation: directive.deprecation.clone(),
+                    },
+                )
+            })
+            .collect();
+
+        Registry {
+            types,
+            directives,
+            implements: self.implements.clone(),
+            query_type: self.query_type.clone(),
+            mutation_type: self.mutation_type.clone(),
+            subscription_type: self.subscription_type.clone(),
+            introspection_mode: self.introspection_mode,
+            enable_federation: self.enable_federation,
+            enable_apollo_link: self.enable_apollo_link,
+            federation_subscription: self.federation_subscription,
+            ignore_name_conflicts: self.ignore_name_conflicts.clone(),
+            collect_name_conflicts: self.collect_name_conflicts,
+            name_conflict_typenames: self.name_conflict_typenames.clone(),
+            reference_stubs: self.reference_stubs.clone(),
+            link_imports: self.link_imports.clone(),
+            link_specs: self.link_specs.clone(),
+            compose_directives: self.compose_directives.clone(),
+            enable_incremental_delivery: self.enable_incremental_delivery,
+        }
+    }
+
+    pub fn remove_unused_types(&mut self) {
+        self.remove_unused_types_except(&HashSet::new());
+    }
+
+    /// Remove the types that are not reachable from the root types, the
+    /// entities, the directives or the types in `keep`.
+    ///
+    /// The types in `keep` are kept together with all the types they
+    /// reference, which is useful for types only used by resolvers
+    /// registered at runtime.
+    pub fn remove_unused_types_except(&mut self, keep: &HashSet<String>) {
+        let mut used_types = BTreeSet::new();
+        let mut unused_types = BTreeSet::new();
+
         fn traverse_type<'a>(
             types: &'a BTreeMap<String, MetaType>,
             used_types: &mut BTreeSet<&'a str>,
@@ -854,42 +2038,19 @@ impl Registry {
 
             if let Some(ty) = types.get(type_name) {
                 used_types.insert(type_name);
-                match ty {
-                    MetaType::Object { fields, .. } => {
-                        for field in fields.values() {
-                            traverse_field(types, used_types, field);
-                        }
-                    }
-                    MetaType::Interface {
-                        fields,
-                        possible_types,
-                        ..
-                    } => {
-                        for field in fields.values() {
-                            traverse_field(types, used_types, field);
-                        }
-                        for type_name in possible_types.iter() {
-                            traverse_type(types, used_types, type_name);
-                        }
-                    }
-                    MetaType::Union { possible_types, .. } => {
-                        for type_name in possible_types.iter() {
-                            traverse_type(types, used_types, type_name);
-                        }
-                    }
-                    MetaType::InputObject { input_fields, .. } => {
-                        for field in input_fields.values() {
-                            traverse_input_value(types, used_types, field);
-                        }
-                    }
-                    _ => {}
+                for reference in type_references(ty) {
+                    traverse_type(types, used_types, reference.type_name());
                 }
             }
         }
 
         for directive in self.directives.values() {
             for arg in directive.args.values() {
-                traverse_input_value(&self.types, &mut used_types, arg);
+                traverse_type(
+                    &self.types,
+                    &mut used_types,
+                    MetaTypeName::concrete_typename(&arg.ty),
+                );
             }
         }
 
@@ -897,20 +2058,13 @@ impl Registry {
             .into_iter()
             .chain(self.mutation_type.iter())
             .chain(self.subscription_type.iter())
+            .chain(keep.iter())
         {
             traverse_type(&self.types, &mut used_types, type_name);
         }
 
-        for ty in self.types.values().filter(|ty| match ty {
-            MetaType::Object {
-                keys: Some(keys), ..
-            }
-            | MetaType::Interface {
-                keys: Some(keys), ..
-            } => !keys.is_empty(),
-            _ => false,
-        }) {
-            traverse_type(&self.types, &mut used_types, ty.name());
+        for (name, _) in self.entities() {
+            traverse_type(&self.types, &mut used_types, name);
         }
 
         for ty in self.types.values() {
@@ -925,7 +2079,122 @@ impl Registry {
         }
     }
 
+    /// Returns every reference to the concrete type `type_name` as
+    /// `(containing type, member)` pairs, ordered by containing type and then
+    /// by declaration order.
+    ///
+    /// The member is `user` for a field or an input field, `user(id:)` for an
+    /// argument, and empty for a union member or a possible type of an
+    /// interface.
+    pub fn referenced_by(&self, type_name: &str) -> Vec<(String, String)> {
+        self.types
+            .values()
+            .flat_map(|ty| {
+                type_references(ty)
+                    .into_iter()
+                    .filter(|reference| reference.type_name() == type_name)
+                    .map(|reference| {
+                        (
+                            ty.name().to_string(),
+                            reference.member().unwrap_or_default(),
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    /// Remove the specified type, and remove it from the possible types of
+    /// interfaces and from the interface implementations.
+    ///
+    /// Returns the references to the removed type that are left in the
+    /// registry, such as `Query.user` for a field, `Query.user(id:)` for an
+    /// argument, `UserInput.friend` for an input field, `SearchResult` for a
+    /// union member or `@auth(role:)` for a directive argument. Returns an
+    /// error without removing anything if the type is a root type.
+    pub fn remove_type(&mut self, name: &str) -> Result<Vec<String>, SchemaError> {
+        let roots = [
+            ("query", Some(self.query_type.as_str())),
+            ("mutation", self.mutation_type.as_deref()),
+            ("subscription", self.subscription_type.as_deref()),
+        ];
+        if let Some((operation, _)) = roots.iter().find(|(_, root)| *root == Some(name)) {
+            return Err(SchemaError::new(
+                name,
+                format!("The {} root type `{}` cannot be removed", operation, name),
+            ));
+        }
+
+        if self.types.remove(name).is_none() {
+            return Ok(Vec::new());
+        }
+
+        for ty in self.types.values_mut() {
+            if let MetaType::Interface { possible_types, .. } = ty {
+                possible_types.shift_remove(name);
+            }
+        }
+        self.implements.remove(name);
+        for interfaces in self.implements.values_mut() {
+            interfaces.remove(name);
+        }
+        self.implements
+            .retain(|_, interfaces| !interfaces.is_empty());
+
+        let mut references = Vec::new();
+        for ty in self.types.values() {
+            for reference in type_references(ty)
+                .into_iter()
+                .filter(|reference| reference.type_name() == name)
+            {
+                references.push(match reference.member() {
+                    Some(member) => format!("{}.{}", ty.name(), member),
+                    None => ty.name().to_string(),
+                });
+            }
+        }
+
+        for directive in self.directives() {
+            for arg in directive
+                .args
+                .values()
+                .filter(|arg| MetaTypeName::concrete_typename(&arg.ty) == name)
+            {
+                references.push(format!("@{}({}:)", directive.name, arg.name));
+            }
+        }
+
+        Ok(references)
+    }
+
+    /// Returns the names of the types visible to the current request, see
+    /// [`Registry::find_visible_types_for`].
+    ///
+    /// The introspection mode is the most restrictive of the mode of the
+    /// schema and the mode of the request.
     pub fn find_visible_types(&self, ctx: &Context<'_>) -> HashSet<&str> {
+        let mode = match (self.introspection_mode, ctx.query_env.introspection_mode) {
+            (IntrospectionMode::Disabled, _) | (_, IntrospectionMode::Disabled) => {
+                IntrospectionMode::Disabled
+            }
+            (IntrospectionMode::IntrospectionOnly, _)
+            | (_, IntrospectionMode::IntrospectionOnly) => IntrospectionMode::IntrospectionOnly,
+            _ => IntrospectionMode::Enabled,
+        };
+        self.find_visible_types_for(ctx, mode)
+    }
+
+    /// Returns the names of the types visible to the current request in the
+    /// specified introspection mode.
+    ///
+    /// A type is visible if it is reachable from a root type, an entity or a
+    /// directive through visible fields and arguments. The built-in scalars
+    /// are always visible. The introspection types, whose names start with
+    /// `__`, are visible unless the mode is [`IntrospectionMode::Disabled`].
+    pub fn find_visible_types_for(
+        &self,
+        ctx: &Context<'_>,
+        mode: IntrospectionMode,
+    ) -> HashSet<&str> {
         let mut visible_types = HashSet::new();
 
         fn traverse_field<'a>(
@@ -1032,16 +2301,8 @@ impl Registry {
             traverse_type(ctx, &self.types, &mut visible_types, type_name);
         }
 
-        for ty in self.types.values().filter(|ty| match ty {
-            MetaType::Object {
-                keys: Some(keys), ..
-            }
-            | MetaType::Interface {
-                keys: Some(keys), ..
-            } => !keys.is_empty(),
-            _ => false,
-        }) {
-            traverse_type(ctx, &self.types, &mut visible_types, ty.name());
+        for (name, _) in self.entities() {
+            traverse_type(ctx, &self.types, &mut visible_types, name);
         }
 
         for ty in self.types.values() {
@@ -1061,7 +2322,9 @@ impl Registry {
             .values()
             .filter_map(|ty| {
                 let name = ty.name();
-                if is_system_type(name) || visible_types.contains(name) {
+                if name.starts_with("__") {
+                    (mode != IntrospectionMode::Disabled).then(|| name)
+                } else if is_builtin_scalar(name) || visible_types.contains(name) {
                     Some(name)
                 } else {
                     None
@@ -1083,5 +2346,162 @@ fn is_system_type(name: &str) -> bool {
         return true;
     }
 
+    is_builtin_scalar(name)
+}
+
+fn is_builtin_scalar(name: &str) -> bool {
     name == "Boolean" || name == "Int" || name == "Float" || name == "String" || name == "ID"
 }
+
+/// A reference from a type to another type, see [`type_references`].
+#[derive(Clone, Copy)]
+enum TypeReference<'a> {
+    Field(&'a MetaField),
+    Argument(&'a MetaField, &'a MetaInputValue),
+    InputField(&'a MetaInputValue),
+    PossibleType(&'a str),
+}
+
+impl<'a> TypeReference<'a> {
+    /// Returns the concrete name of the referenced type.
+    fn type_name(self) -> &'a str {
+        match self {
+            TypeReference::Field(field) => MetaTypeName::concrete_typename(&field.ty),
+            TypeReference::Argument(_, input_value) | TypeReference::InputField(input_value) => {
+                MetaTypeName::concrete_typename(&input_value.ty)
+            }
+            TypeReference::PossibleType(type_name) => type_name,
+        }
+    }
+
+    /// Returns the name of the member holding the reference, or `None` for a
+    /// possible type.
+    fn member(self) -> Option<String> {
+        match self {
+            TypeReference::Field(field) => Some(field.name.clone()),
+            TypeReference::Argument(field, arg) => Some(format!("{}({}:)", field.name, arg.name)),
+            TypeReference::InputField(input_value) => Some(input_value.name.clone()),
+            TypeReference::PossibleType(_) => None,
+        }
+    }
+}
+
+/// Returns the references from `ty` to other types: the types of its fields
+/// and their arguments, of its input fields, and its possible types.
+fn type_references(ty: &MetaType) -> Vec<TypeReference<'_>> {
+    let mut references = Vec::new();
+    if let Some(fields) = ty.fields() {
+        for field in fields.values() {
+            references.push(TypeReference::Field(field));
+            references.extend(
+                field
+                    .args
+                    .values()
+                    .map(|arg| TypeReference::Argument(field, arg)),
+            );
+        }
+    }
+    match ty {
+        MetaType::Interface { possible_types, .. } | MetaType::Union { possible_types, .. } => {
+            references.extend(
+                possible_types
+                    .iter()
+                    .map(|type_name| TypeReference::PossibleType(type_name)),
+            );
+        }
+        MetaType::InputObject { input_fields, .. } => {
+            references.extend(input_fields.values().map(TypeReference::InputField));
+        }
+        _ => {}
+    }
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_subtype(sub: &str, super_type: &str) -> bool {
+        MetaTypeName::create(super_type).is_subtype(&MetaTypeName::create(sub))
+    }
+
+    #[test]
+    fn test_is_subtype() {
+        assert!(is_subtype("T", "T"));
+        assert!(is_subtype("T!", "T"));
+        assert!(is_subtype("T!", "T!"));
+        assert!(!is_subtype("T", "T!"));
+        assert!(!is_subtype("T", "U"));
+        assert!(!is_subtype("T!", "U"));
+
+        assert!(is_subtype("[T]", "[T]"));
+        assert!(is_subtype("[T!]", "[T]"));
+        assert!(!is_subtype("[T]", "[T!]"));
+        assert!(is_subtype("[T]!", "[T]"));
+        assert!(is_subtype("[T!]!", "[T]"));
+        assert!(is_subtype("[T!]!", "[T!]"));
+        assert!(is_subtype("[T!]!", "[T]!"));
+        assert!(!is_subtype("[T]", "[T]!"));
+        assert!(!is_subtype("[T]!", "[T!]!"));
+
+        assert!(is_subtype("[[T!]!]!", "[[T]]"));
+        assert!(!is_subtype("[[T]]", "[[T!]]"));
+
+        assert!(!is_subtype("T", "[T]"));
+        assert!(!is_subtype("[T]", "T"));
+        assert!(!is_subtype("T!", "[T]"));
+        assert!(!is_subtype("[T]!", "T"));
+
+        // (sub, super, expected)
+        let table = [
+            ("Int", "Int", true),
+            ("Int!", "Int", true),
+            ("Int", "Int!", false),
+            ("Int!", "Float", false),
+            ("[Int]", "[Int]", true),
+            ("[Int!]", "[Int]", true),
+            ("[Int!]!", "[Int]", true),
+            ("[Int!]!", "[Int]!", true),
+            ("[Int]!", "[Int]", true),
+            ("[Int]", "[Int!]", false),
+            ("[Int]", "[Int]!", false),
+            ("[Int]!", "[Int!]!", false),
+            ("[Int!]", "[Int!]!", false),
+            ("[[Int!]!]!", "[[Int]]", true),
+            ("[[Int!]]", "[[Int]!]", false),
+            ("[Int!]", "Int", false),
+            ("Int!", "[Int]", false),
+            ("[[Int]]", "[Int]", false),
+        ];
+        for (sub, super_type, expected) in table {
+            assert_eq!(
+                is_subtype(sub, super_type),
+                expected,
+                "{} is_subtype of {}",
+                sub,
+                super_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_input_value_is_required() {
+        let input_value = |ty: &str, default_value: Option<&str>| MetaInputValue {
+            name: "value".to_string(),
+            description: None,
+            ty: ty.to_string(),
+            default_value: default_value.map(ToString::to_string),
+            deprecation: Deprecation::NoDeprecated,
+            visible: None,
+            inaccessible: false,
+            tags: &[],
+            is_secret: false,
+        };
+
+        assert!(input_value("Int!", None).is_required());
+        assert!(input_value("[Int]!", None).is_required());
+        assert!(!input_value("Int!", Some("1")).is_required());
+        assert!(!input_value("Int", None).is_required());
+        assert!(!input_value("[Int!]", None).is_required());
+    }
+}
@@ -0,0 +1,437 @@
+use std::collections::{HashMap, HashSet};
+
+use indexmap::IndexMap;
+use thiserror::Error;
+
+use crate::{
+    registry::{
+        export_sdl::SPEC_DIRECTIVES, is_builtin_scalar, MetaInputValue, MetaType, MetaTypeName,
+        Registry,
+    },
+    Name, Value,
+};
+
+/// The kind of a name passed to [`Registry::normalize_names`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum NameKind {
+    /// The name of a type.
+    Type,
+    /// The name of a field of an object or an interface.
+    Field,
+    /// The name of a field of an input object.
+    InputField,
+    /// The name of a field argument or of a directive argument.
+    Argument,
+    /// The name of an enum value.
+    EnumValue,
+}
+
+/// An error returned by [`Registry::rename_type`] and
+/// [`Registry::normalize_names`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RenameError {
+    /// The type to rename does not exist.
+    #[error("The type `{0}` does not exist")]
+    NotFound(String),
+
+    /// The type to rename is an introspection type or a built-in scalar.
+    #[error("The type `{0}` is a system type and cannot be renamed")]
+    SystemType(String),
+
+    /// A type with the new name already exists.
+    #[error("The type `{0}` already exists")]
+    AlreadyExists(String),
+
+    /// Two fields, input fields, arguments or enum values of `owner` would
+    /// have the same name after renaming.
+    #[error("`{owner}` would have two members named `{name}`")]
+    DuplicateMember { owner: String, name: String },
+}
+
+fn check_entries<'a>(
+    names: impl Iterator<Item = &'a String>,
+    kind: NameKind,
+    f: &impl Fn(NameKind, &str) -> Option<String>,
+    owner: &str,
+) -> Result<(), RenameError> {
+    let mut seen = HashSet::new();
+    for name in names {
+        let new_name = f(kind, name).unwrap_or_else(|| name.clone());
+        if !seen.insert(new_name.clone()) {
+            return Err(RenameError::DuplicateMember {
+                owner: owner.to_string(),
+                name: new_name,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn rename_entries<V>(
+    entries: &mut IndexMap<String, V>,
+    kind: NameKind,
+    f: &impl Fn(NameKind, &str) -> Option<String>,
+    set_name: impl Fn(&mut V, String),
+) {
+    *entries = std::mem::take(entries)
+        .into_iter()
+        .map(|(name, mut value)| match f(kind, &name) {
+            Some(new_name) => {
+                set_name(&mut value, new_name.clone());
+                (new_name, value)
+            }
+            None => (name, value),
+        })
+        .collect();
+}
+
+/// The renamed enum values and input fields, used to rewrite the default
+/// values that reference them.
+#[derive(Default)]
+struct DefaultValueRenames {
+    /// The renamed values of each enum, by their old name.
+    enum_values: HashMap<String, HashMap<String, String>>,
+    /// The new name, if renamed, and the type of the fields of each input
+    /// object, by their old name.
+    input_fields: HashMap<String, HashMap<String, (Option<String>, String)>>,
+}
+
+impl DefaultValueRenames {
+    fn new(registry: &Registry, f: &impl Fn(NameKind, &str) -> Option<String>) -> Self {
+        let mut renames = Self::default();
+        for ty in registry.types.values() {
+            if ty.name().starts_with("__") {
+                continue;
+            }
+
+            match ty {
+                MetaType::Enum {
+                    name, enum_values, ..
+                } => {
+                    let values = enum_values
+                        .keys()
+                        .filter_map(|value| {
+                            f(NameKind::EnumValue, value)
+                                .map(|new_value| (value.clone(), new_value))
+                        })
+                        .collect::<HashMap<_, _>>();
+                    if !values.is_empty() {
+                        renames.enum_values.insert(name.clone(), values);
+                    }
+                }
+                MetaType::InputObject {
+                    name, input_fields, ..
+                } => {
+                    let fields = input_fields
+                        .values()
+                        .map(|field| {
+                            (
+                                field.name.clone(),
+                                (f(NameKind::InputField, &field.name), field.ty.clone()),
+                            )
+                        })
+                        .collect();
+                    renames.input_fields.insert(name.clone(), fields);
+                }
+                _ => {}
+            }
+        }
+        renames
+    }
+
+    /// Rewrites the default value of `input_value`, if it can be parsed and
+    /// references a renamed enum value or input field.
+    fn rename_default_value(&self, input_value: &mut MetaInputValue) {
+        if let Some(value) = input_value.default_value_lenient() {
+            let new_value = self.rename_value(value.clone(), &input_value.ty);
+            if new_value != value {
+                input_value.default_value = Some(new_value.to_string());
+            }
+        }
+    }
+
+    fn rename_value(&self, value: Value, ty: &str) -> Value {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => self.rename_value(value, ty),
+            MetaTypeName::List(ty) => match value {
+                Value::List(items) => Value::List(
+                    items
+                        .into_iter()
+                        .map(|item| self.rename_value(item, ty))
+                        .collect(),
+                ),
+                value => self.rename_value(value, ty),
+            },
+            MetaTypeName::Named(ty) => {
+                let enum_value = |name: &str| self.enum_values.get(ty)?.get(name).cloned();
+                match value {
+                    Value::Enum(name) => match enum_value(&name) {
+                        Some(new_name) => Value::Enum(Name::new(new_name)),
+                        None => Value::Enum(name),
+                    },
+                    Value::String(s) => Value::String(enum_value(&s).unwrap_or(s)),
+                    Value::Object(fields) => match self.input_fields.get(ty) {
+                        Some(input_fields) => Value::Object(
+                            fields
+                                .into_iter()
+                                .map(|(name, value)| match input_fields.get(name.as_str()) {
+                                    Some((new_name, ty)) => (
+                                        new_name.as_ref().map(Name::new).unwrap_or(name),
+                                        self.rename_value(value, ty),
+                                    ),
+                                    None => (name, value),
+                                })
+                                .collect(),
+                        ),
+                        None => Value::Object(fields),
+                    },
+                    value => value,
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn set_type_name(ty: &mut MetaType, new_name: String) {
+    match ty {
+        MetaType::Scalar { name, .. }
+        | MetaType::Object { name, .. }
+        | MetaType::Interface { name, .. }
+        | MetaType::Union { name, .. }
+        | MetaType::Enum { name, .. }
+        | MetaType::InputObject { name, .. } => *name = new_name,
+    }
+}
+
+impl Registry {
+    /// Renames the types, fields, input fields, arguments and enum values of
+    /// the registry with `f`, for example to migrate `snake_case` field names
+    /// to `camelCase`.
+    ///
+    /// `f` receives the kind and the current name, and returns the new name
+    /// or `None` to leave the name unchanged. All references to renamed types
+    /// are rewritten, as well as the default values that reference renamed
+    /// enum values or input fields. The arguments of the directives are
+    /// renamed as [`NameKind::Argument`]. Introspection types and fields, the
+    /// built-in scalars and the directives of the specification are never
+    /// renamed.
+    ///
+    /// Returns an error, leaving the registry unchanged, if two types or two
+    /// members of the same type would have the same name after renaming.
+    ///
+    /// Note: The field sets of federation directives such as `@key`, and the
+    /// default values that cannot be parsed, are not rewritten.
+    pub fn normalize_names(
+        &mut self,
+        f: impl Fn(NameKind, &str) -> Option<String>,
+    ) -> Result<(), RenameError> {
+        let mut renames = HashMap::new();
+        for name in self.types.keys() {
+            if name.starts_with("__") || is_builtin_scalar(name) {
+                continue;
+            }
+            if let Some(new_name) = f(NameKind::Type, name) {
+                if &new_name != name {
+                    renames.insert(name.clone(), new_name);
+                }
+            }
+        }
+
+        let mut type_names = HashSet::new();
+        for name in self.types.keys() {
+            let new_name = renames.get(name).unwrap_or(name);
+            if !type_names.insert(new_name) {
+                return Err(RenameError::AlreadyExists(new_name.clone()));
+            }
+        }
+
+        let f = |kind, name: &str| {
+            if name.starts_with("__") {
+                None
+            } else {
+                f(kind, name)
+            }
+        };
+
+        for ty in self.types.values() {
+            if ty.name().starts_with("__") {
+                continue;
+            }
+
+            match ty {
+                MetaType::Object { name, fields, .. }
+                | MetaType::Interface { name, fields, .. } => {
+                    for field in fields.values() {
+                        check_entries(
+                            field.args.keys(),
+                            NameKind::Argument,
+                            &f,
+                            &format!("{}.{}", name, field.name),
+                        )?;
+                    }
+                    check_entries(fields.keys(), NameKind::Field, &f, name)?;
+                }
+                MetaType::InputObject {
+                    name, input_fields, ..
+                } => check_entries(input_fields.keys(), NameKind::InputField, &f, name)?,
+                MetaType::Enum {
+                    name, enum_values, ..
+                } => check_entries(enum_values.keys(), NameKind::EnumValue, &f, name)?,
+                MetaType::Scalar { .. } | MetaType::Union { .. } => {}
+            }
+        }
+
+        for directive in self.directives.values() {
+            if !SPEC_DIRECTIVES.contains(&directive.name) {
+                check_entries(
+                    directive.args.keys(),
+                    NameKind::Argument,
+                    &f,
+                    &format!("@{}", directive.name),
+                )?;
+            }
+        }
+
+        let default_value_renames = DefaultValueRenames::new(self, &f);
+        for ty in self.types.values_mut() {
+            if ty.name().starts_with("__") {
+                continue;
+            }
+
+            match ty {
+                MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => {
+                    for arg in fields
+                        .values_mut()
+                        .flat_map(|field| field.args.values_mut())
+                    {
+                        default_value_renames.rename_default_value(arg);
+                    }
+                }
+                MetaType::InputObject { input_fields, .. } => {
+                    for field in input_fields.values_mut() {
+                        default_value_renames.rename_default_value(field);
+                    }
+                }
+                _ => {}
+            }
+        }
+        for arg in self
+            .directives
+            .values_mut()
+            .flat_map(|directive| directive.args.values_mut())
+        {
+            default_value_renames.rename_default_value(arg);
+        }
+
+        for directive in self.directives.values_mut() {
+            if !SPEC_DIRECTIVES.contains(&directive.name) {
+                rename_entries(&mut directive.args, NameKind::Argument, &f, |arg, name| {
+                    arg.name = name
+                });
+            }
+        }
+
+        for ty in self.types.values_mut() {
+            if ty.name().starts_with("__") {
+                continue;
+            }
+
+            match ty {
+                MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => {
+                    for field in fields.values_mut() {
+                        rename_entries(&mut field.args, NameKind::Argument, &f, |arg, name| {
+                            arg.name = name
+                        });
+                    }
+                    rename_entries(fields, NameKind::Field, &f, |field, name| field.name = name);
+                }
+                MetaType::InputObject { input_fields, .. } => {
+                    rename_entries(input_fields, NameKind::InputField, &f, |field, name| {
+                        field.name = name
+                    });
+                }
+                MetaType::Enum { enum_values, .. } => {
+                    rename_entries(enum_values, NameKind::EnumValue, &f, |value, name| {
+                        value.name = name
+                    });
+                }
+                MetaType::Scalar { .. } | MetaType::Union { .. } => {}
+            }
+        }
+
+        if renames.is_empty() {
+            return Ok(());
+        }
+
+        self.types = std::mem::take(&mut self.types)
+            .into_iter()
+            .map(|(name, mut ty)| match renames.get(&name) {
+                Some(new_name) => {
+                    set_type_name(&mut ty, new_name.clone());
+                    (new_name.clone(), ty)
+                }
+                None => (name, ty),
+            })
+            .collect();
+
+        for root in Some(&mut self.query_type)
+            .into_iter()
+            .chain(self.mutation_type.as_mut())
+            .chain(self.subscription_type.as_mut())
+        {
+            if let Some(new_name) = renames.get(root) {
+                *root = new_name.clone();
+            }
+        }
+
+        self.rename_type_references(&renames);
+        Ok(())
+    }
+
+    /// Renames the type `old` to `new` and rewrites all references to it,
+    /// including wrapped field and argument types such as `[Old!]!`, the
+    /// possible types of interfaces and unions, the implemented interfaces
+    /// and the root operation types.
+    ///
+    /// Returns an error if `old` does not exist or is an introspection type or
+    /// a built-in scalar, or if a type named `new` already exists.
+    pub fn rename_type(&mut self, old: &str, new: &str) -> Result<(), RenameError> {
+        if old.starts_with("__") || is_builtin_scalar(old) {
+            return Err(RenameError::SystemType(old.to_string()));
+        }
+        if !self.types.contains_key(old) {
+            return Err(RenameError::NotFound(old.to_string()));
+        }
+        if old == new {
+            return Ok(());
+        }
+        if self.types.contains_key(new) {
+            return Err(RenameError::AlreadyExists(new.to_string()));
+        }
+
+        self.types = std::mem::take(&mut self.types)
+            .into_iter()
+            .map(|(name, mut ty)| {
+                if name == old {
+                    set_type_name(&mut ty, new.to_string());
+                    (new.to_string(), ty)
+                } else {
+                    (name, ty)
+                }
+            })
+            .collect();
+
+        for root in Some(&mut self.query_type)
+            .into_iter()
+            .chain(self.mutation_type.as_mut())
+            .chain(self.subscription_type.as_mut())
+        {
+            if root == old {
+                *root = new.to_string();
+            }
+        }
+
+        self.rename_type_references(&HashMap::from([(old.to_string(), new.to_string())]));
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use std::hash::Hasher;
+
+use fnv::FnvHasher;
+
+/// A 64-bit FNV-1a hasher over a canonical byte encoding.
+///
+/// Unlike `DefaultHasher`, the result is specified and does not depend on the
+/// Rust version or the platform, so it can be persisted. Integers are written
+/// as fixed-width little-endian bytes, strings and sequences are prefixed by
+/// their length as a `u64`, and optional values by a `0`/`1` tag.
+pub(crate) struct StableHasher(FnvHasher);
+
+impl StableHasher {
+    pub(crate) fn new() -> Self {
+        Self(FnvHasher::default())
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.write(bytes);
+    }
+
+    pub(crate) fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+
+    pub(crate) fn write_bool(&mut self, value: bool) {
+        self.write_u8(value as u8);
+    }
+
+    pub(crate) fn write_len(&mut self, len: usize) {
+        self.write(&(len as u64).to_le_bytes());
+    }
+
+    pub(crate) fn write_str(&mut self, value: &str) {
+        self.write_len(value.len());
+        self.write(value.as_bytes());
+    }
+
+    pub(crate) fn write_opt_str(&mut self, value: Option<&str>) {
+        match value {
+            Some(value) => {
+                self.write_u8(1);
+                self.write_str(value);
+            }
+            None => self.write_u8(0),
+        }
+    }
+
+    pub(crate) fn write_strs<'a>(&mut self, values: impl ExactSizeIterator<Item = &'a str>) {
+        self.write_len(values.len());
+        for value in values {
+            self.write_str(value);
+        }
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a() {
+        let hash = |bytes: &[u8]| {
+            let mut hasher = StableHasher::new();
+            hasher.write(bytes);
+            hasher.finish()
+        };
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+}
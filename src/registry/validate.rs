@@ -0,0 +1,562 @@
+use thiserror::Error;
+
+use crate::registry::{
+    __DirectiveLocation, ComplexityType, MetaDirectiveInvocation, MetaInputValue, MetaType,
+    MetaTypeName, Registry,
+};
+
+/// An error found while validating a [`Registry`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message}")]
+pub struct SchemaError {
+    /// The name of the offending type.
+    pub type_name: String,
+    /// The name of the offending field, if the error is about a field.
+    pub field_name: Option<String>,
+    /// A human-readable description of the error.
+    pub message: String,
+}
+
+impl SchemaError {
+    pub(crate) fn new(type_name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            type_name: type_name.into(),
+            field_name: None,
+            message: message.into(),
+        }
+    }
+}
+
+/// Options for [`Registry::validate_with_options`]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ValidateOptions {
+    deny_orphan_interfaces: bool,
+}
+
+impl ValidateOptions {
+    /// Create a `ValidateOptions`
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Report interfaces without any implementors as errors instead of
+    /// warnings
+    #[inline]
+    #[must_use]
+    pub fn deny_orphan_interfaces(self) -> Self {
+        Self {
+            deny_orphan_interfaces: true,
+        }
+    }
+}
+
+impl Registry {
+    /// Check the registry for schema-level errors, returning all of them at
+    /// once.
+    ///
+    /// This reports root types, union members and interface possible types
+    /// that are missing or are not objects, types that do not provide all the
+    /// fields of the interfaces they implement, fields, arguments and input
+    /// fields of unknown types, fields of oneOf input objects that are
+    /// non-null or have a default value, inconsistent costs, and directives
+    /// applied in locations that their definitions do not allow.
+    ///
+    /// Warnings are ignored, use [`Registry::validate_with_options`] to get
+    /// them.
+    pub fn validate(&self) -> Result<(), Vec<SchemaError>> {
+        self.validate_with_options(Default::default()).map(|_| ())
+    }
+
+    /// Check the registry for schema-level errors with the specified options.
+    ///
+    /// On success, returns the warnings, such as interfaces that no type
+    /// implements, or required arguments and input fields that are
+    /// deprecated.
+    pub fn validate_with_options(
+        &self,
+        options: ValidateOptions,
+    ) -> Result<Vec<SchemaError>, Vec<SchemaError>> {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        self.validate_root_types(&mut errors);
+        self.validate_union_members(&mut errors);
+        self.validate_interface_possible_types(&mut errors);
+        self.validate_implementations(&mut errors);
+        self.validate_referenced_types(&mut errors);
+        self.validate_oneof_inputs(&mut errors);
+        self.validate_costs_into(&mut errors);
+        self.validate_directive_locations_into(&mut errors);
+        self.validate_orphan_interfaces(if options.deny_orphan_interfaces {
+            &mut errors
+        } else {
+            &mut warnings
+        });
+        self.validate_required_deprecations(&mut warnings);
+
+        if errors.is_empty() {
+            Ok(warnings)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Check that every field with a `cost` computes the same constant
+    /// complexity, so that the query complexity limit and the exported `@cost`
+    /// directives agree.
+    pub fn validate_costs(&self) -> Result<(), Vec<SchemaError>> {
+        let mut errors = Vec::new();
+        self.validate_costs_into(&mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Check that every directive applied to a type, a field or an enum value
+    /// is allowed at that location by its definition.
+    ///
+    /// Directives that are not registered are ignored.
+    pub fn validate_directive_locations(&self) -> Result<(), Vec<SchemaError>> {
+        let mut errors = Vec::new();
+        self.validate_directive_locations_into(&mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn validate_directive_locations_into(&self, errors: &mut Vec<SchemaError>) {
+        let mut check = |invocations: &[MetaDirectiveInvocation],
+                         location: __DirectiveLocation,
+                         type_name: &str,
+                         field_name: Option<&str>,
+                         target: String| {
+            for invocation in invocations {
+                let directive = match self.directives.get(&invocation.name) {
+                    Some(directive) => directive,
+                    None => continue,
+                };
+                if !directive.is_applicable_at(location) {
+                    errors.push(SchemaError {
+                        field_name: field_name.map(ToString::to_string),
+                        ..SchemaError::new(
+                            type_name,
+                            format!(
+                                "The directive `@{}` cannot be applied to {}, it is only allowed on {}",
+                                directive.name,
+                                target,
+                                directive
+                                    .locations
+                                    .iter()
+                                    .map(|location| format!("{:?}", location))
+                                    .collect::<Vec<_>>()
+                                    .join(" | ")
+                            ),
+                        )
+                    });
+                }
+            }
+        };
+
+        for ty in self.types.values() {
+            match ty {
+                MetaType::Object {
+                    directive_invocations,
+                    ..
+                } => check(
+                    directive_invocations,
+                    __DirectiveLocation::OBJECT,
+                    ty.name(),
+                    None,
+                    format!("the object `{}`", ty.name()),
+                ),
+                MetaType::Interface {
+                    directive_invocations,
+                    ..
+                } => check(
+                    directive_invocations,
+                    __DirectiveLocation::INTERFACE,
+                    ty.name(),
+                    None,
+                    format!("the interface `{}`", ty.name()),
+                ),
+                MetaType::Enum { enum_values, .. } => {
+                    for value in enum_values.values() {
+                        check(
+                            &value.directive_invocations,
+                            __DirectiveLocation::ENUM_VALUE,
+                            ty.name(),
+                            Some(&value.name),
+                            format!("the enum value `{}.{}`", ty.name(), value.name),
+                        );
+                    }
+                }
+                _ => {}
+            }
+
+            for field in ty.fields().into_iter().flat_map(|fields| fields.values()) {
+                check(
+                    &field.directive_invocations,
+                    __DirectiveLocation::FIELD_DEFINITION,
+                    ty.name(),
+                    Some(&field.name),
+                    format!("the field `{}.{}`", ty.name(), field.name),
+                );
+            }
+        }
+    }
+
+    fn validate_costs_into(&self, errors: &mut Vec<SchemaError>) {
+        for ty in self.types.values() {
+            for field in ty.fields().into_iter().flat_map(|fields| fields.values()) {
+                let cost = match field.cost {
+                    Some(cost) => cost,
+                    None => continue,
+                };
+
+                let consistent = matches!(
+                    field.compute_complexity,
+                    Some(ComplexityType::Const(complexity)) if complexity == cost
+                );
+                if !consistent {
+                    errors.push(SchemaError {
+                        field_name: Some(field.name.clone()),
+                        ..SchemaError::new(
+                            ty.name(),
+                            format!(
+                                "The field `{}.{}` has a cost of {}, but its complexity is not {}",
+                                ty.name(),
+                                field.name,
+                                cost,
+                                cost
+                            ),
+                        )
+                    });
+                }
+            }
+        }
+    }
+
+    fn validate_required_deprecations(&self, errors: &mut Vec<SchemaError>) {
+        for ty in self.types.values() {
+            let deprecated_required = |input_value: &&MetaInputValue| {
+                input_value.deprecation.is_deprecated() && input_value.is_required()
+            };
+
+            match ty {
+                MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => {
+                    for field in fields.values() {
+                        for arg in field.args.values().filter(deprecated_required) {
+                            errors.push(SchemaError {
+                                field_name: Some(field.name.clone()),
+                                ..SchemaError::new(
+                                    ty.name(),
+                                    format!(
+                                        "The argument `{}.{}({}:)` is required and should not be deprecated",
+                                        ty.name(),
+                                        field.name,
+                                        arg.name
+                                    ),
+                                )
+                            });
+                        }
+                    }
+                }
+                MetaType::InputObject { input_fields, .. } => {
+                    for field in input_fields.values().filter(deprecated_required) {
+                        errors.push(SchemaError {
+                            field_name: Some(field.name.clone()),
+                            ..SchemaError::new(
+                                ty.name(),
+                                format!(
+                                    "The input field `{}.{}` is required and should not be deprecated",
+                                    ty.name(),
+                                    field.name
+                                ),
+                            )
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn validate_orphan_interfaces(&self, errors: &mut Vec<SchemaError>) {
+        for ty in self.types.values() {
+            if let MetaType::Interface {
+                name,
+                possible_types,
+                ..
+            } = ty
+            {
+                if possible_types.is_empty() {
+                    errors.push(SchemaError::new(
+                        name,
+                        format!("The interface `{}` is not implemented by any type", name),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn validate_interface_possible_types(&self, errors: &mut Vec<SchemaError>) {
+        for ty in self.types.values() {
+            if let MetaType::Interface {
+                name,
+                possible_types,
+                ..
+            } = ty
+            {
+                for possible_type in possible_types {
+                    match self.types.get(possible_type) {
+                        Some(MetaType::Object { .. }) => {}
+                        Some(possible_ty) => errors.push(SchemaError::new(
+                            name,
+                            format!(
+                                "The possible type `{}` of the interface `{}` must be an object, but it is a `{}`",
+                                possible_type,
+                                name,
+                                possible_ty.type_id()
+                            ),
+                        )),
+                        None => errors.push(SchemaError::new(
+                            name,
+                            format!(
+                                "The possible type `{}` of the interface `{}` is not registered",
+                                possible_type, name
+                            ),
+                        )),
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_implementations(&self, errors: &mut Vec<SchemaError>) {
+        let mut implements = self.implements.iter().collect::<Vec<_>>();
+        implements.sort_by_key(|(type_name, _)| *type_name);
+
+        for (type_name, interfaces) in implements {
+            let fields = match self.types.get(type_name).and_then(MetaType::fields) {
+                Some(fields) => fields,
+                None => continue,
+            };
+
+            let mut interfaces = interfaces.iter().collect::<Vec<_>>();
+            interfaces.sort();
+
+            for interface in interfaces {
+                let interface_fields = match self.types.get(interface) {
+                    Some(MetaType::Interface { fields, .. }) => fields,
+                    Some(ty) => {
+                        errors.push(SchemaError::new(
+                            type_name,
+                            format!(
+                                "The type `{}` implements `{}`, but it is a `{}`",
+                                type_name,
+                                interface,
+                                ty.type_id()
+                            ),
+                        ));
+                        continue;
+                    }
+                    None => {
+                        errors.push(SchemaError::new(
+                            type_name,
+                            format!(
+                                "The type `{}` implements the unknown interface `{}`",
+                                type_name, interface
+                            ),
+                        ));
+                        continue;
+                    }
+                };
+
+                for field_name in interface_fields.keys() {
+                    if field_name.starts_with("__") || fields.contains_key(field_name) {
+                        continue;
+                    }
+                    errors.push(SchemaError {
+                        field_name: Some(field_name.clone()),
+                        ..SchemaError::new(
+                            type_name,
+                            format!(
+                                "The field `{}.{}` is required by the interface `{}`, but it is not provided by `{}`",
+                                interface, field_name, interface, type_name
+                            ),
+                        )
+                    });
+                }
+            }
+        }
+    }
+
+    fn validate_oneof_inputs(&self, errors: &mut Vec<SchemaError>) {
+        for ty in self.types.values() {
+            let input_fields = match ty {
+                MetaType::InputObject {
+                    input_fields,
+                    oneof: true,
+                    ..
+                } => input_fields,
+                _ => continue,
+            };
+
+            for field in input_fields.values() {
+                let problem = if MetaTypeName::create(&field.ty).is_non_null() {
+                    "must be nullable"
+                } else if field.default_value.is_some() {
+                    "must not have a default value"
+                } else {
+                    continue;
+                };
+                errors.push(SchemaError {
+                    field_name: Some(field.name.clone()),
+                    ..SchemaError::new(
+                        ty.name(),
+                        format!(
+                            "The field `{}.{}` of a oneOf input object {}",
+                            ty.name(),
+                            field.name,
+                            problem
+                        ),
+                    )
+                });
+            }
+        }
+    }
+
+    fn validate_referenced_types(&self, errors: &mut Vec<SchemaError>) {
+        let is_unknown = |ty: &str| !self.types.contains_key(MetaTypeName::concrete_typename(ty));
+
+        for ty in self.types.values() {
+            match ty {
+                MetaType::Object { fields, .. } | MetaType::Interface { fields, .. } => {
+                    for field in fields.values() {
+                        if is_unknown(&field.ty) {
+                            errors.push(SchemaError {
+                                field_name: Some(field.name.clone()),
+                                ..SchemaError::new(
+                                    ty.name(),
+                                    format!(
+                                        "The field `{}.{}` has the unknown type `{}`",
+                                        ty.name(),
+                                        field.name,
+                                        field.ty
+                                    ),
+                                )
+                            });
+                        }
+                        for arg in field.args.values().filter(|arg| is_unknown(&arg.ty)) {
+                            errors.push(SchemaError {
+                                field_name: Some(field.name.clone()),
+                                ..SchemaError::new(
+                                    ty.name(),
+                                    format!(
+                                        "The argument `{}.{}({}:)` has the unknown type `{}`",
+                                        ty.name(),
+                                        field.name,
+                                        arg.name,
+                                        arg.ty
+                                    ),
+                                )
+                            });
+                        }
+                    }
+                }
+                MetaType::InputObject { input_fields, .. } => {
+                    for field in input_fields.values().filter(|field| is_unknown(&field.ty)) {
+                        errors.push(SchemaError {
+                            field_name: Some(field.name.clone()),
+                            ..SchemaError::new(
+                                ty.name(),
+                                format!(
+                                    "The input field `{}.{}` has the unknown type `{}`",
+                                    ty.name(),
+                                    field.name,
+                                    field.ty
+                                ),
+                            )
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn validate_union_members(&self, errors: &mut Vec<SchemaError>) {
+        for ty in self.types.values() {
+            if let MetaType::Union {
+                name,
+                possible_types,
+                ..
+            } = ty
+            {
+                for member in possible_types {
+                    match self.types.get(member) {
+                        Some(MetaType::Object { .. }) => {}
+                        Some(member_ty) => errors.push(SchemaError::new(
+                            name,
+                            format!(
+                                "The member `{}` of the union `{}` must be an object, but it is a `{}`",
+                                member,
+                                name,
+                                member_ty.type_id()
+                            ),
+                        )),
+                        None => errors.push(SchemaError::new(
+                            name,
+                            format!(
+                                "The member `{}` of the union `{}` is not registered",
+                                member, name
+                            ),
+                        )),
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_root_types(&self, errors: &mut Vec<SchemaError>) {
+        let roots = [
+            ("query", Some(self.query_type.as_str())),
+            ("mutation", self.mutation_type.as_deref()),
+            ("subscription", self.subscription_type.as_deref()),
+        ];
+
+        for (operation, type_name) in roots {
+            let type_name = match type_name {
+                Some(type_name) if !type_name.is_empty() => type_name,
+                _ => continue,
+            };
+
+            match self.types.get(type_name) {
+                Some(MetaType::Object { .. }) => {}
+                Some(ty) => errors.push(SchemaError::new(
+                    type_name,
+                    format!(
+                        "The {} root type `{}` must be an object, but it is a `{}`",
+                        operation,
+                        type_name,
+                        ty.type_id()
+                    ),
+                )),
+                None => errors.push(SchemaError::new(
+                    type_name,
+                    format!(
+                        "The {} root type `{}` is not registered",
+                        operation, type_name
+                    ),
+                )),
+            }
+        }
+    }
+}
@@ -48,6 +48,14 @@ pub trait ContainerType: OutputType {
     async fn find_entity(&self, _: &Context<'_>, _params: &Value) -> ServerResult<Option<Value>> {
         Ok(None)
     }
+
+    /// Returns `true` if the field `name` must be resolved serially, see the
+    /// `serial` field attribute.
+    ///
+    /// Objects with serial fields, or with flattened fields, override this.
+    fn is_serial_field(&self, _ctx: &ContextSelectionSet<'_>, _name: &str) -> bool {
+        false
+    }
 }
 
 #[async_trait::async_trait]
@@ -59,6 +67,10 @@ impl<T: ContainerType + ?Sized> ContainerType for &T {
     async fn find_entity(&self, ctx: &Context<'_>, params: &Value) -> ServerResult<Option<Value>> {
         T::find_entity(*self, ctx, params).await
     }
+
+    fn is_serial_field(&self, ctx: &ContextSelectionSet<'_>, name: &str) -> bool {
+        T::is_serial_field(*self, ctx, name)
+    }
 }
 
 #[async_trait::async_trait]
@@ -70,6 +82,10 @@ impl<T: ContainerType + ?Sized> ContainerType for Arc<T> {
     async fn find_entity(&self, ctx: &Context<'_>, params: &Value) -> ServerResult<Option<Value>> {
         T::find_entity(self, ctx, params).await
     }
+
+    fn is_serial_field(&self, ctx: &ContextSelectionSet<'_>, name: &str) -> bool {
+        T::is_serial_field(self, ctx, name)
+    }
 }
 
 #[async_trait::async_trait]
@@ -81,6 +97,10 @@ impl<T: ContainerType + ?Sized> ContainerType for Box<T> {
     async fn find_entity(&self, ctx: &Context<'_>, params: &Value) -> ServerResult<Option<Value>> {
         T::find_entity(self, ctx, params).await
     }
+
+    fn is_serial_field(&self, ctx: &ContextSelectionSet<'_>, name: &str) -> bool {
+        T::is_serial_field(self, ctx, name)
+    }
 }
 
 #[async_trait::async_trait]
@@ -98,6 +118,28 @@ impl<T: ContainerType, E: Into<Error> + Send + Sync + Clone> ContainerType for R
             Err(err) => Err(ctx.set_error_path(err.clone().into().into_server_error(ctx.item.pos))),
         }
     }
+    fn is_serial_field(&self, ctx: &ContextSelectionSet<'_>, name: &str) -> bool {
+        match self {
+            Ok(value) => T::is_serial_field(value, ctx, name),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Returns `true` if the field `name` of the object `T` is registered as
+/// serial, for the fields that objects flatten from the return type of a
+/// resolver.
+#[doc(hidden)]
+pub fn is_registered_serial_field<T: OutputType + ?Sized>(
+    ctx: &ContextSelectionSet<'_>,
+    name: &str,
+) -> bool {
+    ctx.schema_env
+        .registry
+        .types
+        .get(T::type_name().as_ref())
+        .and_then(|ty| ty.field_by_name(name))
+        .map_or(false, |field| field.serial)
 }
 
 /// Resolve an container by executing each of the fields concurrently.
@@ -150,12 +192,42 @@ async fn resolve_container_inner<'a, T: ContainerType + ?Sized>(
     let mut fields = Fields(Vec::new());
     fields.add_set(ctx, root)?;
 
-    let res = if parallel {
-        futures_util::future::try_join_all(fields.0).await?
+    let res = if parallel && !fields.0.iter().any(|field| field.serial) {
+        futures_util::future::try_join_all(fields.0.into_iter().map(|field| field.future)).await?
+    } else if parallel {
+        // Fields marked as serial are resolved one after another in selection
+        // order, concurrently with the other fields.
+        let (serial, concurrent): (Vec<_>, Vec<_>) = fields
+            .0
+            .into_iter()
+            .enumerate()
+            .partition(|(_, field)| field.serial);
+        let (serial_indexes, serial): (Vec<_>, Vec<_>) = serial.into_iter().unzip();
+        let (concurrent_indexes, concurrent): (Vec<_>, Vec<_>) = concurrent.into_iter().unzip();
+
+        let (serial_results, concurrent_results) = futures_util::future::try_join(
+            async move {
+                let mut results = Vec::with_capacity(serial.len());
+                for field in serial {
+                    results.push(field.future.await?);
+                }
+                Ok(results)
+            },
+            futures_util::future::try_join_all(concurrent.into_iter().map(|field| field.future)),
+        )
+        .await?;
+
+        let mut res = serial_indexes
+            .into_iter()
+            .zip(serial_results)
+            .chain(concurrent_indexes.into_iter().zip(concurrent_results))
+            .collect::<Vec<_>>();
+        res.sort_by_key(|(idx, _)| *idx);
+        res.into_iter().map(|(_, value)| value).collect()
     } else {
         let mut results = Vec::with_capacity(fields.0.len());
         for field in fields.0 {
-            results.push(field.await?);
+            results.push(field.future.await?);
         }
         results
     };
@@ -169,8 +241,13 @@ async fn resolve_container_inner<'a, T: ContainerType + ?Sized>(
 
 type BoxFieldFuture<'a> = Pin<Box<dyn Future<Output = ServerResult<(Name, Value)>> + 'a + Send>>;
 
+struct FieldFuture<'a> {
+    future: BoxFieldFuture<'a>,
+    serial: bool,
+}
+
 /// A set of fields on an container that are being selected.
-pub struct Fields<'a>(Vec<BoxFieldFuture<'a>>);
+pub struct Fields<'a>(Vec<FieldFuture<'a>>);
 
 impl<'a> Fields<'a> {
     /// Add another set of fields to this set of fields using the given
@@ -189,12 +266,17 @@ impl<'a> Fields<'a> {
                         let field_name = ctx_field.item.node.response_key().node.clone();
                         let typename = root.introspection_type_name().into_owned();
 
-                        self.0.push(Box::pin(async move {
-                            Ok((field_name, Value::String(typename)))
-                        }));
+                        self.0.push(FieldFuture {
+                            future: Box::pin(
+                                async move { Ok((field_name, Value::String(typename))) },
+                            ),
+                            serial: false,
+                        });
                         continue;
                     }
 
+                    let serial = root.is_serial_field(ctx, field.node.name.node.as_str());
+
                     let resolve_fut = Box::pin({
                         let ctx = ctx.clone();
                         async move {
@@ -292,7 +374,10 @@ impl<'a> Fields<'a> {
                         }
                     });
 
-                    self.0.push(resolve_fut);
+                    self.0.push(FieldFuture {
+                        future: resolve_fut,
+                        serial,
+                    });
                 }
                 selection => {
                     let (type_condition, selection_set) = match selection {
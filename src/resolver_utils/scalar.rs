@@ -101,7 +101,7 @@ macro_rules! scalar {
         $crate::scalar_internal!(
             $ty,
             $name,
-            ::std::option::Option::Some($desc),
+            ::std::option::Option::Some(::std::string::ToString::to_string($desc)),
             ::std::option::Option::Some($specified_by_url)
         );
     };
@@ -110,7 +110,7 @@ macro_rules! scalar {
         $crate::scalar_internal!(
             $ty,
             $name,
-            ::std::option::Option::Some($desc),
+            ::std::option::Option::Some(::std::string::ToString::to_string($desc)),
             ::std::option::Option::None
         );
     };
@@ -163,6 +163,14 @@ macro_rules! scalar_internal {
                         name: ::std::borrow::ToOwned::to_owned($name),
                         description: $desc,
                         is_valid: |value| <$ty as $crate::ScalarType>::is_valid(value),
+                        round_trip: ::std::option::Option::Some(|value| {
+                            <$ty as $crate::ScalarType>::parse(value)
+                                .map(|value| <$ty as $crate::ScalarType>::to_value(&value))
+                                .map_err(|err| {
+                                    err.into_server_error(::std::default::Default::default())
+                                        .message
+                                })
+                        }),
                         visible: ::std::option::Option::None,
                         inaccessible: false,
                         tags: ::std::default::Default::default(),
@@ -200,6 +208,14 @@ macro_rules! scalar_internal {
                         name: ::std::borrow::ToOwned::to_owned($name),
                         description: $desc,
                         is_valid: |value| <$ty as $crate::ScalarType>::is_valid(value),
+                        round_trip: ::std::option::Option::Some(|value| {
+                            <$ty as $crate::ScalarType>::parse(value)
+                                .map(|value| <$ty as $crate::ScalarType>::to_value(&value))
+                                .map_err(|err| {
+                                    err.into_server_error(::std::default::Default::default())
+                                        .message
+                                })
+                        }),
                         visible: ::std::option::Option::None,
                         inaccessible: false,
                         tags: ::std::default::Default::default(),
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use http::{
     header::{HeaderMap, HeaderName},
@@ -24,6 +24,11 @@ pub struct Response {
     #[serde(skip)]
     pub cache_control: CacheControl,
 
+    /// The request headers that the response varies by, sorted and without
+    /// duplicates
+    #[serde(skip)]
+    pub vary: Vec<String>,
+
     /// Errors
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub errors: Vec<ServerError>,
@@ -77,6 +82,21 @@ impl Response {
         }
     }
 
+    /// Set the request headers that the response varies by.
+    #[must_use]
+    pub fn vary(self, vary: Vec<String>) -> Self {
+        Self { vary, ..self }
+    }
+
+    /// Get 'Vary' header value.
+    pub fn vary_value(&self) -> Option<String> {
+        if self.vary.is_empty() {
+            None
+        } else {
+            Some(self.vary.join(", "))
+        }
+    }
+
     /// Returns `true` if the response is ok.
     #[inline]
     pub fn is_ok(&self) -> bool {
@@ -124,6 +144,26 @@ impl BatchResponse {
         }
     }
 
+    /// Get 'Vary' header value, with the request headers that any of the
+    /// responses varies by.
+    pub fn vary_value(&self) -> Option<String> {
+        match self {
+            BatchResponse::Single(resp) => resp.vary_value(),
+            BatchResponse::Batch(resp) => {
+                let vary = resp
+                    .iter()
+                    .flat_map(|item| &item.vary)
+                    .map(String::as_str)
+                    .collect::<BTreeSet<_>>();
+                if vary.is_empty() {
+                    None
+                } else {
+                    Some(vary.into_iter().collect::<Vec<_>>().join(", "))
+                }
+            }
+        }
+    }
+
     /// Returns `true` if all responses are ok.
     pub fn is_ok(&self) -> bool {
         match self {
@@ -1,18 +1,16 @@
 use std::{
     any::Any,
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     ops::Deref,
     sync::Arc,
 };
 
 use futures_util::stream::{self, Stream, StreamExt};
-use indexmap::map::IndexMap;
 
 use crate::{
     context::{Data, QueryEnvInner},
     custom_directive::CustomDirectiveFactory,
     extensions::{ExtensionFactory, Extensions},
-    model::__DirectiveLocation,
     parser::{
         parse_query,
         types::{
@@ -21,7 +19,7 @@ use crate::{
         },
         Positioned,
     },
-    registry::{MetaDirective, MetaInputValue, Registry, SDLExportOptions},
+    registry::{LinkSpec, Registry, SDLExportOptions, SchemaError},
     resolver_utils::{resolve_container, resolve_container_serial},
     subscription::collect_subscription_streams,
     types::QueryRoot,
@@ -39,6 +37,9 @@ pub enum IntrospectionMode {
     /// Enables introspection
     Enabled,
     /// Disables introspection
+    ///
+    /// The `__schema` field resolves to an error, and the `__type` field
+    /// resolves to `null`.
     Disabled,
 }
 
@@ -183,6 +184,46 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Set the directives imported by the apollo federation 2 `@link`
+    /// directive, such as `["@key", "@shareable"]`.
+    ///
+    /// By default, only the federation directives used in the schema are
+    /// imported.
+    #[must_use]
+    pub fn apollo_link_imports<I, T>(mut self, imports: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.registry.link_imports = Some(imports.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Link an additional specification, such as a custom authorization
+    /// specification, in the Federation SDL.
+    #[must_use]
+    pub fn link_spec(mut self, spec: LinkSpec) -> Self {
+        self.registry.add_link_spec(spec);
+        self
+    }
+
+    /// Compose the custom directive `name`, such as `@myDirective`, in the
+    /// federation 2 supergraph.
+    ///
+    /// The Federation SDL declares the directive with `@composeDirective`
+    /// and includes its definition. The directive must also be imported from
+    /// a specification linked with [`SchemaBuilder::link_spec`].
+    ///
+    /// # Panics
+    ///
+    /// [`SchemaBuilder::finish`] panics if no directive named `name` is
+    /// registered.
+    #[must_use]
+    pub fn compose_directive(mut self, name: impl AsRef<str>) -> Self {
+        self.registry.add_compose_directive(name.as_ref());
+        self
+    }
+
     /// Make the Federation SDL include subscriptions.
     ///
     /// Note: Not included by default, in order to be compatible with Apollo
@@ -193,6 +234,30 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
         self
     }
 
+    /// Export the entity `T` as a reference stub in the Federation SDL.
+    ///
+    /// A reference stub only contains the fields used by its `@key`
+    /// directives, each marked as `@external`. Use it for entities that are
+    /// owned by another subgraph and only referenced by this one.
+    #[must_use]
+    pub fn export_reference_stub<T: OutputType>(mut self) -> Self {
+        self.registry
+            .reference_stubs
+            .insert(T::type_name().into_owned());
+        self
+    }
+
+    /// Declare the `@defer` and `@stream` directives of incremental delivery
+    /// in the schema, so that clients can detect whether they are supported.
+    ///
+    /// Note: The directives are only advertised, the responses are not
+    /// delivered incrementally.
+    #[must_use]
+    pub fn enable_incremental_delivery(mut self) -> Self {
+        self.registry.add_incremental_delivery_directives();
+        self
+    }
+
     /// Override the name of the specified input type.
     #[must_use]
     pub fn override_input_type_description<T: InputType>(mut self, desc: &'static str) -> Self {
@@ -236,6 +301,10 @@ impl<Query, Mutation, Subscription> SchemaBuilder<Query, Mutation, Subscription>
             self.registry.create_federation_types();
         }
 
+        if let Err(errors) = self.registry.validate_compose_directives() {
+            panic!("{}", errors[0]);
+        }
+
         Schema(Arc::new(SchemaInner {
             validation_mode: self.validation_mode,
             query: self.query,
@@ -392,59 +461,16 @@ where
             enable_apollo_link: false,
             federation_subscription: false,
             ignore_name_conflicts,
+            collect_name_conflicts: false,
+            name_conflict_typenames: Default::default(),
+            reference_stubs: Default::default(),
+            link_imports: None,
+            link_specs: Vec::new(),
+            compose_directives: Vec::new(),
+            enable_incremental_delivery: false,
         };
 
-        registry.add_directive(MetaDirective {
-            name: "include",
-            description: Some("Directs the executor to include this field or fragment only when the `if` argument is true."),
-            locations: vec![
-                __DirectiveLocation::FIELD,
-                __DirectiveLocation::FRAGMENT_SPREAD,
-                __DirectiveLocation::INLINE_FRAGMENT
-            ],
-            args: {
-                let mut args = IndexMap::new();
-                args.insert("if".to_string(), MetaInputValue {
-                    name: "if",
-                    description: Some("Included when true."),
-                    ty: "Boolean!".to_string(),
-                    default_value: None,
-                    visible: None,
-                    inaccessible: false,
-                    tags: Default::default(),
-                    is_secret: false,
-                });
-                args
-            },
-            is_repeatable: false,
-            visible: None,
-        });
-
-        registry.add_directive(MetaDirective {
-            name: "skip",
-            description: Some("Directs the executor to skip this field or fragment when the `if` argument is true."),
-            locations: vec![
-                __DirectiveLocation::FIELD,
-                __DirectiveLocation::FRAGMENT_SPREAD,
-                __DirectiveLocation::INLINE_FRAGMENT
-            ],
-            args: {
-                let mut args = IndexMap::new();
-                args.insert("if".to_string(), MetaInputValue {
-                    name: "if",
-                    description: Some("Skipped when true."),
-                    ty: "Boolean!".to_string(),
-                    default_value: None,
-                    visible: None,
-                    inaccessible: false,
-                    tags: Default::default(),
-                    is_secret: false,
-                });
-                args
-            },
-            is_repeatable: false,
-            visible: None,
-        });
+        registry.add_include_and_skip_directives();
 
         // register scalars
         <bool as InputType>::create_type_info(&mut registry);
@@ -490,6 +516,27 @@ where
         self.0.env.registry.export_sdl(options)
     }
 
+    /// Returns SDL(Schema Definition Language) of this schema with options,
+    /// or the errors of the directive applications that are not allowed at
+    /// their location, see [`Registry::try_export_sdl`].
+    pub fn try_sdl_with_options(
+        &self,
+        options: SDLExportOptions,
+    ) -> Result<String, Vec<SchemaError>> {
+        self.0.env.registry.try_export_sdl(options)
+    }
+
+    /// Returns the result of the standard introspection query of this schema
+    /// as JSON, without executing a query.
+    ///
+    /// See [`Registry::to_json_introspection`].
+    pub fn introspection_json(&self, include_deprecated: bool) -> Option<serde_json::Value> {
+        self.0
+            .env
+            .registry
+            .to_json_introspection(include_deprecated)
+    }
+
     /// Get all names in this schema
     ///
     /// Maybe you want to serialize a custom binary protocol. In order to
@@ -513,7 +560,7 @@ where
         mut extensions: Extensions,
         request: Request,
         session_data: Arc<Data>,
-    ) -> Result<(QueryEnv, CacheControl), Vec<ServerError>> {
+    ) -> Result<(QueryEnv, CacheControl, Vec<String>), Vec<ServerError>> {
         let mut request = request;
         let query_data = Arc::new(std::mem::take(&mut request.data));
         extensions.attach_query_data(query_data.clone());
@@ -538,6 +585,7 @@ where
         };
 
         // check rules
+        let mut vary = BTreeSet::new();
         let validation_result = {
             let validation_fut = async {
                 check_rules(
@@ -545,6 +593,7 @@ where
                     &document,
                     Some(&request.variables),
                     self.validation_mode,
+                    &mut vary,
                 )
             };
             futures_util::pin_mut!(validation_fut);
@@ -613,7 +662,8 @@ where
             introspection_mode: request.introspection_mode,
             errors: Default::default(),
         };
-        Ok((QueryEnv::new(env), validation_result.cache_control))
+        let vary = vary.into_iter().map(ToString::to_string).collect();
+        Ok((QueryEnv::new(env), validation_result.cache_control, vary))
     }
 
     async fn execute_once(&self, env: QueryEnv) -> Response {
@@ -664,11 +714,12 @@ where
                     .prepare_request(extensions, request, Default::default())
                     .await
                 {
-                    Ok((env, cache_control)) => {
+                    Ok((env, cache_control, vary)) => {
                         let fut = async {
                             self.execute_once(env.clone())
                                 .await
                                 .cache_control(cache_control)
+                                .vary(vary)
                         };
                         futures_util::pin_mut!(fut);
                         env.extensions
@@ -710,7 +761,7 @@ where
         let stream = futures_util::stream::StreamExt::boxed({
             let extensions = extensions.clone();
             async_stream::stream! {
-                let (env, cache_control) = match schema.prepare_request(extensions, request, session_data).await {
+                let (env, cache_control, vary) = match schema.prepare_request(extensions, request, session_data).await {
                     Ok(res) => res,
                     Err(errors) => {
                         yield Response::from_errors(errors);
@@ -719,7 +770,7 @@ where
                 };
 
                 if env.operation.node.ty != OperationType::Subscription {
-                    yield schema.execute_once(env).await.cache_control(cache_control);
+                    yield schema.execute_once(env).await.cache_control(cache_control).vary(vary);
                     return;
                 }
 
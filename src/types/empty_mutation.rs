@@ -60,6 +60,7 @@ impl OutputType for EmptyMutation {
             inaccessible: false,
             tags: Default::default(),
             is_subscription: false,
+            directive_invocations: Vec::new(),
             rust_typename: std::any::type_name::<Self>(),
         })
     }
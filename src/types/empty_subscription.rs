@@ -29,6 +29,7 @@ impl SubscriptionType for EmptySubscription {
             inaccessible: false,
             tags: Default::default(),
             is_subscription: true,
+            directive_invocations: Vec::new(),
             rust_typename: std::any::type_name::<Self>(),
         })
     }
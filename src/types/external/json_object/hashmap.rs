@@ -33,8 +33,9 @@ where
     fn create_type_info(registry: &mut Registry) -> String {
         registry.create_input_type::<Self, _>(MetaTypeId::Scalar, |_| MetaType::Scalar {
             name: <Self as InputType>::type_name().to_string(),
-            description: Some("A scalar that can represent any JSON Object value."),
+            description: Some("A scalar that can represent any JSON Object value.".to_string()),
             is_valid: |_| true,
+            round_trip: None,
             visible: None,
             inaccessible: false,
             tags: Default::default(),
@@ -91,8 +92,9 @@ where
     fn create_type_info(registry: &mut Registry) -> String {
         registry.create_output_type::<Self, _>(MetaTypeId::Scalar, |_| MetaType::Scalar {
             name: <Self as OutputType>::type_name().to_string(),
-            description: Some("A scalar that can represent any JSON Object value."),
+            description: Some("A scalar that can represent any JSON Object value.".to_string()),
             is_valid: |_| true,
+            round_trip: None,
             visible: None,
             inaccessible: false,
             tags: Default::default(),
@@ -51,8 +51,9 @@ impl<T: DeserializeOwned + Serialize + Send + Sync> InputType for Json<T> {
     fn create_type_info(registry: &mut Registry) -> String {
         registry.create_input_type::<Json<T>, _>(MetaTypeId::Scalar, |_| MetaType::Scalar {
             name: <Self as InputType>::type_name().to_string(),
-            description: Some("A scalar that can represent any JSON value."),
+            description: Some("A scalar that can represent any JSON value.".to_string()),
             is_valid: |_| true,
+            round_trip: None,
             visible: None,
             inaccessible: false,
             tags: Default::default(),
@@ -82,8 +83,9 @@ impl<T: Serialize + Send + Sync> OutputType for Json<T> {
     fn create_type_info(registry: &mut Registry) -> String {
         registry.create_output_type::<Json<T>, _>(MetaTypeId::Scalar, |_| MetaType::Scalar {
             name: <Self as OutputType>::type_name().to_string(),
-            description: Some("A scalar that can represent any JSON value."),
+            description: Some("A scalar that can represent any JSON value.".to_string()),
             is_valid: |_| true,
+            round_trip: None,
             visible: None,
             inaccessible: false,
             tags: Default::default(),
@@ -111,8 +113,9 @@ impl InputType for serde_json::Value {
         registry.create_input_type::<serde_json::Value, _>(MetaTypeId::Scalar, |_| {
             MetaType::Scalar {
                 name: <Self as InputType>::type_name().to_string(),
-                description: Some("A scalar that can represent any JSON value."),
+                description: Some("A scalar that can represent any JSON value.".to_string()),
                 is_valid: |_| true,
+                round_trip: None,
                 visible: None,
                 inaccessible: false,
                 tags: Default::default(),
@@ -144,8 +147,9 @@ impl OutputType for serde_json::Value {
         registry.create_output_type::<serde_json::Value, _>(MetaTypeId::Scalar, |_| {
             MetaType::Scalar {
                 name: <Self as OutputType>::type_name().to_string(),
-                description: Some("A scalar that can represent any JSON value."),
+                description: Some("A scalar that can represent any JSON value.".to_string()),
                 is_valid: |_| true,
+                round_trip: None,
                 visible: None,
                 inaccessible: false,
                 tags: Default::default(),
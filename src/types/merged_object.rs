@@ -34,6 +34,9 @@ where
             Err(err) => Err(err),
         }
     }
+    fn is_serial_field(&self, ctx: &ContextSelectionSet<'_>, name: &str) -> bool {
+        self.0.is_serial_field(ctx, name) || self.1.is_serial_field(ctx, name)
+    }
 }
 
 #[async_trait::async_trait]
@@ -83,6 +86,7 @@ where
                 inaccessible: false,
                 tags: Default::default(),
                 is_subscription: false,
+                directive_invocations: Vec::new(),
                 rust_typename: std::any::type_name::<Self>(),
             }
         })
@@ -144,6 +148,7 @@ where
                 inaccessible: false,
                 tags: Default::default(),
                 is_subscription: false,
+                directive_invocations: Vec::new(),
                 rust_typename: std::any::type_name::<Self>(),
             }
         })
@@ -180,6 +185,7 @@ impl SubscriptionType for MergedObjectTail {
             inaccessible: false,
             tags: Default::default(),
             is_subscription: false,
+            directive_invocations: Vec::new(),
             rust_typename: std::any::type_name::<Self>(),
         })
     }
@@ -60,6 +60,15 @@ impl<T: ObjectType> ContainerType for QueryRoot<T> {
                 .await
                 .map(Some);
             }
+        } else if ctx.item.node.name.node == "__schema" {
+            // `__schema` is non-null, so it cannot be silently resolved to null.
+            return Err(ctx.set_error_path(ServerError::new(
+                "Introspection is disabled.",
+                Some(ctx.item.pos),
+            )));
+        } else if ctx.item.node.name.node == "__type" {
+            // No type is visible when introspection is disabled.
+            return Ok(Some(Value::Null));
         }
 
         if ctx.schema_env.registry.introspection_mode == IntrospectionMode::IntrospectionOnly
@@ -100,6 +109,10 @@ impl<T: ObjectType> ContainerType for QueryRoot<T> {
 
         self.inner.resolve_field(ctx).await
     }
+
+    fn is_serial_field(&self, ctx: &ContextSelectionSet<'_>, name: &str) -> bool {
+        self.inner.is_serial_field(ctx, name)
+    }
 }
 
 #[async_trait::async_trait]
@@ -123,11 +136,14 @@ impl<T: ObjectType> OutputType for QueryRoot<T> {
                     "__schema".to_string(),
                     registry::MetaField {
                         name: "__schema".to_string(),
-                        description: Some("Access the current type schema of this server."),
+                        description: Some(
+                            "Access the current type schema of this server.".to_string(),
+                        ),
                         args: Default::default(),
                         ty: schema_type,
                         deprecation: Default::default(),
                         cache_control: Default::default(),
+                        vary: &[],
                         external: false,
                         requires: None,
                         provides: None,
@@ -136,6 +152,9 @@ impl<T: ObjectType> OutputType for QueryRoot<T> {
                         tags: Default::default(),
                         visible: None,
                         compute_complexity: None,
+                        cost: None,
+                        serial: false,
+                        directive_invocations: Vec::new(),
                         override_from: None,
                     },
                 );
@@ -144,16 +163,19 @@ impl<T: ObjectType> OutputType for QueryRoot<T> {
                     "__type".to_string(),
                     registry::MetaField {
                         name: "__type".to_string(),
-                        description: Some("Request the type information of a single type."),
+                        description: Some(
+                            "Request the type information of a single type.".to_string(),
+                        ),
                         args: {
                             let mut args = IndexMap::new();
                             args.insert(
                                 "name".to_string(),
                                 registry::MetaInputValue {
-                                    name: "name",
+                                    name: "name".to_string(),
                                     description: None,
                                     ty: "String!".to_string(),
                                     default_value: None,
+                                    deprecation: Default::default(),
                                     visible: None,
                                     inaccessible: false,
                                     tags: Default::default(),
@@ -165,6 +187,7 @@ impl<T: ObjectType> OutputType for QueryRoot<T> {
                         ty: "__Type".to_string(),
                         deprecation: Default::default(),
                         cache_control: Default::default(),
+                        vary: &[],
                         external: false,
                         requires: None,
                         provides: None,
@@ -174,6 +197,9 @@ impl<T: ObjectType> OutputType for QueryRoot<T> {
                         override_from: None,
                         visible: None,
                         compute_complexity: None,
+                        cost: None,
+                        serial: false,
+                        directive_invocations: Vec::new(),
                     },
                 );
             }
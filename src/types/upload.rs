@@ -112,6 +112,7 @@ impl InputType for Upload {
             name: Self::type_name().to_string(),
             description: None,
             is_valid: |value| matches!(value, Value::String(_)),
+            round_trip: None,
             visible: None,
             inaccessible: false,
             tags: Default::default(),
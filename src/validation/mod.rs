@@ -8,6 +8,8 @@ mod utils;
 mod visitor;
 mod visitors;
 
+use std::collections::BTreeSet;
+
 pub use visitor::VisitorContext;
 use visitor::{visit, VisitorNil};
 
@@ -39,11 +41,14 @@ pub enum ValidationMode {
     Fast,
 }
 
+/// Checks the rules, and adds the `Vary` headers of the selected fields to
+/// `vary`.
 pub fn check_rules(
     registry: &Registry,
     doc: &ExecutableDocument,
     variables: Option<&Variables>,
     mode: ValidationMode,
+    vary: &mut BTreeSet<&'static str>,
 ) -> Result<ValidationResult, Vec<ServerError>> {
     let mut ctx = VisitorContext::new(registry, doc, variables);
     let mut cache_control = CacheControl::default();
@@ -77,6 +82,7 @@ pub fn check_rules(
                 .with(rules::UploadFile)
                 .with(visitors::CacheControlCalculate {
                     cache_control: &mut cache_control,
+                    vary,
                 })
                 .with(visitors::ComplexityCalculate::new(&mut complexity))
                 .with(visitors::DepthCalculate::new(&mut depth));
@@ -88,6 +94,7 @@ pub fn check_rules(
                 .with(rules::UploadFile)
                 .with(visitors::CacheControlCalculate {
                     cache_control: &mut cache_control,
+                    vary,
                 })
                 .with(visitors::ComplexityCalculate::new(&mut complexity))
                 .with(visitors::DepthCalculate::new(&mut depth));
@@ -1,6 +1,5 @@
 use crate::{
     parser::types::{Directive, Field},
-    registry::MetaTypeName,
     validation::visitor::{Visitor, VisitorContext},
     Positioned,
 };
@@ -20,8 +19,7 @@ impl<'a> Visitor<'a> for ProvidedNonNullArguments {
             .get(directive.node.name.node.as_str())
         {
             for arg in schema_directive.args.values() {
-                if MetaTypeName::create(&arg.ty).is_non_null()
-                    && arg.default_value.is_none()
+                if arg.is_required()
                     && !directive
                         .node
                         .arguments
@@ -42,8 +40,7 @@ impl<'a> Visitor<'a> for ProvidedNonNullArguments {
         if let Some(parent_type) = ctx.parent_type() {
             if let Some(schema_field) = parent_type.field_by_name(&field.node.name.node) {
                 for arg in schema_field.args.values() {
-                    if MetaTypeName::create(&arg.ty).is_non_null()
-                        && arg.default_value.is_none()
+                    if arg.is_required()
                         && !field
                             .node
                             .arguments
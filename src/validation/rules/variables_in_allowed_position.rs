@@ -274,6 +274,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn non_null_list_of_non_null_strings_into_string_list() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          query Query($stringListVar: [String!]!)
+          {
+            complicatedArgs {
+              stringListArgField(stringListArg: $stringListVar)
+            }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn non_null_list_of_non_null_strings_into_non_null_string_list() {
+        expect_passes_rule!(
+            factory,
+            r#"
+          query Query($stringListVar: [String!]!)
+          {
+            complicatedArgs {
+              nonNullStringListArgField(nonNullStringListArg: $stringListVar)
+            }
+          }
+        "#,
+        );
+    }
+
     #[test]
     fn string_into_string_list_in_item_position() {
         expect_passes_rule!(
@@ -439,6 +469,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn string_list_into_non_null_string_list() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          query Query($stringListVar: [String]!) {
+            complicatedArgs {
+              nonNullStringListArgField(nonNullStringListArg: $stringListVar)
+            }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn nullable_string_list_into_non_null_string_list() {
+        expect_fails_rule!(
+            factory,
+            r#"
+          query Query($stringListVar: [String!]) {
+            complicatedArgs {
+              nonNullStringListArgField(nonNullStringListArg: $stringListVar)
+            }
+          }
+        "#,
+        );
+    }
+
     #[test]
     fn boolean_into_non_null_boolean_in_directive() {
         expect_fails_rule!(
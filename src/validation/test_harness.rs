@@ -263,6 +263,13 @@ impl ComplicatedArgs {
         unimplemented!()
     }
 
+    async fn non_null_string_list_arg_field(
+        &self,
+        non_null_string_list_arg: Vec<String>,
+    ) -> Option<String> {
+        unimplemented!()
+    }
+
     async fn complex_arg_field(&self, complex_arg: Option<ComplexInput>) -> Option<String> {
         unimplemented!()
     }
@@ -694,13 +694,13 @@ fn visit_input_value<'a, V: Visitor<'a>>(
             if let Some(expected_ty) = expected_ty {
                 let expected_ty = expected_ty.unwrap_non_null();
                 if let MetaTypeName::Named(expected_ty) = expected_ty {
-                    if let Some(MetaType::InputObject { input_fields, .. }) = ctx
+                    if let Some(ty) = ctx
                         .registry
                         .types
                         .get(MetaTypeName::concrete_typename(expected_ty))
                     {
                         for (item_key, item_value) in values {
-                            if let Some(input_value) = input_fields.get(item_key.as_str()) {
+                            if let Some(input_value) = ty.input_field_by_name(item_key) {
                                 visit_input_value(
                                     v,
                                     ctx,
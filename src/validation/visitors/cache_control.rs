@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+
 use crate::{
     parser::types::{Field, SelectionSet},
     registry::MetaType,
@@ -7,6 +9,7 @@ use crate::{
 
 pub struct CacheControlCalculate<'a> {
     pub cache_control: &'a mut CacheControl,
+    pub vary: &'a mut BTreeSet<&'static str>,
 }
 
 impl<'ctx, 'a> Visitor<'ctx> for CacheControlCalculate<'a> {
@@ -30,6 +33,7 @@ impl<'ctx, 'a> Visitor<'ctx> for CacheControlCalculate<'a> {
             .and_then(|parent| parent.field_by_name(&field.node.name.node))
         {
             *self.cache_control = self.cache_control.merge(&registry_field.cache_control);
+            self.vary.extend(registry_field.vary);
         }
     }
 }
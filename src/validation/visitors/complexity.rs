@@ -1,5 +1,5 @@
 use async_graphql_parser::types::{ExecutableDocument, OperationDefinition, VariableDefinition};
-use async_graphql_value::Name;
+use async_graphql_value::{ConstValue, Name};
 
 use crate::{
     parser::types::Field,
@@ -24,6 +24,35 @@ impl<'ctx, 'a> ComplexityCalculate<'ctx, 'a> {
     }
 }
 
+impl<'ctx, 'a> ComplexityCalculate<'ctx, 'a> {
+    /// Returns the value of the integer argument `name` of the field, or
+    /// `None` if it is absent, is not an integer or references an unbound
+    /// variable.
+    fn multiplier(&self, ctx: &VisitorContext<'ctx>, field: &Field, name: &str) -> Option<usize> {
+        let value = field
+            .get_argument(name)?
+            .node
+            .clone()
+            .into_const_with(|name| {
+                self.variable_definition
+                    .into_iter()
+                    .flatten()
+                    .find(|def| def.node.name.node == name)
+                    .and_then(|def| {
+                        ctx.variables
+                            .and_then(|variables| variables.get(&def.node.name.node))
+                            .or_else(|| def.node.default_value())
+                    })
+                    .cloned()
+                    .ok_or(())
+            });
+        match value {
+            Ok(ConstValue::Number(n)) => n.as_u64().map(|n| n as usize),
+            _ => None,
+        }
+    }
+}
+
 impl<'ctx, 'a> Visitor<'ctx> for ComplexityCalculate<'ctx, 'a> {
     fn mode(&self) -> VisitMode {
         VisitMode::Inline
@@ -57,6 +86,11 @@ impl<'ctx, 'a> Visitor<'ctx> for ComplexityCalculate<'ctx, 'a> {
             if let Some(meta_field) = fields.get(MetaTypeName::concrete_typename(
                 field.node.name.node.as_str(),
             )) {
+                if meta_field.is_introspection() {
+                    // Introspection queries do not count towards the complexity.
+                    return;
+                }
+
                 if let Some(compute_complexity) = &meta_field.compute_complexity {
                     match compute_complexity {
                         ComplexityType::Const(n) => {
@@ -75,6 +109,12 @@ impl<'ctx, 'a> Visitor<'ctx> for ComplexityCalculate<'ctx, 'a> {
                                 Err(err) => ctx.report_error(vec![field.pos], err.to_string()),
                             }
                         }
+                        ComplexityType::Multiplier { arg, default } => {
+                            let multiplier =
+                                self.multiplier(ctx, &field.node, arg).unwrap_or(*default);
+                            *self.complexity_stack.last_mut().unwrap() +=
+                                multiplier.saturating_mul(children_complex);
+                        }
                     }
 
                     return;
@@ -144,6 +184,12 @@ mod tests {
         async fn d(&self) -> MyObj {
             todo!()
         }
+
+        #[graphql(complexity = "multiply:first:4")]
+        #[allow(unused_variables)]
+        async fn page(&self, first: Option<i32>) -> Vec<MyObj> {
+            todo!()
+        }
     }
 
     struct Subscription;
@@ -184,6 +230,49 @@ mod tests {
         assert_eq!(complex, expect_complex);
     }
 
+    #[test]
+    fn multiplier_complexity() {
+        check_complex(
+            r#"
+        {
+            page(first: 10) {
+                a b
+            }
+        }"#,
+            20,
+        );
+
+        check_complex(
+            r#"
+        {
+            page {
+                a b
+            }
+        }"#,
+            8,
+        );
+
+        check_complex(
+            r#"
+        query($first: Int) {
+            page(first: $first) {
+                a b
+            }
+        }"#,
+            8,
+        );
+
+        check_complex(
+            r#"
+        query($first: Int = 3) {
+            page(first: $first) {
+                a b
+            }
+        }"#,
+            6,
+        );
+    }
+
     #[test]
     fn complex_object() {
         check_complex(
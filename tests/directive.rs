@@ -127,3 +127,90 @@ pub async fn test_custom_directive() {
         value!({ "value": "&abc*" })
     );
 }
+
+#[test]
+pub fn test_deprecated_directive() {
+    struct Noop;
+
+    #[async_trait::async_trait]
+    impl CustomDirective for Noop {}
+
+    #[Directive(location = "field", deprecation = "Use `@noop` instead.")]
+    fn legacy_noop() -> impl CustomDirective {
+        Noop
+    }
+
+    #[Directive(location = "field")]
+    fn noop() -> impl CustomDirective {
+        Noop
+    }
+
+    let mut registry = registry::Registry::default();
+    legacy_noop.register(&mut registry);
+    noop.register(&mut registry);
+
+    let directive = &registry.directives["legacy_noop"];
+    assert!(directive.is_deprecated());
+    assert_eq!(directive.deprecation.reason(), Some("Use `@noop` instead."));
+    assert!(!registry.directives["noop"].is_deprecated());
+}
+
+#[tokio::test]
+pub async fn test_incremental_delivery_directives() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        pub async fn value(&self) -> i32 {
+            10
+        }
+
+        pub async fn values(&self) -> Vec<i32> {
+            vec![1, 2, 3]
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_incremental_delivery()
+        .finish();
+
+    let sdl = schema.sdl();
+    assert!(sdl.contains(
+        "directive @defer(label: String, if: Boolean! = true) on FRAGMENT_SPREAD | INLINE_FRAGMENT\n"
+    ));
+    assert!(sdl.contains(
+        "directive @stream(label: String, if: Boolean! = true, initialCount: Int = 0) on FIELD\n"
+    ));
+
+    assert_eq!(
+        schema
+            .execute(r#"{ __schema { directives { name locations } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__schema": {
+                "directives": [
+                    { "name": "defer", "locations": ["FRAGMENT_SPREAD", "INLINE_FRAGMENT"] },
+                    { "name": "include", "locations": ["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"] },
+                    { "name": "skip", "locations": ["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"] },
+                    { "name": "stream", "locations": ["FIELD"] },
+                ]
+            }
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ ... @defer(label: "a") { value } values @stream(initialCount: 1) }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 10, "values": [1, 2, 3] })
+    );
+    assert!(!Schema::new(Query, EmptyMutation, EmptySubscription)
+        .sdl()
+        .contains("@defer"));
+}
@@ -0,0 +1,836 @@
+#![allow(unreachable_code)]
+
+use async_graphql::*;
+
+#[tokio::test]
+pub async fn test_wrap_long_unions() {
+    #[derive(SimpleObject)]
+    struct Article {
+        title: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct Image {
+        url: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct Video {
+        url: String,
+    }
+
+    #[derive(Union)]
+    enum SearchResult {
+        Article(Article),
+        Image(Image),
+        Video(Video),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn search(&self) -> Vec<SearchResult> {
+            todo!()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert!(schema
+        .sdl()
+        .contains("union SearchResult = Article | Image | Video\n"));
+    assert!(schema
+        .sdl_with_options(SDLExportOptions::new().wrap_long_unions(120))
+        .contains("union SearchResult = Article | Image | Video\n"));
+    assert!(schema
+        .sdl_with_options(SDLExportOptions::new().wrap_long_unions(40))
+        .contains("union SearchResult =\n    Article\n  | Image\n  | Video\n"));
+}
+
+#[tokio::test]
+pub async fn test_export_with_rust_mapping() {
+    #[derive(SimpleObject)]
+    struct MyObj {
+        value: i32,
+    }
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum MyEnum {
+        A,
+        B,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn obj(&self) -> MyObj {
+            todo!()
+        }
+
+        async fn value(&self, _e: MyEnum) -> i32 {
+            todo!()
+        }
+    }
+
+    let mut registry = registry::Registry {
+        query_type: "Query".to_string(),
+        ..Default::default()
+    };
+    Query::create_type_info(&mut registry);
+
+    let (sdl, mapping) = registry.export_with_rust_mapping(SDLExportOptions::new());
+    assert!(sdl.contains("type MyObj {\n\tvalue: Int!\n}"));
+    assert_eq!(
+        mapping,
+        serde_json::json!({
+            "MyEnum": std::any::type_name::<MyEnum>(),
+            "MyObj": std::any::type_name::<MyObj>(),
+            "Query": std::any::type_name::<Query>(),
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_include_cache_control() {
+    #[derive(SimpleObject)]
+    #[graphql(cache_control(max_age = 60))]
+    struct Cached {
+        value: i32,
+        #[graphql(cache_control(private, vary = "Cookie", vary = "Accept-Language"))]
+        private_value: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(cache_control(max_age = 30))]
+        async fn cached(&self) -> Cached {
+            todo!()
+        }
+
+        #[graphql(cache_control(no_cache))]
+        async fn uncached(&self) -> i32 {
+            todo!()
+        }
+
+        async fn plain(&self) -> i32 {
+            todo!()
+        }
+
+        async fn inherited(&self) -> Cached {
+            todo!()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(!schema.sdl().contains("@cacheControl"));
+
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().include_cache_control());
+    assert!(sdl.contains("directive @cacheControl(maxAge: Int, scope: CacheControlScope, inheritMaxAge: Boolean) on FIELD_DEFINITION | OBJECT | INTERFACE | UNION"));
+    assert!(sdl.contains("enum CacheControlScope {\n\tPUBLIC\n\tPRIVATE\n}"));
+    assert!(sdl.contains("type Cached @cacheControl(maxAge: 60) {"));
+    assert!(sdl.contains("\tvalue: Int!\n"));
+    assert!(sdl.contains("\tprivateValue: Int! @cacheControl(scope: PRIVATE)\n"));
+    assert!(sdl.contains("\tcached: Cached! @cacheControl(maxAge: 30)\n"));
+    assert!(sdl.contains("\tuncached: Int! @cacheControl(maxAge: 0)\n"));
+    assert!(sdl.contains("\tplain: Int!\n"));
+    assert!(sdl.contains("\tinherited: Cached! @cacheControl(maxAge: 60)\n"));
+}
+
+#[tokio::test]
+pub async fn test_group_by_kind() {
+    #[derive(SimpleObject)]
+    struct Apple {
+        id: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct Banana {
+        id: i32,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&i32"))]
+    enum Fruit {
+        Apple(Apple),
+        Banana(Banana),
+    }
+
+    #[derive(Union)]
+    enum AnyFruit {
+        Apple(Apple),
+        Banana(Banana),
+    }
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Color {
+        Red,
+        Yellow,
+    }
+
+    #[derive(InputObject)]
+    struct Filter {
+        color: Color,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn fruits(&self, _filter: Filter) -> Vec<Fruit> {
+            todo!()
+        }
+
+        async fn any_fruit(&self) -> AnyFruit {
+            todo!()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let type_order = |sdl: &str| {
+        sdl.lines()
+            .filter(|line| {
+                ["enum ", "interface ", "type ", "union ", "input "]
+                    .iter()
+                    .any(|prefix| line.starts_with(prefix))
+            })
+            .map(|line| line.split(' ').nth(1).unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        type_order(&schema.sdl()),
+        vec!["AnyFruit", "Apple", "Banana", "Color", "Filter", "Fruit", "Query"]
+    );
+    assert_eq!(
+        type_order(&schema.sdl_with_options(SDLExportOptions::new().group_by_kind(true))),
+        vec!["Color", "Fruit", "Apple", "Banana", "Query", "AnyFruit", "Filter"]
+    );
+}
+
+#[tokio::test]
+pub async fn test_include_cost() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(cost = 5)]
+        async fn expensive(&self) -> i32 {
+            0
+        }
+
+        async fn cheap(&self) -> i32 {
+            0
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_complexity(4)
+        .finish();
+
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().include_cost());
+    assert!(sdl.contains("directive @cost(weight: Int!) on FIELD_DEFINITION"));
+    assert!(sdl.contains("\texpensive: Int! @cost(weight: 5)\n"));
+    assert!(sdl.contains("\tcheap: Int!\n"));
+    assert!(!schema.sdl().contains("@cost"));
+
+    assert!(schema.execute("{ cheap }").await.is_ok());
+    assert_eq!(
+        schema.execute("{ expensive }").await.errors[0].message,
+        "Query is too complex."
+    );
+}
+
+#[tokio::test]
+pub async fn test_compact_enums_and_input_objects() {
+    /// A color
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Size {
+        Small,
+        #[graphql(deprecation = "use Small")]
+        Tiny,
+    }
+
+    #[derive(InputObject)]
+    struct Filter {
+        /// The color
+        color: Color,
+        #[graphql(default = "abc")]
+        name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn color(&self, _filter: Filter, _size: Size) -> Color {
+            todo!()
+        }
+    }
+
+    let sdl = Schema::new(Query, EmptyMutation, EmptySubscription)
+        .sdl_with_options(SDLExportOptions::new().compact());
+    assert!(sdl.contains("enum Color { RED GREEN BLUE }\n"));
+    assert!(sdl.contains("input Filter { color: Color! name: String! = \"abc\" }\n"));
+    assert!(sdl.contains("enum Size {\n\tSMALL\n\tTINY @deprecated(reason: \"use Small\")\n}\n"));
+    assert!(!sdl.contains("A color"));
+    assert!(!sdl.contains("The color"));
+    assert!(!sdl.contains("\n\n"));
+}
+
+#[tokio::test]
+pub async fn test_compact_sdl() {
+    /// A date
+    struct Date(String);
+
+    #[Scalar(specified_by_url = r#"https://example.com/date?format="iso""#)]
+    impl ScalarType for Date {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            match value {
+                Value::String(s) => Ok(Date(s)),
+                _ => Err(InputValueError::expected_type(value)),
+            }
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.0.clone())
+        }
+    }
+
+    /// A user
+    #[derive(SimpleObject)]
+    struct User {
+        /// The name
+        name: String,
+        #[graphql(deprecation = "use name")]
+        login: String,
+    }
+
+    #[derive(InputObject)]
+    struct UserFilter {
+        name: Option<String>,
+        #[graphql(deprecation = "use name")]
+        login: Option<String>,
+    }
+
+    #[derive(InputObject)]
+    struct Page {
+        offset: i32,
+        limit: i32,
+    }
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        /// Returns the date
+        async fn today(&self, #[graphql(default = "say \"hi\"")] _greeting: String) -> Date {
+            todo!()
+        }
+
+        async fn user(&self, _filter: Option<UserFilter>, _page: Option<Page>) -> User {
+            todo!()
+        }
+    }
+
+    let sdl = Schema::new(Root, EmptyMutation, EmptySubscription)
+        .sdl_with_options(SDLExportOptions::new().compact().include_specified_by());
+    assert!(sdl.contains(
+        "type Root { today(greeting: String! = \"say \\\"hi\\\"\"): Date! user(filter: UserFilter, page: Page): User! }\n"
+    ));
+    assert!(sdl.contains("input Page { offset: Int! limit: Int! }\n"));
+    assert!(sdl.contains(
+        "input UserFilter {\n\tname: String\n\tlogin: String @deprecated(reason: \"use name\")\n}\n"
+    ));
+    assert!(sdl.contains(
+        "type User {\n\tname: String!\n\tlogin: String! @deprecated(reason: \"use name\")\n}\n"
+    ));
+    assert!(sdl.contains(
+        "scalar Date @specifiedBy(url: \"https://example.com/date?format=\\\"iso\\\"\")\n"
+    ));
+    assert!(sdl.contains("schema { query: Root }\n"));
+    assert!(!sdl.contains("\n\n"));
+    assert!(!sdl.contains("A user"));
+    assert!(!sdl.contains("The name"));
+    assert!(!sdl.contains("Returns the date"));
+    parser::parse_schema(&sdl).unwrap();
+}
+
+#[tokio::test]
+pub async fn test_sorted_fields() {
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Status {
+        Open,
+        Closed,
+    }
+
+    #[derive(InputObject)]
+    struct Filter {
+        title: String,
+        author: String,
+    }
+
+    #[derive(Interface)]
+    #[graphql(
+        field(name = "title", type = "&String"),
+        field(name = "id", type = "&i32")
+    )]
+    enum Node {
+        Post(Post),
+    }
+
+    #[derive(SimpleObject)]
+    struct Post {
+        title: String,
+        id: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn status(&self) -> Status {
+            todo!()
+        }
+
+        async fn posts(&self, _limit: i32, _filter: Filter, _after: String) -> Vec<Node> {
+            todo!()
+        }
+    }
+
+    let sdl = Schema::new(Query, EmptyMutation, EmptySubscription)
+        .sdl_with_options(SDLExportOptions::new().sorted_fields());
+    assert!(sdl.contains(
+        "type Query {\n\tposts(after: String!, filter: Filter!, limit: Int!): [Node!]!\n\tstatus: Status!\n}\n"
+    ));
+    assert!(sdl.contains("type Post implements Node {\n\tid: Int!\n\ttitle: String!\n}\n"));
+    assert!(sdl.contains("interface Node {\n\tid: Int!\n\ttitle: String!\n}\n"));
+    assert!(sdl.contains("input Filter {\n\tauthor: String!\n\ttitle: String!\n}\n"));
+    assert!(sdl.contains("enum Status {\n\tOPEN\n\tCLOSED\n}\n"));
+}
+
+#[tokio::test]
+pub async fn test_include_directives() {
+    struct Noop;
+
+    impl CustomDirective for Noop {}
+
+    /// Adds a prefix to the value
+    #[Directive(location = "field", repeatable)]
+    fn prefix(
+        #[graphql(default = "-")] _text: String,
+        _count: Option<i32>,
+    ) -> impl CustomDirective {
+        Noop
+    }
+
+    #[Directive(location = "field")]
+    fn noop() -> impl CustomDirective {
+        Noop
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            todo!()
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .directive(prefix)
+        .directive(noop)
+        .finish();
+    assert!(!schema.sdl().contains("directive @"));
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().include_directives());
+    assert!(sdl.contains(
+        "\"\"\"\nAdds a prefix to the value\n\"\"\"\ndirective @prefix(text: String! = \"-\", count: Int) repeatable on FIELD\n"
+    ));
+    assert!(sdl.contains("directive @noop on FIELD\n"));
+    assert!(!sdl.contains("directive @include"));
+    assert!(!sdl.contains("directive @skip"));
+
+    let mut registry = registry::RegistryBuilder::new()
+        .object("Query")
+        .field("value", "Int!")
+        .query_type("Query")
+        .build();
+    if let Some(registry::MetaType::Object {
+        fields,
+        directive_invocations,
+        ..
+    }) = registry.types.get_mut("Query")
+    {
+        directive_invocations.push(registry::MetaDirectiveInvocation {
+            name: "internal".to_string(),
+            args: Default::default(),
+        });
+        fields.get_mut("value").unwrap().directive_invocations.push(
+            registry::MetaDirectiveInvocation {
+                name: "source".to_string(),
+                args: [
+                    ("name".to_string(), Value::String("a \"b\"".to_string())),
+                    ("weight".to_string(), Value::from(2)),
+                ]
+                .into_iter()
+                .collect(),
+            },
+        );
+    }
+    let sdl = registry
+        .try_export_sdl(SDLExportOptions::new().include_directives())
+        .unwrap();
+    assert!(sdl.contains(
+        "type Query @internal {\n\tvalue: Int! @source(name: \"a \\\"b\\\"\", weight: 2)\n}\n"
+    ));
+    let sdl = registry.try_export_sdl(SDLExportOptions::new()).unwrap();
+    assert!(sdl.contains("type Query {\n\tvalue: Int!\n}\n"));
+}
+
+#[tokio::test]
+pub async fn test_enum_value_directives() {
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Color {
+        #[graphql(
+            directive = r#"@semanticValue(uri: "https://schema.org/Red", label: "say \"red\"\n\u001f")"#
+        )]
+        Red,
+        #[graphql(
+            directive = "@internal",
+            directive = "@weight(value: 2, tags: [A, B], meta: {x: null})"
+        )]
+        Green,
+        Blue,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn color(&self) -> Color {
+            Color::Red
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema.sdl().contains("\tRED\n"));
+
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().include_directives());
+    assert!(sdl.contains(
+        "\tRED @semanticValue(uri: \"https://schema.org/Red\", label: \"say \\\"red\\\"\\n\\u001f\")\n"
+    ));
+    assert!(sdl.contains("\tGREEN @internal @weight(value: 2, tags: [A,B], meta: {x: null})\n"));
+    assert!(sdl.contains("\tBLUE\n"));
+
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().include_directives().compact());
+    assert!(sdl.contains("enum Color {\n"));
+}
+
+#[test]
+pub fn test_directive_application_locations() {
+    let mut registry = registry::RegistryBuilder::new()
+        .object("Query")
+        .field("value", "Int!")
+        .query_type("Query")
+        .build();
+    registry.add_directive(registry::MetaDirective {
+        name: "internal",
+        description: None,
+        locations: vec![registry::__DirectiveLocation::OBJECT],
+        args: Default::default(),
+        is_repeatable: false,
+        visible: None,
+        deprecation: Default::default(),
+    });
+    let internal = &registry.directives["internal"];
+    assert!(internal.is_applicable_at(registry::__DirectiveLocation::OBJECT));
+    assert!(!internal.is_applicable_at(registry::__DirectiveLocation::FIELD_DEFINITION));
+
+    let invocation = |name: &str| registry::MetaDirectiveInvocation {
+        name: name.to_string(),
+        args: Default::default(),
+    };
+    if let Some(registry::MetaType::Object {
+        fields,
+        directive_invocations,
+        ..
+    }) = registry.types.get_mut("Query")
+    {
+        directive_invocations.push(invocation("internal"));
+        let value = fields.get_mut("value").unwrap();
+        // Unregistered directives are not checked.
+        value.directive_invocations.push(invocation("source"));
+    }
+    assert!(registry
+        .try_export_sdl(SDLExportOptions::new().include_directives())
+        .unwrap()
+        .contains("type Query @internal {\n\tvalue: Int! @source\n}\n"));
+    assert_eq!(registry.validate(), Ok(()));
+
+    if let Some(registry::MetaType::Object { fields, .. }) = registry.types.get_mut("Query") {
+        let value = fields.get_mut("value").unwrap();
+        value.directive_invocations.push(invocation("internal"));
+    }
+    let errors = registry
+        .try_export_sdl(SDLExportOptions::new().include_directives())
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].type_name, "Query");
+    assert_eq!(errors[0].field_name.as_deref(), Some("value"));
+    assert_eq!(
+        errors[0].message,
+        "The directive `@internal` cannot be applied to the field `Query.value`, it is only allowed on OBJECT"
+    );
+    assert_eq!(registry.validate(), Err(errors));
+
+    // The applications are not emitted, so they are not checked.
+    assert!(registry.try_export_sdl(SDLExportOptions::new()).is_ok());
+}
+
+#[test]
+pub fn test_schema_definition() {
+    let registry = registry::RegistryBuilder::new()
+        .object("Query")
+        .field("value", "Int!")
+        .object("Mutation")
+        .field("value", "Int!")
+        .query_type("Query")
+        .mutation_type("Mutation")
+        .build();
+    let sdl = registry.try_export_sdl(SDLExportOptions::new()).unwrap();
+    assert!(!sdl.contains("schema {"));
+    let sdl = registry
+        .try_export_sdl(SDLExportOptions::new().always_emit_schema_definition())
+        .unwrap();
+    assert!(sdl.ends_with("schema {\n\tquery: Query\n\tmutation: Mutation\n}\n"));
+
+    let registry = registry::RegistryBuilder::new()
+        .object("RootQuery")
+        .field("value", "Int!")
+        .object("RootMutation")
+        .field("value", "Int!")
+        .query_type("RootQuery")
+        .mutation_type("RootMutation")
+        .build();
+    let sdl = registry.try_export_sdl(SDLExportOptions::new()).unwrap();
+    assert!(sdl.ends_with("schema {\n\tquery: RootQuery\n\tmutation: RootMutation\n}\n"));
+
+    // A type named `Mutation` that is not the mutation root type.
+    let registry = registry::RegistryBuilder::new()
+        .object("Query")
+        .field("mutation", "Mutation")
+        .object("Mutation")
+        .field("value", "Int!")
+        .query_type("Query")
+        .build();
+    let sdl = registry.try_export_sdl(SDLExportOptions::new()).unwrap();
+    assert!(sdl.ends_with("schema {\n\tquery: Query\n}\n"));
+}
+
+#[test]
+pub fn test_block_string_descriptions() {
+    /// A user of the "admin" console.
+    ///
+    /// Users can be disabled.
+    #[derive(SimpleObject)]
+    struct User {
+        /// The name, as in `C:\Users\name`
+        name: String,
+        /// A "nickname"
+        nickname: String,
+        /// Contains """ in the middle.
+        quoted: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        /// The current user.
+        async fn user(&self) -> User {
+            todo!()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let user = r#"
+"""
+A user of the "admin" console.
+
+Users can be disabled.
+"""
+type User {
+	"""
+	The name, as in `C:\Users\name`
+	"""
+	name: String!
+	"""
+	A "nickname"
+	"""
+	nickname: String!
+	"""
+	Contains \""" in the middle.
+	"""
+	quoted: String!
+}
+"#;
+    assert!(schema.sdl().contains(user));
+
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().prefer_single_line_descriptions());
+    assert!(sdl.contains("\t\"The current user.\"\n\tuser: User!\n"));
+    let user = r#"
+"""
+A user of the "admin" console.
+
+Users can be disabled.
+"""
+type User {
+	"The name, as in `C:\\Users\\name`"
+	name: String!
+	"""
+	A "nickname"
+	"""
+	nickname: String!
+	"""
+	Contains \""" in the middle.
+	"""
+	quoted: String!
+}
+"#;
+    assert!(sdl.contains(user));
+}
+
+#[test]
+pub fn test_list_and_object_default_values() {
+    #[derive(InputObject)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn sum(&self, #[graphql(default_with = "vec![1, 2, 3]")] values: Vec<i32>) -> i32 {
+            values.into_iter().sum()
+        }
+
+        async fn norm(&self, #[graphql(default_with = "Point { x: 1, y: 2 }")] p: Point) -> i32 {
+            p.x.abs() + p.y.abs()
+        }
+
+        async fn value(&self, _a: Option<Vec<i32>>, _b: Option<Vec<i32>>, _c: Option<i32>) -> i32 {
+            todo!()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let sdl = schema.sdl();
+    assert!(sdl.contains("sum(values: [Int!]! = [1,2,3]): Int!"));
+    assert!(sdl.contains("norm(p: Point! = {x: 1,y: 2}): Int!"));
+
+    let mut registry = registry::Registry::default();
+    registry.set_query_type::<Query>();
+    let args = &mut registry
+        .types
+        .get_mut("Query")
+        .unwrap()
+        .fields_mut()
+        .unwrap()
+        .get_mut("value")
+        .unwrap()
+        .args;
+    args["a"].default_value = Some("[ 1 , 2 ]".to_string());
+    args["b"].default_value = Some("[1, 2".to_string());
+    args["c"].default_value = Some(r#"{"x": [1, "two"]}"#.to_string());
+    let sdl = registry.try_export_sdl(SDLExportOptions::new()).unwrap();
+    assert!(sdl.contains(r#"value(a: [Int!] = [1,2], b: [Int!], c: Int = {x: [1,"two"]}): Int!"#));
+    parser::parse_schema(&sdl).unwrap();
+}
+
+#[test]
+pub fn test_sorted_implements() {
+    let registry = registry::RegistryBuilder::new()
+        .interface("Node")
+        .field("id", "ID!")
+        .interface("Entity")
+        .field("id", "ID!")
+        .interface("Timestamped")
+        .field("createdAt", "String!")
+        .object("User")
+        .field("id", "ID!")
+        .field("createdAt", "String!")
+        .implements("Timestamped")
+        .implements("Node")
+        .implements("Entity")
+        .object("Query")
+        .field("user", "User")
+        .query_type("Query")
+        .build();
+
+    let sdl = registry.try_export_sdl(SDLExportOptions::new()).unwrap();
+    assert!(sdl.contains("type User implements Entity & Node & Timestamped {"));
+    for _ in 0..10 {
+        assert_eq!(
+            registry.try_export_sdl(SDLExportOptions::new()).unwrap(),
+            sdl
+        );
+    }
+}
+
+#[test]
+pub fn test_directive_argument_default_values() {
+    let mut registry = registry::Registry::from_sdl(
+        r#"
+        directive @cache(
+            policy: CachePolicy!
+            policies: [CachePolicy!]
+            scope: String
+            hint: CacheHint
+        ) on FIELD_DEFINITION
+
+        enum CachePolicy {
+            PUBLIC
+            PRIVATE
+        }
+
+        input CacheHint {
+            policy: CachePolicy
+            label: String
+        }
+
+        type Query {
+            value: Int
+        }
+        "#,
+    )
+    .unwrap();
+
+    let args = &mut registry.directives.get_mut("cache").unwrap().args;
+    // An enum value stored as a string, as serialized by serde.
+    args["policy"].default_value = Some(r#""PUBLIC""#.to_string());
+    args["policies"].default_value = Some(r#"["PUBLIC", "PRIVATE"]"#.to_string());
+    // A string stored without quotes.
+    args["scope"].default_value = Some("PUBLIC".to_string());
+    args["hint"].default_value = Some(r#"{"policy": "PRIVATE", "label": "PUBLIC"}"#.to_string());
+
+    let sdl = registry
+        .try_export_sdl(SDLExportOptions::new().include_directives())
+        .unwrap();
+    assert!(sdl.contains(
+        r#"directive @cache(policy: CachePolicy! = PUBLIC, policies: [CachePolicy!] = [PUBLIC,PRIVATE], scope: String = "PUBLIC", hint: CacheHint = {label: "PUBLIC",policy: PRIVATE}) on FIELD_DEFINITION"#
+    ));
+    parser::parse_schema(&sdl).unwrap();
+}
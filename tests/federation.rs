@@ -827,3 +827,620 @@ pub async fn test_entity_tag() {
         panic!("schema was not up-to-date. rerun")
     }
 }
+
+#[tokio::test]
+pub async fn test_entity_reference_stub() {
+    #[derive(SimpleObject)]
+    #[graphql(extends)]
+    struct User {
+        id: ID,
+        name: String,
+        email: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> User {
+            User {
+                id,
+                name: String::new(),
+                email: String::new(),
+            }
+        }
+
+        async fn me(&self) -> User {
+            todo!()
+        }
+    }
+
+    let schema_sdl = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .export_reference_stub::<User>()
+        .finish()
+        .sdl_with_options(SDLExportOptions::new().federation());
+    assert!(schema_sdl.contains("extend type User @key(fields: \"id\") {\n\tid: ID! @external\n}"));
+
+    // the full type is still exported outside of federation
+    let schema_sdl = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .export_reference_stub::<User>()
+        .finish()
+        .sdl();
+    assert!(schema_sdl.contains("\tname: String!\n"));
+}
+
+#[test]
+pub fn test_federation_manifest() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+        #[graphql(shareable)]
+        name: String,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(extends)]
+    struct Product {
+        #[graphql(external)]
+        upc: String,
+        #[graphql(inaccessible)]
+        price: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> User {
+            User {
+                id,
+                name: String::new(),
+            }
+        }
+
+        #[graphql(entity)]
+        async fn find_product_by_upc(&self, upc: String) -> Product {
+            Product { upc, price: 0 }
+        }
+
+        async fn me(&self) -> User {
+            todo!()
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn rename(&self, _name: String) -> bool {
+            true
+        }
+    }
+
+    let mut registry = registry::Registry::default();
+    registry.set_query_type::<Query>();
+    registry.set_mutation_type::<Mutation>();
+    registry.reference_stubs.insert("Product".to_string());
+
+    assert_eq!(
+        registry.federation_manifest(),
+        serde_json::json!({
+            "entities": [
+                { "name": "Product", "keys": [{ "fields": "upc", "resolvable": false }] },
+                { "name": "User", "keys": [{ "fields": "id", "resolvable": true }] },
+            ],
+            "rootFields": {
+                "query": ["me"],
+                "mutation": ["rename"],
+                "subscription": [],
+            },
+            "directives": ["@external", "@inaccessible", "@key", "@shareable"],
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_link_imports() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+        #[graphql(shareable)]
+        name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> User {
+            User {
+                id,
+                name: String::new(),
+            }
+        }
+    }
+
+    let mut registry = registry::Registry::default();
+    registry.set_query_type::<Query>();
+    assert_eq!(
+        registry.imported_link_directives(),
+        vec!["@key", "@shareable"]
+    );
+    assert_eq!(registry.validate_link_imports(), Ok(()));
+
+    registry.link_imports = Some(vec!["@key".to_string()]);
+    assert_eq!(
+        registry.validate_link_imports(),
+        Err(vec![registry::LinkError::NotImported(
+            "@shareable".to_string()
+        )])
+    );
+
+    let schema_sdl = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_apollo_fed2_link()
+        .apollo_link_imports(["@key", "@shareable"])
+        .finish()
+        .sdl_with_options(SDLExportOptions::new().federation());
+    assert!(schema_sdl.contains("\timport: [\"@key\", \"@shareable\"]\n"));
+}
+
+#[tokio::test]
+pub async fn test_link_specs() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema_sdl = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_apollo_fed2_link()
+        .apollo_link_imports(["@key"])
+        .link_spec(
+            registry::LinkSpec::new("https://myspecs.dev/auth/v1.0")
+                .alias("auth")
+                .import("@authenticated")
+                .import("Scope"),
+        )
+        .finish()
+        .sdl_with_options(SDLExportOptions::new().federation());
+    assert!(schema_sdl.contains(
+        r#"extend schema @link(
+	url: "https://specs.apollo.dev/federation/v2.0",
+	import: ["@key"]
+)
+extend schema @link(
+	url: "https://myspecs.dev/auth/v1.0",
+	as: "auth",
+	import: ["@authenticated", "Scope"]
+)
+"#
+    ));
+
+    let schema_sdl = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_apollo_fed2_link()
+        .link_spec(registry::LinkSpec::new("https://myspecs.dev/auth/v1.0").import(r#"Sc"o\pe"#))
+        .finish()
+        .sdl_with_options(SDLExportOptions::new().federation());
+    assert!(schema_sdl.contains(r#"import: ["Sc\"o\\pe"]"#));
+}
+
+#[tokio::test]
+pub async fn test_federation_version() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+        #[graphql(shareable)]
+        name: String,
+        #[graphql(override_from = "accounts")]
+        email: String,
+        #[graphql(inaccessible)]
+        password: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> User {
+            User {
+                id,
+                name: String::new(),
+                email: String::new(),
+                password: String::new(),
+            }
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .enable_apollo_fed2_link()
+        .finish();
+
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().federation());
+    assert!(sdl.contains("extend schema @link("));
+    assert!(sdl.contains("\tname: String! @shareable\n"));
+    assert!(sdl.contains("\temail: String! @override(from: \"accounts\")\n"));
+    assert!(sdl.contains("\tpassword: String! @inaccessible\n"));
+
+    let sdl = schema.sdl_with_options(
+        SDLExportOptions::new()
+            .federation()
+            .federation_version(FederationVersion::V1),
+    );
+    assert!(!sdl.contains("@link"));
+    assert!(sdl.contains("type User @key(fields: \"id\") {"));
+    assert!(sdl.contains("\tname: String!\n"));
+    assert!(sdl.contains("\temail: String!\n"));
+    assert!(sdl.contains("\tpassword: String!\n"));
+
+    let mut registry = registry::Registry::default();
+    registry.set_query_type::<Query>();
+    assert_eq!(
+        registry.unsupported_federation_directives(FederationVersion::V1),
+        vec!["@inaccessible", "@override", "@shareable"]
+    );
+    assert!(registry
+        .unsupported_federation_directives(FederationVersion::V2)
+        .is_empty());
+}
+
+#[tokio::test]
+pub async fn test_extended_type_local_fields() {
+    struct User {
+        id: ID,
+    }
+
+    #[Object(extends)]
+    impl User {
+        #[graphql(external)]
+        async fn id(&self) -> &ID {
+            &self.id
+        }
+
+        #[graphql(external)]
+        async fn email(&self) -> String {
+            todo!()
+        }
+
+        #[graphql(external)]
+        async fn username(&self) -> String {
+            todo!()
+        }
+
+        #[graphql(external)]
+        async fn age(&self) -> i32 {
+            todo!()
+        }
+
+        #[graphql(requires = "email")]
+        async fn gravatar(&self) -> String {
+            todo!()
+        }
+
+        async fn reviews(&self) -> Vec<String> {
+            todo!()
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> User {
+            User { id }
+        }
+
+        #[graphql(provides = "username")]
+        async fn me(&self) -> User {
+            todo!()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema
+        .sdl_with_options(SDLExportOptions::new().federation())
+        .contains(
+            r#"extend type User @key(fields: "id") {
+	id: ID! @external
+	email: String! @external
+	username: String! @external
+	gravatar: String! @requires(fields: "email")
+	reviews: [String!]!
+}"#
+        ));
+
+    // external fields are kept outside of federation
+    assert!(schema.sdl().contains("\tage: Int!\n"));
+}
+
+#[tokio::test]
+pub async fn test_compose_directive() {
+    struct Noop;
+
+    #[async_trait::async_trait]
+    impl CustomDirective for Noop {}
+
+    #[Directive(location = "field")]
+    fn custom(message: String) -> impl CustomDirective {
+        let _ = message;
+        Noop
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema_sdl = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .directive(custom)
+        .enable_apollo_fed2_link()
+        .link_spec(registry::LinkSpec::new("https://myspecs.dev/custom/v1.0").import("@custom"))
+        .compose_directive("@custom")
+        .finish()
+        .sdl_with_options(SDLExportOptions::new().federation());
+    assert!(schema_sdl.contains(
+        r#"extend schema @link(
+	url: "https://specs.apollo.dev/federation/v2.1",
+	import: ["@composeDirective"]
+)
+extend schema @link(
+	url: "https://myspecs.dev/custom/v1.0",
+	import: ["@custom"]
+)
+extend schema @composeDirective(name: "@custom")
+"#
+    ));
+    assert!(schema_sdl.contains("directive @custom(message: String!) on FIELD\n"));
+
+    let mut registry = registry::Registry::default();
+    registry.add_compose_directive("@missing");
+    assert_eq!(
+        registry.validate_compose_directives().unwrap_err(),
+        vec![registry::LinkError::UnknownComposedDirective(
+            "missing".to_string()
+        )]
+    );
+}
+
+#[test]
+pub fn test_unresolvable_key() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+        email: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> User {
+            User {
+                id,
+                email: String::new(),
+            }
+        }
+    }
+
+    let mut registry = registry::Registry::default();
+    registry.set_query_type::<Query>();
+    registry.add_key_with("User", "email", false);
+
+    assert_eq!(
+        registry.types["User"].keys(),
+        Some(
+            &[
+                registry::MetaKey::new("id"),
+                registry::MetaKey {
+                    fields: "email".to_string(),
+                    resolvable: false,
+                },
+            ][..]
+        )
+    );
+
+    let sdl = registry
+        .try_export_sdl(SDLExportOptions::new().federation())
+        .unwrap();
+    assert!(sdl
+        .contains("type User @key(fields: \"id\") @key(fields: \"email\", resolvable: false) {\n"));
+
+    assert_eq!(
+        registry.federation_manifest()["entities"],
+        serde_json::json!([{
+            "name": "User",
+            "keys": [
+                { "fields": "id", "resolvable": true },
+                { "fields": "email", "resolvable": false },
+            ],
+        }])
+    );
+}
+
+#[test]
+pub fn test_entities() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+    }
+
+    #[derive(SimpleObject)]
+    struct Product {
+        upc: String,
+        sku: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> User {
+            User { id }
+        }
+
+        #[graphql(entity)]
+        async fn find_product_by_upc(&self, upc: String) -> Product {
+            Product {
+                upc,
+                sku: String::new(),
+            }
+        }
+
+        #[graphql(entity)]
+        async fn find_product_by_sku(&self, sku: String) -> Product {
+            Product {
+                upc: String::new(),
+                sku,
+            }
+        }
+    }
+
+    let mut registry = registry::Registry::default();
+    registry.set_query_type::<Query>();
+
+    assert_eq!(
+        registry.entities(),
+        vec![
+            (
+                "Product",
+                &[registry::MetaKey::new("upc"), registry::MetaKey::new("sku")][..]
+            ),
+            ("User", &[registry::MetaKey::new("id")][..]),
+        ]
+    );
+}
+
+#[test]
+pub fn test_validate_inaccessible() {
+    #[derive(SimpleObject)]
+    #[graphql(inaccessible)]
+    struct Secret {
+        value: i32,
+    }
+
+    #[derive(InputObject)]
+    #[graphql(inaccessible)]
+    struct SecretInput {
+        value: i32,
+    }
+
+    #[derive(InputObject)]
+    struct Filter {
+        secret: Option<SecretInput>,
+        #[graphql(inaccessible)]
+        hidden: Option<SecretInput>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn secrets(&self, _filter: Filter) -> Vec<Secret> {
+            todo!()
+        }
+
+        #[graphql(inaccessible)]
+        async fn hidden(&self, _input: SecretInput) -> Secret {
+            todo!()
+        }
+
+        async fn value(&self, #[graphql(inaccessible)] _input: Option<SecretInput>) -> i32 {
+            todo!()
+        }
+
+        async fn search(&self, _input: Option<SecretInput>) -> i32 {
+            todo!()
+        }
+    }
+
+    let mut registry = registry::Registry::default();
+    registry.set_query_type::<Query>();
+
+    let errors = registry.validate_inaccessible().unwrap_err();
+    assert_eq!(
+        errors
+            .iter()
+            .map(|error| error.message.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "The input field `Filter.secret` is accessible, but its type `SecretInput` is `@inaccessible`",
+            "The field `Query.secrets` is accessible, but its type `Secret` is `@inaccessible`",
+            "The argument `Query.search(input:)` is accessible, but its type `SecretInput` is `@inaccessible`",
+        ]
+    );
+    assert_eq!(errors[0].type_name, "Filter");
+    assert_eq!(errors[0].field_name.as_deref(), Some("secret"));
+}
+
+#[test]
+pub fn test_used_feature_directives() {
+    use registry::FederationFeature;
+
+    #[derive(SimpleObject)]
+    #[graphql(shareable)]
+    struct User {
+        id: ID,
+        #[graphql(external)]
+        name: String,
+        #[graphql(requires = "name", tag = "internal")]
+        greeting: String,
+    }
+
+    #[derive(OneofObject)]
+    enum UserBy {
+        Id(ID),
+        Name(String),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, _id: ID) -> User {
+            todo!()
+        }
+
+        async fn user(&self, _by: UserBy) -> User {
+            todo!()
+        }
+    }
+
+    let mut registry = registry::Registry::default();
+    registry.set_query_type::<Query>();
+
+    assert_eq!(
+        registry
+            .used_feature_directives()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec![
+            FederationFeature::Key,
+            FederationFeature::Tag,
+            FederationFeature::Shareable,
+            FederationFeature::External,
+            FederationFeature::Requires,
+            FederationFeature::OneOf,
+        ]
+    );
+    assert_eq!(
+        registry
+            .used_federation_directives()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec!["@external", "@key", "@requires", "@shareable", "@tag"]
+    );
+}
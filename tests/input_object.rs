@@ -694,3 +694,128 @@ pub async fn test_input_object_process_with() {
         })
     );
 }
+
+#[tokio::test]
+pub async fn test_deprecated_input_values() {
+    #[derive(InputObject)]
+    struct MyInput {
+        a: i32,
+        #[graphql(deprecation = "Use `a`")]
+        b: Option<i32>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, input: MyInput, #[graphql(deprecation)] old: Option<i32>) -> i32 {
+            input.a + input.b.unwrap_or_default() + old.unwrap_or_default()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let sdl = schema.sdl();
+    assert!(sdl.contains("\tb: Int @deprecated(reason: \"Use `a`\")\n"));
+    assert!(sdl.contains("\tvalue(input: MyInput!, old: Int @deprecated): Int!\n"));
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    __type(name: "MyInput") {
+                        all: inputFields { name isDeprecated deprecationReason }
+                        current: inputFields(includeDeprecated: false) { name }
+                    }
+                    query: __type(name: "Query") {
+                        fields {
+                            all: args { name isDeprecated deprecationReason }
+                            current: args(includeDeprecated: false) { name }
+                        }
+                    }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__type": {
+                "all": [
+                    { "name": "a", "isDeprecated": false, "deprecationReason": null },
+                    { "name": "b", "isDeprecated": true, "deprecationReason": "Use `a`" },
+                ],
+                "current": [{ "name": "a" }],
+            },
+            "query": {
+                "fields": [{
+                    "all": [
+                        { "name": "input", "isDeprecated": false, "deprecationReason": null },
+                        { "name": "old", "isDeprecated": true, "deprecationReason": null },
+                    ],
+                    "current": [{ "name": "input" }],
+                }]
+            }
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ value(input: { a: 1, b: 2 }, old: 3) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 6 })
+    );
+}
+
+#[tokio::test]
+pub async fn test_self_referential_input_object() {
+    #[derive(InputObject)]
+    struct Filter {
+        name: Option<String>,
+        not: Option<Box<Filter>>,
+        and: Option<Vec<Filter>>,
+    }
+
+    impl Filter {
+        fn count(&self) -> usize {
+            1 + self
+                .not
+                .as_ref()
+                .map(|filter| filter.count())
+                .unwrap_or_default()
+                + self.and.iter().flatten().map(Filter::count).sum::<usize>()
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn count(&self, filter: Filter) -> usize {
+            filter.count()
+        }
+    }
+
+    let mut registry = registry::Registry::default();
+    <Filter as InputType>::create_type_info(&mut registry);
+    let ty = &registry.types["Filter"];
+    assert_eq!(ty.type_id(), registry::MetaTypeId::InputObject);
+    assert_eq!(ty.rust_typename(), Some(std::any::type_name::<Filter>()));
+    assert_eq!(
+        ty.input_field_by_name("not").unwrap().ty,
+        "Filter".to_string()
+    );
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+            count(filter: { name: "a", not: { name: "b" }, and: [{ not: { name: "c" } }, {}] })
+        }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "count": 5
+        })
+    );
+}
@@ -1274,6 +1274,51 @@ pub async fn test_disable_introspection() {
     );
 }
 
+#[tokio::test]
+pub async fn test_disabled_introspection_schema() {
+    #[derive(SimpleObject)]
+    struct Query {
+        value: i32,
+    }
+
+    let query = "{ __schema { types { name } } }";
+    let schema = Schema::build(Query { value: 10 }, EmptyMutation, EmptySubscription)
+        .disable_introspection()
+        .finish();
+    let errors = schema.execute(query).await.into_result().unwrap_err();
+    assert_eq!(
+        errors,
+        vec![ServerError {
+            message: "Introspection is disabled.".to_string(),
+            source: None,
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("__schema".to_string())],
+            extensions: None,
+        }]
+    );
+
+    let schema = Schema::new(Query { value: 10 }, EmptyMutation, EmptySubscription);
+    let errors = schema
+        .execute(Request::new(query).disable_introspection())
+        .await
+        .into_result()
+        .unwrap_err();
+    assert_eq!(errors[0].message, "Introspection is disabled.");
+
+    assert_eq!(
+        schema
+            .execute(
+                Request::new(r#"{ value __typename __type(name: "Query") { name } }"#)
+                    .disable_introspection()
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "value": 10, "__typename": "Query", "__type": null })
+    );
+}
+
 #[tokio::test]
 pub async fn test_introspection_only() {
     let schema = Schema::build(Query, Mutation, EmptySubscription)
@@ -1511,3 +1556,139 @@ pub async fn test_introspection_default() {
     let res = schema.execute(query).await.into_result().unwrap().data;
     assert_eq!(res, res_json);
 }
+
+#[tokio::test]
+pub async fn test_introspection_complexity() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_complexity(2)
+        .finish();
+
+    let res = schema
+        .execute("{ __schema { types { name fields { name type { name } } } } }")
+        .await;
+    assert!(res.is_ok());
+
+    let res = schema
+        .execute(r#"{ value __type(name: "Query") { name fields { name } } }"#)
+        .await;
+    assert!(res.is_ok());
+
+    let res = schema.execute("{ a: value b: value c: value }").await;
+    assert_eq!(
+        res.errors
+            .into_iter()
+            .map(|err| err.message)
+            .collect::<Vec<_>>(),
+        vec!["Query is too complex.".to_string()]
+    );
+}
+
+#[tokio::test]
+pub async fn test_introspection_json() {
+    let query = r#"
+        query IntrospectionQuery($includeDeprecated: Boolean!) {
+            __schema {
+                queryType { name }
+                mutationType { name }
+                subscriptionType { name }
+                types { ...FullType }
+                directives {
+                    name
+                    description
+                    locations
+                    args(includeDeprecated: $includeDeprecated) { ...InputValue }
+                    isRepeatable
+                }
+            }
+        }
+
+        fragment FullType on __Type {
+            kind
+            name
+            description
+            specifiedByURL
+            fields(includeDeprecated: $includeDeprecated) {
+                name
+                description
+                args(includeDeprecated: $includeDeprecated) { ...InputValue }
+                type { ...TypeRef }
+                isDeprecated
+                deprecationReason
+            }
+            inputFields(includeDeprecated: $includeDeprecated) { ...InputValue }
+            interfaces { ...TypeRef }
+            enumValues(includeDeprecated: $includeDeprecated) {
+                name
+                description
+                isDeprecated
+                deprecationReason
+            }
+            possibleTypes { ...TypeRef }
+            oneOf
+        }
+
+        fragment InputValue on __InputValue {
+            name
+            description
+            type { ...TypeRef }
+            defaultValue
+            isDeprecated
+            deprecationReason
+        }
+
+        fragment TypeRef on __Type {
+            kind
+            name
+            ofType {
+                kind
+                name
+                ofType {
+                    kind
+                    name
+                    ofType {
+                        kind
+                        name
+                        ofType {
+                            kind
+                            name
+                            ofType {
+                                kind
+                                name
+                                ofType { kind name ofType { kind name } }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    "#;
+
+    let schema = Schema::new(Query, Mutation, Subscription);
+    for include_deprecated in [true, false] {
+        let res = schema
+            .execute(Request::new(query).variables(Variables::from_json(
+                serde_json::json!({ "includeDeprecated": include_deprecated }),
+            )))
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(
+            schema.introspection_json(include_deprecated),
+            Some(serde_json::json!({ "data": res.data.into_json().unwrap() }))
+        );
+    }
+
+    let schema = Schema::build(Query, Mutation, Subscription)
+        .disable_introspection()
+        .finish();
+    assert_eq!(schema.introspection_json(true), None);
+}
@@ -665,3 +665,66 @@ pub async fn root() {
         value!({ "__schema": { "queryType": { "name": "Query" } } })
     );
 }
+
+#[tokio::test]
+pub async fn test_visible_types_introspection_mode() {
+    #[derive(SimpleObject)]
+    struct MyObj {
+        a: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn obj(&self) -> MyObj {
+            MyObj { a: 1 }
+        }
+
+        async fn visible_types(&self, ctx: &Context<'_>) -> Vec<String> {
+            let mut types = ctx
+                .schema_env
+                .registry
+                .find_visible_types(ctx)
+                .into_iter()
+                .filter(|name| *name == "MyObj" || *name == "Int" || *name == "__Type")
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            types.sort();
+            types
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ visibleTypes }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "visibleTypes": ["Int", "MyObj", "__Type"] })
+    );
+    assert_eq!(
+        schema
+            .execute(Request::new("{ visibleTypes }").disable_introspection())
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "visibleTypes": ["Int", "MyObj"] })
+    );
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .disable_introspection()
+        .finish();
+    assert_eq!(
+        schema
+            .execute("{ visibleTypes }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({ "visibleTypes": ["Int", "MyObj"] })
+    );
+}
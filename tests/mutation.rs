@@ -251,3 +251,132 @@ pub async fn test_serial_merged_object() {
         .await;
     assert_eq!(&*list.lock().await, &[1, 2, 3, 4]);
 }
+
+#[tokio::test]
+pub async fn test_serial_fields() {
+    type List = Arc<Mutex<Vec<i32>>>;
+
+    struct MyObj;
+
+    #[Object]
+    impl MyObj {
+        #[graphql(serial)]
+        async fn append1(&self, ctx: &Context<'_>) -> bool {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            ctx.data_unchecked::<List>().lock().await.push(1);
+            true
+        }
+
+        async fn append2(&self, ctx: &Context<'_>) -> bool {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            ctx.data_unchecked::<List>().lock().await.push(2);
+            true
+        }
+
+        #[graphql(serial)]
+        async fn append3(&self, ctx: &Context<'_>) -> bool {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            ctx.data_unchecked::<List>().lock().await.push(3);
+            true
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn obj(&self) -> MyObj {
+            MyObj
+        }
+    }
+
+    let list = List::default();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(list.clone())
+        .finish();
+    let res = schema
+        .execute("{ obj { append3 append2 append1 } }")
+        .await
+        .into_result()
+        .unwrap()
+        .data;
+    assert_eq!(
+        res,
+        value!({ "obj": { "append3": true, "append2": true, "append1": true } })
+    );
+    // `append2` is not serial and resolves concurrently with the serial fields.
+    assert_eq!(&*list.lock().await, &[2, 3, 1]);
+}
+
+#[tokio::test]
+pub async fn test_serial_fields_merged_and_flattened() {
+    type List = Arc<Mutex<Vec<i32>>>;
+
+    struct Slow;
+
+    #[Object]
+    impl Slow {
+        #[graphql(serial)]
+        async fn append1(&self, ctx: &Context<'_>) -> bool {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            ctx.data_unchecked::<List>().lock().await.push(1);
+            true
+        }
+    }
+
+    struct Fast;
+
+    #[Object]
+    impl Fast {
+        #[graphql(serial)]
+        async fn append2(&self, ctx: &Context<'_>) -> bool {
+            ctx.data_unchecked::<List>().lock().await.push(2);
+            true
+        }
+    }
+
+    #[derive(MergedObject)]
+    struct Merged(Slow, Fast);
+
+    struct Flattened;
+
+    #[Object]
+    impl Flattened {
+        #[graphql(flatten)]
+        async fn slow(&self) -> Slow {
+            Slow
+        }
+
+        #[graphql(flatten)]
+        async fn fast(&self) -> Fast {
+            Fast
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn merged(&self) -> Merged {
+            Merged(Slow, Fast)
+        }
+
+        async fn flattened(&self) -> Flattened {
+            Flattened
+        }
+    }
+
+    let list = List::default();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(list.clone())
+        .finish();
+
+    for query in [
+        "{ merged { append1 append2 } }",
+        "{ flattened { append1 append2 } }",
+    ] {
+        list.lock().await.clear();
+        schema.execute(query).await.into_result().unwrap();
+        assert_eq!(&*list.lock().await, &[1, 2], "{}", query);
+    }
+}
@@ -193,3 +193,51 @@ async fn ignore_name_conflicts() {
         })
     );
 }
+
+#[tokio::test]
+async fn test_multiply_complexity() {
+    #[derive(SimpleObject)]
+    struct Item {
+        a: i32,
+        b: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(complexity = "multiply:count:4")]
+        async fn items(&self, #[graphql(name = "count")] n: Option<i32>) -> Vec<Item> {
+            (0..n.unwrap_or(4)).map(|a| Item { a, b: a }).collect()
+        }
+    }
+
+    let mut registry = registry::Registry::default();
+    registry.set_query_type::<Query>();
+    assert!(matches!(
+        registry.field_complexity("Query", "items"),
+        Some(registry::ComplexityType::Multiplier { arg, default: 4 }) if arg == "count"
+    ));
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_complexity(4)
+        .finish();
+
+    assert_eq!(
+        schema
+            .execute("{ items(count: 2) { a b } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "items": [{ "a": 0, "b": 0 }, { "a": 1, "b": 1 }],
+        })
+    );
+    for query in ["{ items(count: 3) { a b } }", "{ items { a b } }"] {
+        assert_eq!(
+            schema.execute(query).await.into_result().unwrap_err(),
+            vec![ServerError::new("Query is too complex.", None)]
+        );
+    }
+}
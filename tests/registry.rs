@@ -0,0 +1,2742 @@
+#![allow(unreachable_code)]
+
+use async_graphql::{
+    registry::{
+        Change, ComplexityType, CycleInfo, FromSdlError, MergeError, MetaField, MetaKey, MetaType,
+        MetaTypeId, MetaTypeName, NameKind, Registry, RegistryBuilder, RenameError,
+        SDLExportOptions, SchemaError, ValidateOptions,
+    },
+    *,
+};
+
+#[test]
+pub fn test_custom_scalar_names() {
+    struct Zebra;
+
+    #[Scalar]
+    impl ScalarType for Zebra {
+        fn parse(_value: Value) -> InputValueResult<Self> {
+            Ok(Zebra)
+        }
+
+        fn to_value(&self) -> Value {
+            Value::Null
+        }
+    }
+
+    struct Aardvark;
+
+    #[Scalar]
+    impl ScalarType for Aardvark {
+        fn parse(_value: Value) -> InputValueResult<Self> {
+            Ok(Aardvark)
+        }
+
+        fn to_value(&self) -> Value {
+            Value::Null
+        }
+    }
+
+    let mut registry = Registry::default();
+    <i32 as OutputType>::create_type_info(&mut registry);
+    <String as OutputType>::create_type_info(&mut registry);
+    <Zebra as OutputType>::create_type_info(&mut registry);
+    <Aardvark as OutputType>::create_type_info(&mut registry);
+
+    assert_eq!(registry.custom_scalar_names(), vec!["Aardvark", "Zebra"]);
+}
+
+#[test]
+pub fn test_scalars() {
+    struct Zebra;
+
+    #[Scalar(specified_by_url = "https://example.com/zebra")]
+    impl ScalarType for Zebra {
+        fn parse(_value: Value) -> InputValueResult<Self> {
+            Ok(Zebra)
+        }
+
+        fn to_value(&self) -> Value {
+            Value::Null
+        }
+    }
+
+    struct Aardvark;
+
+    #[Scalar]
+    impl ScalarType for Aardvark {
+        fn parse(_value: Value) -> InputValueResult<Self> {
+            Ok(Aardvark)
+        }
+
+        fn to_value(&self) -> Value {
+            Value::Null
+        }
+    }
+
+    let mut registry = Registry::default();
+    <Zebra as OutputType>::create_type_info(&mut registry);
+    <i32 as OutputType>::create_type_info(&mut registry);
+    <Aardvark as OutputType>::create_type_info(&mut registry);
+    <String as OutputType>::create_type_info(&mut registry);
+
+    assert_eq!(
+        registry.scalars().collect::<Vec<_>>(),
+        vec![
+            ("Aardvark", None),
+            ("Int", None),
+            ("String", None),
+            ("Zebra", Some("https://example.com/zebra")),
+        ]
+    );
+    assert_eq!(
+        registry.custom_scalars().collect::<Vec<_>>(),
+        vec![
+            ("Aardvark", None),
+            ("Zebra", Some("https://example.com/zebra")),
+        ]
+    );
+}
+
+#[test]
+pub fn test_max_input_nesting_depth() {
+    #[derive(InputObject)]
+    struct Leaf {
+        value: i32,
+    }
+
+    #[derive(InputObject)]
+    struct Middle {
+        leaf: Leaf,
+        leaves: Vec<Leaf>,
+    }
+
+    #[derive(InputObject)]
+    struct Top {
+        middle: Option<Middle>,
+        value: String,
+    }
+
+    #[derive(InputObject)]
+    struct Recursive {
+        next: Option<Box<Recursive>>,
+    }
+
+    let mut registry = Registry::default();
+    assert_eq!(registry.max_input_nesting_depth(), 0);
+
+    <Leaf as InputType>::create_type_info(&mut registry);
+    assert_eq!(registry.max_input_nesting_depth(), 1);
+
+    <Top as InputType>::create_type_info(&mut registry);
+    assert_eq!(registry.max_input_nesting_depth(), 3);
+
+    <Recursive as InputType>::create_type_info(&mut registry);
+    assert_eq!(registry.max_input_nesting_depth(), usize::MAX);
+}
+
+#[test]
+pub fn test_input_closure() {
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Role {
+        Admin,
+        Guest,
+    }
+
+    #[derive(InputObject)]
+    struct Profile {
+        role: Role,
+        parent: Option<Box<UserInput>>,
+    }
+
+    #[derive(InputObject)]
+    struct UserInput {
+        name: String,
+        profiles: Vec<Profile>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn create_user(&self, _user: UserInput, _dry_run: Option<bool>) -> i32 {
+            10
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+    registry.set_mutation_type::<Mutation>();
+
+    assert_eq!(
+        registry
+            .input_closure("Mutation", "createUser")
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>(),
+        vec!["Boolean", "Profile", "Role", "String", "UserInput"]
+    );
+    assert!(registry.input_closure("Query", "value").is_empty());
+    assert!(registry.input_closure("Mutation", "unknown").is_empty());
+}
+
+#[test]
+pub fn test_field_signature() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn a(&self, x: i32, y: String) -> i32 {
+            x + y.len() as i32
+        }
+
+        async fn b(&self, x: i32, y: String) -> i32 {
+            x + y.len() as i32
+        }
+
+        async fn c(&self, y: String, x: i32) -> i32 {
+            x + y.len() as i32
+        }
+    }
+
+    let mut registry = Registry::default();
+    <Query as OutputType>::create_type_info(&mut registry);
+    let ty = registry.types.get("Query").unwrap();
+    let a = ty.field_by_name("a").unwrap();
+    let b = ty.field_by_name("b").unwrap();
+    let c = ty.field_by_name("c").unwrap();
+
+    assert_eq!(a.signature(), a.clone().signature());
+    // The signature is a specified hash, so it is the same in every build.
+    assert_eq!(a.signature(), 0x02f4_b77d_77fa_5e79);
+    assert_ne!(a.signature(), b.signature());
+    assert_ne!(
+        b.signature(),
+        MetaField {
+            name: "b".to_string(),
+            ..c.clone()
+        }
+        .signature()
+    );
+
+    let mut documented = a.clone();
+    documented.description = Some("A documented field".to_string());
+    documented.compute_complexity = Some(ComplexityType::Const(10));
+    assert_eq!(a.signature(), documented.signature());
+
+    let mut changed = a.clone();
+    changed.ty = "Int".to_string();
+    assert_ne!(a.signature(), changed.signature());
+}
+
+#[test]
+pub fn test_set_root_types() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            0
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn action(&self) -> bool {
+            true
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl futures_util::Stream<Item = i32> {
+            futures_util::stream::iter(0..10)
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+    registry.set_mutation_type::<Mutation>();
+    registry.set_subscription_type::<Subscription>();
+    assert_eq!(registry.query_type, "Query");
+    assert_eq!(registry.mutation_type.as_deref(), Some("Mutation"));
+    assert_eq!(registry.subscription_type.as_deref(), Some("Subscription"));
+    assert!(registry.types.contains_key("Query"));
+    assert!(registry.types.contains_key("Mutation"));
+    assert!(registry.types.contains_key("Subscription"));
+    assert_eq!(registry.validate(), Ok(()));
+}
+
+#[test]
+pub fn test_validate_root_types() {
+    let mut registry = Registry::default();
+    assert_eq!(registry.validate(), Ok(()));
+
+    <i32 as OutputType>::create_type_info(&mut registry);
+    registry.query_type = "Query".to_string();
+    registry.mutation_type = Some("Int".to_string());
+
+    let errors = registry.validate().unwrap_err();
+    assert_eq!(
+        errors
+            .iter()
+            .map(|err| (err.type_name.as_str(), err.message.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("Query", "The query root type `Query` is not registered"),
+            (
+                "Int",
+                "The mutation root type `Int` must be an object, but it is a `Scalar`"
+            ),
+        ]
+    );
+}
+
+#[test]
+pub fn test_scalar_round_trip() {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    scalar!(Point);
+
+    let mut registry = Registry::default();
+    <i32 as InputType>::create_type_info(&mut registry);
+    <Point as InputType>::create_type_info(&mut registry);
+    <Json<Point> as InputType>::create_type_info(&mut registry);
+
+    assert_eq!(
+        registry.scalar_round_trip("Int", value!(10)),
+        Some(Ok(value!(10)))
+    );
+    assert!(matches!(
+        registry.scalar_round_trip("Int", value!("abc")),
+        Some(Err(_))
+    ));
+    assert_eq!(
+        registry.scalar_round_trip("Point", value!({ "x": 1, "y": 2 })),
+        Some(Ok(value!({ "x": 1, "y": 2 })))
+    );
+    assert!(matches!(
+        registry.scalar_round_trip("Point", value!({ "x": 1 })),
+        Some(Err(_))
+    ));
+    assert_eq!(registry.scalar_round_trip("JSON", value!(1)), None);
+    assert_eq!(registry.scalar_round_trip("Unknown", value!(1)), None);
+}
+
+#[test]
+pub fn test_meta_type_keys() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+        name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(entity)]
+        async fn find_user_by_id(&self, id: ID) -> User {
+            User {
+                id,
+                name: String::new(),
+            }
+        }
+
+        async fn value(&self) -> i32 {
+            0
+        }
+    }
+
+    let mut registry = Registry::default();
+    <Query as OutputType>::create_type_info(&mut registry);
+
+    assert_eq!(
+        registry.types.get("User").unwrap().keys(),
+        Some(&[MetaKey::new("id")][..])
+    );
+    assert_eq!(registry.types.get("Query").unwrap().keys(), None);
+    assert_eq!(registry.types.get("Int").unwrap().keys(), None);
+}
+
+#[test]
+pub fn test_deduplicate_identical_types() {
+    #[derive(SimpleObject)]
+    struct PageA {
+        items: Vec<i32>,
+    }
+
+    #[derive(SimpleObject)]
+    struct PageB {
+        items: Vec<i32>,
+    }
+
+    /// A documented page
+    #[derive(SimpleObject)]
+    struct PageC {
+        items: Vec<i32>,
+    }
+
+    #[derive(SimpleObject)]
+    struct ResultA {
+        page: PageA,
+    }
+
+    #[derive(SimpleObject)]
+    struct ResultB {
+        page: PageB,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn a(&self) -> ResultA {
+            todo!()
+        }
+
+        async fn b(&self) -> Vec<ResultB> {
+            todo!()
+        }
+
+        async fn c(&self) -> Option<PageC> {
+            todo!()
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+
+    assert_eq!(
+        registry.deduplicate_identical_types(),
+        vec![
+            ("PageB".to_string(), "PageA".to_string()),
+            ("ResultB".to_string(), "ResultA".to_string()),
+        ]
+    );
+    assert!(!registry.types.contains_key("PageB"));
+    assert!(!registry.types.contains_key("ResultB"));
+    assert!(registry.types.contains_key("PageC"));
+
+    let query = &registry.types["Query"];
+    assert_eq!(query.field_by_name("a").unwrap().ty, "ResultA!");
+    assert_eq!(query.field_by_name("b").unwrap().ty, "[ResultA!]!");
+    assert_eq!(query.field_by_name("c").unwrap().ty, "PageC");
+
+    assert!(registry.deduplicate_identical_types().is_empty());
+}
+
+#[test]
+pub fn test_transform_descriptions() {
+    /// A color
+    ///
+    /// INTERNAL: only used by the legacy renderer
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Color {
+        /// INTERNAL: deprecated soon
+        Red,
+        /// The color green
+        Green,
+    }
+
+    /// A query root
+    struct Query;
+
+    #[Object]
+    impl Query {
+        /// Returns the color
+        async fn color(&self, #[graphql(desc = "INTERNAL: unused")] _seed: i32) -> Color {
+            Color::Red
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+    registry.transform_descriptions(|description| {
+        let description = description
+            .lines()
+            .filter(|line| !line.starts_with("INTERNAL:"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let description = description.trim();
+        if description.is_empty() {
+            None
+        } else {
+            Some(description.to_string())
+        }
+    });
+
+    let (color_description, red, green) = match &registry.types["Color"] {
+        registry::MetaType::Enum {
+            description,
+            enum_values,
+            ..
+        } => (
+            description.as_deref(),
+            enum_values["RED"].description.as_deref(),
+            enum_values["GREEN"].description.as_deref(),
+        ),
+        _ => unreachable!(),
+    };
+    assert_eq!(color_description, Some("A color"));
+    assert_eq!(red, None);
+    assert_eq!(green, Some("The color green"));
+
+    let field = registry.types["Query"].field_by_name("color").unwrap();
+    assert_eq!(field.description.as_deref(), Some("Returns the color"));
+    assert_eq!(field.args["seed"].description, None);
+}
+
+#[test]
+pub fn test_validate_costs() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(cost = 5)]
+        async fn value(&self) -> i32 {
+            0
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+    assert_eq!(registry.validate_costs(), Ok(()));
+
+    if let Some(registry::MetaType::Object { fields, .. }) = registry.types.get_mut("Query") {
+        fields["value"].compute_complexity = Some(ComplexityType::Const(3));
+    }
+    let errors = registry.validate_costs().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].type_name, "Query");
+    assert_eq!(errors[0].field_name.as_deref(), Some("value"));
+    assert_eq!(
+        errors[0].message,
+        "The field `Query.value` has a cost of 5, but its complexity is not 5"
+    );
+    assert_eq!(registry.validate(), Err(errors));
+}
+
+#[test]
+pub fn test_schema_only() {
+    struct Password(String);
+
+    #[Scalar]
+    impl ScalarType for Password {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            match value {
+                Value::String(s) if s.len() >= 8 => Ok(Password(s)),
+                _ => Err(InputValueError::custom("too short")),
+            }
+        }
+
+        fn is_valid(value: &Value) -> bool {
+            matches!(value, Value::String(s) if s.len() >= 8)
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.0.clone())
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(visible = false)]
+        async fn hidden(&self, _password: Password) -> i32 {
+            0
+        }
+
+        #[graphql(complexity = "count * child_complexity")]
+        async fn items(&self, count: usize) -> Vec<i32> {
+            vec![0; count]
+        }
+
+        #[graphql(complexity = 5)]
+        async fn constant(&self) -> i32 {
+            0
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+    let schema_only = registry.schema_only();
+
+    match &schema_only.types["Password"] {
+        registry::MetaType::Scalar {
+            is_valid,
+            round_trip,
+            ..
+        } => {
+            assert!(is_valid(&value!("short")));
+            assert!(round_trip.is_none());
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(
+        schema_only.scalar_round_trip("Password", value!("short")),
+        None
+    );
+
+    let query = &schema_only.types["Query"];
+    assert!(query.field_by_name("hidden").unwrap().visible.is_none());
+    assert!(matches!(
+        query.field_by_name("items").unwrap().compute_complexity,
+        Some(ComplexityType::Const(1))
+    ));
+    assert!(matches!(
+        query.field_by_name("constant").unwrap().compute_complexity,
+        Some(ComplexityType::Const(5))
+    ));
+
+    // The original registry is unchanged
+    let query = &registry.types["Query"];
+    assert!(query.field_by_name("hidden").unwrap().visible.is_some());
+    assert!(matches!(
+        query.field_by_name("items").unwrap().compute_complexity,
+        Some(ComplexityType::Fn(_))
+    ));
+}
+
+#[test]
+pub fn test_validate_orphan_interfaces() {
+    #[derive(SimpleObject)]
+    struct Circle {
+        radius: f32,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "radius", type = "&f32"))]
+    enum Shape {
+        Circle(Circle),
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "radius", type = "&f32"))]
+    enum Orphan {
+        Circle(Circle),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn shape(&self) -> Shape {
+            todo!()
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+    <Orphan as OutputType>::create_type_info(&mut registry);
+    if let Some(registry::MetaType::Interface { possible_types, .. }) =
+        registry.types.get_mut("Orphan")
+    {
+        possible_types.clear();
+    }
+
+    let orphan = SchemaError {
+        type_name: "Orphan".to_string(),
+        field_name: None,
+        message: "The interface `Orphan` is not implemented by any type".to_string(),
+    };
+    assert_eq!(registry.validate(), Ok(()));
+    assert_eq!(
+        registry.validate_with_options(ValidateOptions::new()),
+        Ok(vec![orphan.clone()])
+    );
+    assert_eq!(
+        registry.validate_with_options(ValidateOptions::new().deny_orphan_interfaces()),
+        Err(vec![orphan])
+    );
+}
+
+#[test]
+pub fn test_registry_builder() {
+    let mut registry = RegistryBuilder::new()
+        .interface("Node")
+        .field("id", "ID!")
+        .object("User")
+        .implements("Node")
+        .field("id", "ID!")
+        .field("friends", "[User!]!")
+        .arg("first", "Int")
+        .object("Image")
+        .field("url", "String!")
+        .object("Unused")
+        .field("value", "Float")
+        .union("Media", &["Image"])
+        .enum_type("Color", &["RED", "GREEN"])
+        .input_object("Filter")
+        .field("color", "Color")
+        .object("Query")
+        .field("node", "Node")
+        .arg("filter", "Filter")
+        .field("media", "[Media!]!")
+        .query_type("Query")
+        .build();
+
+    assert_eq!(registry.query_type, "Query");
+    assert!(registry.types["Node"].is_possible_type("User"));
+    assert!(registry.types["Media"].is_possible_type("Image"));
+    let friends = registry.types["User"].field_by_name("friends").unwrap();
+    assert_eq!(friends.ty, "[User!]!");
+    assert_eq!(friends.args["first"].ty, "Int");
+    for scalar in ["ID", "Int", "String", "Float"] {
+        assert!(registry.types.contains_key(scalar));
+    }
+    assert!(!registry.types.contains_key("Boolean"));
+    assert_eq!(registry.validate(), Ok(()));
+
+    registry.remove_unused_types();
+    assert!(!registry.types.contains_key("Unused"));
+    assert!(registry.types.contains_key("Color"));
+}
+
+#[test]
+pub fn test_input_field_by_name() {
+    let registry = RegistryBuilder::new()
+        .input_object("Filter")
+        .field("color", "String")
+        .object("Query")
+        .field("color", "String")
+        .build();
+
+    let filter = &registry.types["Filter"];
+    assert_eq!(filter.input_field_by_name("color").unwrap().ty, "String");
+    assert!(filter.input_field_by_name("size").is_none());
+    assert!(registry.types["Query"]
+        .input_field_by_name("color")
+        .is_none());
+}
+
+#[test]
+pub fn test_deprecated_enum_values() {
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Size {
+        Small,
+        #[graphql(deprecation = "Use `LARGE`")]
+        Medium,
+        Large,
+        #[graphql(deprecation)]
+        Huge,
+    }
+
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Color {
+        Red,
+        #[graphql(deprecation = "No longer available")]
+        Blue,
+    }
+
+    let mut registry = Registry::default();
+    <Size as InputType>::create_type_info(&mut registry);
+    <Color as InputType>::create_type_info(&mut registry);
+
+    assert_eq!(
+        registry
+            .deprecated_enum_values()
+            .into_iter()
+            .map(|(enum_name, value)| (enum_name, value.name.as_str(), value.deprecation.reason()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("Color", "BLUE", Some("No longer available")),
+            ("Size", "MEDIUM", Some("Use `LARGE`")),
+            ("Size", "HUGE", None),
+        ]
+    );
+}
+
+#[test]
+pub fn test_argument_usages() {
+    #[derive(InputObject)]
+    struct Filter {
+        name: String,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "search", type = "i32", arg(name = "filter", type = "Filter")))]
+    enum Searchable {
+        Query(Query),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn search(&self, _filter: Filter) -> i32 {
+            10
+        }
+
+        async fn users(&self, _filters: Option<Vec<Filter>>, _limit: i32) -> i32 {
+            10
+        }
+
+        async fn value(&self, _name: String) -> i32 {
+            10
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+    <Searchable as OutputType>::create_type_info(&mut registry);
+
+    assert_eq!(
+        registry.argument_usages("Filter"),
+        vec![
+            ("Query", "search", "filter"),
+            ("Query", "users", "filters"),
+            ("Searchable", "search", "filter"),
+        ]
+    );
+    assert_eq!(
+        registry.argument_usages("Int"),
+        vec![("Query", "users", "limit")]
+    );
+    assert!(registry.argument_usages("Unknown").is_empty());
+}
+
+#[test]
+pub fn test_referenced_by() {
+    let registry = RegistryBuilder::new()
+        .object("Query")
+        .field("user", "User")
+        .arg("filter", "UserFilter")
+        .field("users", "[User!]!")
+        .field("node", "Node")
+        .field("search", "Search")
+        .interface("Node")
+        .field("id", "ID!")
+        .object("User")
+        .implements("Node")
+        .field("id", "ID!")
+        .field("friends", "[User]")
+        .field("userName", "String")
+        .object("UserName")
+        .field("value", "String")
+        .input_object("UserFilter")
+        .field("friend", "UserFilter")
+        .union("Search", &["User", "UserName"])
+        .query_type("Query")
+        .build();
+
+    let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+    assert_eq!(
+        registry.referenced_by("User"),
+        vec![
+            pair("Node", ""),
+            pair("Query", "user"),
+            pair("Query", "users"),
+            pair("Search", ""),
+            pair("User", "friends"),
+        ]
+    );
+    assert_eq!(
+        registry.referenced_by("UserFilter"),
+        vec![pair("Query", "user(filter:)"), pair("UserFilter", "friend")]
+    );
+    assert_eq!(registry.referenced_by("Query"), vec![]);
+    assert_eq!(registry.referenced_by("Unknown"), vec![]);
+}
+
+#[test]
+pub fn test_input_object_dependency_order() {
+    let registry = RegistryBuilder::new()
+        .object("Query")
+        .field("value", "Int")
+        .arg("filter", "Filter")
+        .input_object("Filter")
+        .field("range", "Range")
+        .field("tags", "[TagFilter!]")
+        .input_object("Range")
+        .field("from", "Int")
+        .field("to", "Int")
+        .input_object("TagFilter")
+        .field("name", "String!")
+        .field("range", "Range")
+        .query_type("Query")
+        .build();
+    assert_eq!(
+        registry.input_object_dependency_order(),
+        Ok(vec![
+            "Range".to_string(),
+            "TagFilter".to_string(),
+            "Filter".to_string()
+        ])
+    );
+
+    let registry = RegistryBuilder::new()
+        .object("Query")
+        .field("value", "Int")
+        .arg("filter", "Filter")
+        .input_object("Filter")
+        .field("and", "[Filter!]")
+        .field("condition", "Condition")
+        .input_object("Condition")
+        .field("field", "String!")
+        .field("nested", "Nested")
+        .input_object("Nested")
+        .field("condition", "Condition")
+        .query_type("Query")
+        .build();
+    assert_eq!(
+        registry.input_object_dependency_order(),
+        Err(CycleInfo {
+            types: vec!["Condition".to_string(), "Nested".to_string()],
+            break_point: ("Nested".to_string(), "condition".to_string()),
+            order: vec![
+                "Nested".to_string(),
+                "Condition".to_string(),
+                "Filter".to_string()
+            ],
+        })
+    );
+}
+
+#[test]
+pub fn test_normalize_names() {
+    fn to_camel_case(name: &str) -> String {
+        let mut parts = name.split('_');
+        let mut camel = parts.next().unwrap_or_default().to_string();
+        for part in parts {
+            let mut chars = part.chars();
+            if let Some(first) = chars.next() {
+                camel.extend(first.to_uppercase());
+                camel.push_str(chars.as_str());
+            }
+        }
+        camel
+    }
+
+    let mut registry = RegistryBuilder::new()
+        .object("query_root")
+        .field("user_by_id", "user_profile")
+        .arg("user_id", "ID!")
+        .field("all_users", "[user_profile!]!")
+        .arg("sort_order", "sort_order")
+        .arg("filter", "user_filter")
+        .object("user_profile")
+        .field("first_name", "String")
+        .enum_type("sort_order", &["oldest_first", "newest_first"])
+        .input_object("user_filter")
+        .field("name_prefix", "String")
+        .query_type("query_root")
+        .build();
+
+    registry
+        .normalize_names(|kind, name| match kind {
+            NameKind::Type => {
+                let camel = to_camel_case(name);
+                Some(camel[..1].to_uppercase() + &camel[1..])
+            }
+            NameKind::EnumValue => Some(name.to_uppercase()),
+            NameKind::Field | NameKind::InputField | NameKind::Argument if name.contains('_') => {
+                Some(to_camel_case(name))
+            }
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(registry.query_type, "QueryRoot");
+    assert!(!registry.types.contains_key("query_root"));
+
+    let query = registry.types.get("QueryRoot").unwrap();
+    assert_eq!(query.name(), "QueryRoot");
+    let user_by_id = query.field_by_name("userById").unwrap();
+    assert_eq!(user_by_id.name, "userById");
+    assert_eq!(user_by_id.ty, "UserProfile");
+    assert_eq!(user_by_id.args["userId"].name, "userId");
+    let all_users = query.field_by_name("allUsers").unwrap();
+    assert_eq!(all_users.ty, "[UserProfile!]!");
+    assert_eq!(all_users.args["sortOrder"].ty, "SortOrder");
+    assert_eq!(all_users.args["filter"].ty, "UserFilter");
+
+    assert!(registry
+        .types
+        .get("UserProfile")
+        .unwrap()
+        .field_by_name("firstName")
+        .is_some());
+    assert!(registry
+        .types
+        .get("UserFilter")
+        .unwrap()
+        .input_field_by_name("namePrefix")
+        .is_some());
+    match registry.types.get("SortOrder").unwrap() {
+        MetaType::Enum { enum_values, .. } => assert_eq!(
+            enum_values
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("OLDEST_FIRST", "OLDEST_FIRST"),
+                ("NEWEST_FIRST", "NEWEST_FIRST")
+            ]
+        ),
+        _ => unreachable!(),
+    }
+    assert!(registry.types.contains_key("ID"));
+    assert_eq!(registry.validate(), Ok(()));
+}
+
+#[test]
+pub fn test_normalize_names_default_values() {
+    let mut registry = Registry::from_sdl(
+        r#"
+        directive @limit(max_items: Int = 10, user_role: user_role = ADMIN_USER) on FIELD_DEFINITION
+
+        enum user_role { ADMIN_USER GUEST_USER }
+
+        input user_filter {
+            name_prefix: String
+            roles: [user_role!] = [GUEST_USER]
+        }
+
+        type Query {
+            users(
+                role: user_role = ADMIN_USER,
+                filter: user_filter = {name_prefix: "a", roles: [ADMIN_USER]},
+                name: String = "ADMIN_USER",
+            ): [String!]!
+        }
+        "#,
+    )
+    .unwrap();
+
+    registry
+        .normalize_names(|kind, name| match kind {
+            NameKind::EnumValue => Some(name.to_lowercase()),
+            NameKind::InputField => Some(name.replace("_p", "P")),
+            NameKind::Argument => Some(name.to_uppercase()),
+            _ => None,
+        })
+        .unwrap();
+
+    let users = registry.types["Query"].field_by_name("users").unwrap();
+    assert_eq!(
+        users.args["ROLE"].default_value.as_deref(),
+        Some("admin_user")
+    );
+    assert_eq!(
+        users.args["FILTER"].default_value.as_deref(),
+        Some(r#"{namePrefix: "a",roles: [admin_user]}"#)
+    );
+    assert_eq!(
+        users.args["NAME"].default_value.as_deref(),
+        Some(r#""ADMIN_USER""#)
+    );
+    assert_eq!(
+        registry.types["user_filter"]
+            .input_field_by_name("roles")
+            .unwrap()
+            .default_value
+            .as_deref(),
+        Some("[guest_user]")
+    );
+
+    let limit = &registry.directives["limit"];
+    assert_eq!(
+        limit.args.keys().collect::<Vec<_>>(),
+        vec!["MAX_ITEMS", "USER_ROLE"]
+    );
+    assert_eq!(
+        limit.args["USER_ROLE"].default_value.as_deref(),
+        Some("admin_user")
+    );
+    assert_eq!(registry.directives["include"].args["if"].name, "if");
+}
+
+#[test]
+pub fn test_normalize_names_collision() {
+    let build = || {
+        RegistryBuilder::new()
+            .object("Query")
+            .field("user_name", "String")
+            .field("userName", "String")
+            .field("user", "user")
+            .object("user")
+            .field("id", "ID!")
+            .object("User")
+            .field("id", "ID!")
+            .query_type("Query")
+            .build()
+    };
+
+    let mut registry = build();
+    assert_eq!(
+        registry.normalize_names(|kind, name| match kind {
+            NameKind::Field => Some(name.replace("_n", "N")),
+            _ => None,
+        }),
+        Err(RenameError::DuplicateMember {
+            owner: "Query".to_string(),
+            name: "userName".to_string(),
+        })
+    );
+    assert!(registry.types["Query"].field_by_name("user_name").is_some());
+    assert!(registry.types["Query"].field_by_name("userName").is_some());
+
+    let mut registry = build();
+    assert_eq!(
+        registry.normalize_names(|kind, name| match kind {
+            NameKind::Type => Some(name[..1].to_uppercase() + &name[1..]),
+            _ => None,
+        }),
+        Err(RenameError::AlreadyExists("User".to_string()))
+    );
+    assert!(registry.types.contains_key("user"));
+    assert!(registry.types.contains_key("User"));
+}
+
+#[test]
+pub fn test_rename_type() {
+    let mut registry = RegistryBuilder::new()
+        .object("Query")
+        .field("old", "Old")
+        .field("olds", "[Old!]!")
+        .field("oldThing", "OldThing")
+        .field("node", "Node")
+        .field("search", "Search")
+        .interface("Node")
+        .field("id", "ID!")
+        .object("Old")
+        .implements("Node")
+        .field("id", "ID!")
+        .field("parent", "Old")
+        .object("OldThing")
+        .field("id", "ID!")
+        .union("Search", &["Old", "OldThing"])
+        .query_type("Query")
+        .build();
+    if let Some(MetaType::Union {
+        possible_type_tags, ..
+    }) = registry.types.get_mut("Search")
+    {
+        possible_type_tags.insert("Old".to_string(), vec!["old"]);
+        possible_type_tags.insert("OldThing".to_string(), vec!["thing"]);
+    }
+
+    assert_eq!(
+        registry.rename_type("Old", "OldThing"),
+        Err(RenameError::AlreadyExists("OldThing".to_string()))
+    );
+    assert_eq!(
+        registry.rename_type("String", "Text"),
+        Err(RenameError::SystemType("String".to_string()))
+    );
+    assert_eq!(
+        registry.rename_type("__Type", "Type"),
+        Err(RenameError::SystemType("__Type".to_string()))
+    );
+    assert_eq!(
+        registry.rename_type("Unknown", "Known"),
+        Err(RenameError::NotFound("Unknown".to_string()))
+    );
+
+    registry.rename_type("Old", "New").unwrap();
+    assert!(!registry.types.contains_key("Old"));
+    assert_eq!(registry.types["New"].name(), "New");
+    assert_eq!(
+        registry.types["New"].field_by_name("parent").unwrap().ty,
+        "New"
+    );
+
+    let query = &registry.types["Query"];
+    assert_eq!(query.field_by_name("old").unwrap().ty, "New");
+    assert_eq!(query.field_by_name("olds").unwrap().ty, "[New!]!");
+    assert_eq!(query.field_by_name("oldThing").unwrap().ty, "OldThing");
+
+    match &registry.types["Node"] {
+        MetaType::Interface { possible_types, .. } => {
+            assert_eq!(possible_types.iter().collect::<Vec<_>>(), vec!["New"])
+        }
+        _ => unreachable!(),
+    }
+    match &registry.types["Search"] {
+        MetaType::Union {
+            possible_types,
+            possible_type_tags,
+            ..
+        } => {
+            assert_eq!(
+                possible_types.iter().collect::<Vec<_>>(),
+                vec!["New", "OldThing"]
+            );
+            assert_eq!(
+                possible_type_tags.keys().collect::<Vec<_>>(),
+                vec!["New", "OldThing"]
+            );
+            assert_eq!(possible_type_tags["New"], vec!["old"]);
+        }
+        _ => unreachable!(),
+    }
+    assert!(registry.implements["New"].contains("Node"));
+    assert!(!registry.implements.contains_key("Old"));
+
+    registry.rename_type("Query", "QueryRoot").unwrap();
+    assert_eq!(registry.query_type, "QueryRoot");
+    assert_eq!(registry.validate(), Ok(()));
+}
+
+#[test]
+pub fn test_fingerprint() {
+    let build = |widget_name: &str, widget_field: &str| {
+        RegistryBuilder::new()
+            .object("Query")
+            .field("widget", widget_name)
+            .object(widget_name)
+            .field("id", "ID!")
+            .field(widget_field, "[String!]")
+            .arg("first", "Int")
+            .union("Search", &["Query", widget_name])
+            .query_type("Query")
+            .build()
+    };
+
+    let a = build("Widget", "labels");
+    let b = build("Gadget", "labels");
+    let c = build("Widget", "tags");
+
+    let widget = a.types.get("Widget").unwrap();
+    let gadget = b.types.get("Gadget").unwrap();
+    assert!(widget.structurally_eq(gadget));
+    assert_eq!(widget.fingerprint(), gadget.fingerprint());
+    assert_ne!(
+        widget.fingerprint(),
+        c.types.get("Widget").unwrap().fingerprint()
+    );
+    assert_ne!(
+        a.types.get("Search").unwrap().fingerprint(),
+        b.types.get("Search").unwrap().fingerprint()
+    );
+    assert_ne!(
+        widget.fingerprint(),
+        a.types.get("Query").unwrap().fingerprint()
+    );
+
+    // The fingerprint is a specified hash, so it is the same in every build.
+    assert_eq!(
+        a.types.get("Search").unwrap().fingerprint(),
+        0x53ae_cf65_8e81_308f
+    );
+}
+
+#[test]
+pub fn test_iter_types() {
+    let registry = RegistryBuilder::new()
+        .object("Query")
+        .field("user", "User")
+        .object("User")
+        .field("id", "ID!")
+        .field("role", "Role")
+        .enum_type("Role", &["ADMIN", "GUEST"])
+        .query_type("Query")
+        .build();
+
+    assert_eq!(
+        registry
+            .iter_types()
+            .map(|(name, ty)| (name, ty.type_id()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("ID", MetaTypeId::Scalar),
+            ("Query", MetaTypeId::Object),
+            ("Role", MetaTypeId::Enum),
+            ("User", MetaTypeId::Object),
+        ]
+    );
+    assert_eq!(
+        registry
+            .iter_types_filtered(MetaTypeId::Object)
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+        vec!["Query", "User"]
+    );
+    assert_eq!(
+        registry
+            .iter_types_filtered(MetaTypeId::InputObject)
+            .count(),
+        0
+    );
+    assert_eq!(registry.type_count(), 4);
+}
+
+#[test]
+pub fn test_validate_required_deprecations() {
+    #[derive(InputObject)]
+    struct MyInput {
+        #[graphql(deprecation)]
+        a: i32,
+        #[graphql(deprecation, default = 1)]
+        b: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(
+            &self,
+            _input: MyInput,
+            #[graphql(deprecation = "Unused")] _old: i32,
+        ) -> i32 {
+            10
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+
+    assert_eq!(
+        registry
+            .validate_with_options(Default::default())
+            .unwrap()
+            .into_iter()
+            .map(|warning| warning.message)
+            .collect::<Vec<_>>(),
+        vec![
+            "The input field `MyInput.a` is required and should not be deprecated",
+            "The argument `Query.value(old:)` is required and should not be deprecated",
+        ]
+    );
+}
+
+#[test]
+pub fn test_merge() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: ID,
+    }
+
+    struct UsersQuery;
+
+    #[Object]
+    impl UsersQuery {
+        async fn users(&self) -> Vec<User> {
+            todo!()
+        }
+
+        async fn version(&self) -> i32 {
+            1
+        }
+    }
+
+    #[derive(SimpleObject)]
+    struct Product {
+        id: ID,
+        owner: User,
+    }
+
+    struct ProductsQuery;
+
+    #[Object]
+    impl ProductsQuery {
+        async fn products(&self) -> Vec<Product> {
+            todo!()
+        }
+
+        async fn version(&self) -> i32 {
+            1
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn create_product(&self) -> Product {
+            todo!()
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<UsersQuery>();
+
+    let mut other = Registry::default();
+    other.set_query_type::<ProductsQuery>();
+    other.set_mutation_type::<Mutation>();
+
+    registry.merge(other).unwrap();
+    assert_eq!(registry.query_type, "UsersQuery");
+    assert_eq!(registry.mutation_type.as_deref(), Some("Mutation"));
+    assert!(!registry.types.contains_key("ProductsQuery"));
+    assert_eq!(
+        registry
+            .types
+            .get("UsersQuery")
+            .unwrap()
+            .fields()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>(),
+        vec!["users", "version", "products"]
+    );
+    assert!(registry.types.contains_key("Product"));
+    assert_eq!(registry.validate(), Ok(()));
+
+    let conflicting = RegistryBuilder::new()
+        .object("Query")
+        .field("version", "String")
+        .query_type("Query")
+        .build();
+    assert_eq!(
+        registry.merge(conflicting),
+        Err(MergeError::FieldConflict {
+            type_name: "UsersQuery".to_string(),
+            field_name: "version".to_string(),
+        })
+    );
+
+    // A field resolved serially in only one of the registries.
+    struct SerialQuery;
+
+    #[Object]
+    impl SerialQuery {
+        #[graphql(serial)]
+        async fn version(&self) -> i32 {
+            1
+        }
+    }
+
+    let mut conflicting = Registry::default();
+    conflicting.set_query_type::<SerialQuery>();
+    assert_eq!(
+        registry.merge(conflicting),
+        Err(MergeError::FieldConflict {
+            type_name: "UsersQuery".to_string(),
+            field_name: "version".to_string(),
+        })
+    );
+
+    let conflicting = RegistryBuilder::new()
+        .object("Query")
+        .field("user", "User")
+        .input_object("User")
+        .field("id", "ID")
+        .query_type("Query")
+        .build();
+    assert_eq!(
+        registry.merge(conflicting),
+        Err(MergeError::KindMismatch {
+            name: "User".to_string(),
+            existing: MetaTypeId::Object,
+            other: MetaTypeId::InputObject,
+        })
+    );
+    assert_eq!(
+        registry
+            .types
+            .get("UsersQuery")
+            .unwrap()
+            .fields()
+            .unwrap()
+            .len(),
+        3
+    );
+
+    let mut ignored = RegistryBuilder::new()
+        .object("Query")
+        .field("user", "User")
+        .input_object("User")
+        .field("id", "ID")
+        .query_type("Query")
+        .build();
+    ignored.ignore_name_conflicts.insert("User".to_string());
+    registry.merge(ignored).unwrap();
+    assert!(matches!(
+        registry.types.get("User"),
+        Some(MetaType::Object { .. })
+    ));
+}
+
+#[test]
+pub fn test_merge_conflicts() {
+    fn is_admin(_: &Context<'_>) -> bool {
+        true
+    }
+
+    #[derive(SimpleObject)]
+    struct Account {
+        id: ID,
+        #[graphql(visible = "is_admin")]
+        email: String,
+    }
+
+    struct AccountsQuery;
+
+    #[Object]
+    impl AccountsQuery {
+        async fn account(&self) -> Account {
+            todo!()
+        }
+    }
+
+    struct AdminQuery;
+
+    #[Object]
+    impl AdminQuery {
+        async fn accounts(&self) -> Vec<Account> {
+            todo!()
+        }
+    }
+
+    // The same visibility function on both sides is not a conflict.
+    let mut registry = Registry::default();
+    registry.set_query_type::<AccountsQuery>();
+    let mut other = Registry::default();
+    other.set_query_type::<AdminQuery>();
+    registry.merge(other).unwrap();
+    assert!(registry.types["AccountsQuery"]
+        .field_by_name("accounts")
+        .is_some());
+
+    let conflicting = RegistryBuilder::new()
+        .object("Query")
+        .field("account", "Account")
+        .object("Account")
+        .field("id", "ID!")
+        .field("email", "String!")
+        .query_type("Query")
+        .build();
+    assert_eq!(
+        registry.merge(conflicting),
+        Err(MergeError::FieldConflict {
+            type_name: "Account".to_string(),
+            field_name: "email".to_string(),
+        })
+    );
+
+    // The query root type of `other` cannot take the name of another of its
+    // types.
+    let conflicting = RegistryBuilder::new()
+        .object("RootQuery")
+        .field("query", "AccountsQuery")
+        .object("AccountsQuery")
+        .field("id", "ID!")
+        .query_type("RootQuery")
+        .build();
+    assert_eq!(
+        registry.merge(conflicting),
+        Err(MergeError::RootTypeConflict {
+            name: "AccountsQuery".to_string(),
+        })
+    );
+
+    let mut registry = Registry::from_sdl(
+        "directive @cache(ttl: Int) on FIELD_DEFINITION\ntype Query { value: Int }",
+    )
+    .unwrap();
+    let same = Registry::from_sdl(
+        "directive @cache(ttl: Int) on FIELD_DEFINITION\ntype Query { other: Int }",
+    )
+    .unwrap();
+    registry.merge(same).unwrap();
+    let conflicting =
+        Registry::from_sdl("directive @cache(ttl: Int) on OBJECT\ntype Query { value: Int }")
+            .unwrap();
+    assert_eq!(
+        registry.merge(conflicting),
+        Err(MergeError::DirectiveConflict {
+            name: "cache".to_string(),
+        })
+    );
+}
+
+#[test]
+pub fn test_type_name_list_items() {
+    let cases = [
+        ("[[Int!]]!", true, Some(MetaTypeName::List("Int!")), true),
+        ("[Int]", true, Some(MetaTypeName::Named("Int")), true),
+        ("[Int!]!", true, Some(MetaTypeName::NonNull("Int")), false),
+        ("Int!", false, None, false),
+        ("Int", false, None, false),
+    ];
+    for (type_name, is_list, item_type, is_nullable_item) in cases {
+        let ty = MetaTypeName::create(type_name);
+        assert_eq!(ty.is_list(), is_list, "{}", type_name);
+        assert_eq!(ty.list_item_type(), item_type, "{}", type_name);
+        assert_eq!(
+            ty.is_nullable_list_item(),
+            is_nullable_item,
+            "{}",
+            type_name
+        );
+
+        // A wrapped name stored as `Named` behaves like the parsed type.
+        let named = MetaTypeName::Named(type_name);
+        assert_eq!(named.is_list(), is_list, "{}", type_name);
+        assert_eq!(named.list_item_type(), item_type, "{}", type_name);
+    }
+}
+
+#[test]
+pub fn test_validate_type_references() {
+    let mut registry = RegistryBuilder::new()
+        .object("Query")
+        .field("node", "Node")
+        .field("missing", "[Missing!]")
+        .arg("filter", "UnknownInput")
+        .interface("Node")
+        .field("id", "ID!")
+        .field("name", "String")
+        .object("User")
+        .field("id", "ID!")
+        .implements("Node")
+        .union("SearchResult", &["User", "Node"])
+        .query_type("Query")
+        .build();
+    if let Some(MetaType::Interface { possible_types, .. }) = registry.types.get_mut("Node") {
+        possible_types.insert("Ghost".to_string());
+    }
+
+    let errors = registry.validate().unwrap_err();
+    assert_eq!(
+        errors
+            .iter()
+            .map(|err| (
+                err.type_name.as_str(),
+                err.field_name.as_deref(),
+                err.message.as_str()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "SearchResult",
+                None,
+                "The member `Node` of the union `SearchResult` must be an object, but it is a `Interface`"
+            ),
+            (
+                "Node",
+                None,
+                "The possible type `Ghost` of the interface `Node` is not registered"
+            ),
+            (
+                "User",
+                Some("name"),
+                "The field `Node.name` is required by the interface `Node`, but it is not provided by `User`"
+            ),
+            (
+                "Query",
+                Some("missing"),
+                "The field `Query.missing` has the unknown type `[Missing!]`"
+            ),
+            (
+                "Query",
+                Some("missing"),
+                "The argument `Query.missing(filter:)` has the unknown type `UnknownInput`"
+            ),
+        ]
+    );
+}
+
+#[test]
+pub fn test_oneof_inputs() {
+    #[derive(OneofObject)]
+    enum UserBy {
+        Id(ID),
+        Email(String),
+    }
+
+    #[derive(InputObject)]
+    struct Filter {
+        name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self, _by: UserBy, _filter: Filter) -> i32 {
+            todo!()
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+    assert_eq!(registry.oneof_inputs(), vec!["UserBy"]);
+    assert_eq!(registry.validate(), Ok(()));
+
+    let sdl = registry.try_export_sdl(Default::default()).unwrap();
+    assert!(sdl.contains("input UserBy @oneOf {\n\tid: ID\n\temail: String\n}\n"));
+
+    if let Some(MetaType::InputObject { input_fields, .. }) = registry.types.get_mut("UserBy") {
+        input_fields.get_mut("id").unwrap().ty = "ID!".to_string();
+        input_fields.get_mut("email").unwrap().default_value = Some("\"a\"".to_string());
+    }
+    let errors = registry.validate().unwrap_err();
+    assert_eq!(
+        errors
+            .iter()
+            .map(|err| (
+                err.type_name.as_str(),
+                err.field_name.as_deref(),
+                err.message.as_str()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "UserBy",
+                Some("id"),
+                "The field `UserBy.id` of a oneOf input object must be nullable"
+            ),
+            (
+                "UserBy",
+                Some("email"),
+                "The field `UserBy.email` of a oneOf input object must not have a default value"
+            ),
+        ]
+    );
+}
+
+#[test]
+pub fn test_remove_type() {
+    let mut registry = RegistryBuilder::new()
+        .object("Query")
+        .field("user", "User")
+        .arg("filter", "UserFilter")
+        .field("search", "[SearchResult!]!")
+        .interface("Node")
+        .field("id", "ID!")
+        .object("User")
+        .field("id", "ID!")
+        .implements("Node")
+        .object("Post")
+        .field("id", "ID!")
+        .implements("Node")
+        .union("SearchResult", &["User", "Post"])
+        .input_object("UserFilter")
+        .field("name", "String")
+        .input_object("PostFilter")
+        .field("author", "UserFilter")
+        .query_type("Query")
+        .build();
+
+    assert_eq!(
+        registry.remove_type("User"),
+        Ok(vec!["Query.user".to_string(), "SearchResult".to_string()])
+    );
+    assert!(!registry.types.contains_key("User"));
+    assert_eq!(
+        registry.types.get("Node").unwrap().possible_types(),
+        Some(&["Post".to_string()].into_iter().collect())
+    );
+    assert!(!registry.implements.contains_key("User"));
+
+    assert_eq!(
+        registry.remove_type("UserFilter"),
+        Ok(vec![
+            "PostFilter.author".to_string(),
+            "Query.user(filter:)".to_string()
+        ])
+    );
+    assert_eq!(registry.remove_type("Unknown"), Ok(vec![]));
+
+    let err = registry.remove_type("Query").unwrap_err();
+    assert_eq!(err.message, "The query root type `Query` cannot be removed");
+    assert!(registry.types.contains_key("Query"));
+}
+
+#[test]
+pub fn test_interfaces_of() {
+    let registry = RegistryBuilder::new()
+        .interface("Node")
+        .field("id", "ID!")
+        .interface("Named")
+        .field("name", "String!")
+        .object("User")
+        .field("id", "ID!")
+        .field("name", "String!")
+        .implements("Node")
+        .implements("Named")
+        .object("Post")
+        .field("id", "ID!")
+        .implements("Node")
+        .object("Query")
+        .field("node", "Node")
+        .query_type("Query")
+        .build();
+
+    assert_eq!(registry.interfaces_of("User"), vec!["Named", "Node"]);
+    assert_eq!(registry.interfaces_of("Post"), vec!["Node"]);
+    assert!(registry.interfaces_of("Query").is_empty());
+    assert!(registry.interfaces_of("Unknown").is_empty());
+
+    assert_eq!(registry.implementors_of("Node"), vec!["Post", "User"]);
+    assert_eq!(registry.implementors_of("Named"), vec!["User"]);
+    assert!(registry.implementors_of("Unknown").is_empty());
+}
+
+#[test]
+pub fn test_field_path_type() {
+    let registry = RegistryBuilder::new()
+        .object("Query")
+        .field("user", "User")
+        .field("users", "[User!]!")
+        .field("media", "Media")
+        .object("User")
+        .field("address", "Address!")
+        .field("name", "String")
+        .object("Address")
+        .field("zip", "String!")
+        .union("Media", &["User"])
+        .query_type("Query")
+        .build();
+
+    let type_name = |path: &[&str]| registry.field_path_type(path).map(|ty| ty.name());
+    assert_eq!(
+        type_name(&["Query", "user", "address", "zip"]),
+        Some("String")
+    );
+    assert_eq!(type_name(&["Query", "users", "address"]), Some("Address"));
+    assert_eq!(type_name(&["Query", "media"]), Some("Media"));
+    assert_eq!(type_name(&["Query"]), Some("Query"));
+    assert_eq!(type_name(&[]), None);
+    assert_eq!(type_name(&["Unknown"]), None);
+    assert_eq!(type_name(&["Query", "unknown"]), None);
+    assert_eq!(type_name(&["Query", "user", "name", "length"]), None);
+    assert_eq!(type_name(&["Query", "media", "address"]), None);
+}
+
+#[test]
+pub fn test_types_overlap() {
+    let registry = RegistryBuilder::new()
+        .interface("Node")
+        .field("id", "ID!")
+        .interface("Named")
+        .field("name", "String!")
+        .interface("Timestamped")
+        .field("createdAt", "String!")
+        .object("User")
+        .field("id", "ID!")
+        .field("name", "String!")
+        .implements("Node")
+        .implements("Named")
+        .object("Post")
+        .field("id", "ID!")
+        .field("createdAt", "String!")
+        .implements("Node")
+        .implements("Timestamped")
+        .union("Media", &["Post"])
+        .object("Query")
+        .field("node", "Node")
+        .query_type("Query")
+        .build();
+
+    assert!(registry.types_overlap("Named", "Node"));
+    assert!(registry.types_overlap("Node", "Named"));
+    assert!(!registry.types_overlap("Named", "Timestamped"));
+    assert!(registry.types_overlap("Media", "Timestamped"));
+    assert!(!registry.types_overlap("Media", "Named"));
+    assert!(registry.types_overlap("User", "Named"));
+    assert!(!registry.types_overlap("Post", "Named"));
+    assert!(registry.types_overlap("User", "User"));
+    assert!(!registry.types_overlap("User", "Post"));
+    assert!(!registry.types_overlap("User", "Unknown"));
+
+    let user = registry.types["User"].clone();
+    assert!(user.type_overlap(&registry.types["User"]));
+}
+
+#[test]
+pub fn test_field_complexity() {
+    struct Item;
+
+    #[Object]
+    impl Item {
+        #[graphql(complexity = 2)]
+        async fn name(&self) -> String {
+            todo!()
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(complexity = 3)]
+        async fn item(&self) -> Item {
+            todo!()
+        }
+
+        async fn value(&self) -> i32 {
+            todo!()
+        }
+    }
+
+    struct DynamicQuery;
+
+    #[Object]
+    impl DynamicQuery {
+        #[graphql(complexity = "count * child_complexity")]
+        async fn items(&self, count: usize) -> Vec<Item> {
+            let _ = count;
+            todo!()
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+    assert!(matches!(
+        registry.field_complexity("Query", "item"),
+        Some(ComplexityType::Const(3))
+    ));
+    assert!(registry.field_complexity("Query", "value").is_none());
+    assert!(registry.field_complexity("Query", "unknown").is_none());
+    assert!(registry.field_complexity("Unknown", "item").is_none());
+    assert_eq!(registry.max_static_complexity(), Some(5));
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<DynamicQuery>();
+    assert!(matches!(
+        registry.field_complexity("DynamicQuery", "items"),
+        Some(ComplexityType::Fn(_))
+    ));
+    assert_eq!(registry.max_static_complexity(), None);
+}
+
+#[test]
+pub fn test_remove_unused_types_except() {
+    let build = || {
+        RegistryBuilder::new()
+            .object("Dynamic")
+            .field("detail", "Detail")
+            .arg("filter", "Filter")
+            .object("Detail")
+            .field("value", "String")
+            .input_object("Filter")
+            .field("limit", "Int")
+            .object("Unused")
+            .field("value", "String")
+            .object("Query")
+            .field("value", "String")
+            .query_type("Query")
+            .build()
+    };
+
+    let mut registry = build();
+    registry.remove_unused_types();
+    assert!(!registry.types.contains_key("Dynamic"));
+    assert!(!registry.types.contains_key("Detail"));
+
+    let mut registry = build();
+    registry.remove_unused_types_except(&["Dynamic".to_string()].into_iter().collect());
+    for name in ["Query", "Dynamic", "Detail", "Filter", "Int"] {
+        assert!(registry.types.contains_key(name), "{}", name);
+    }
+    assert!(!registry.types.contains_key("Unused"));
+}
+
+#[test]
+pub fn test_diff() {
+    let old = RegistryBuilder::new()
+        .object("Query")
+        .field("user", "User")
+        .arg("id", "ID!")
+        .field("users", "[User]")
+        .arg("first", "Int")
+        .interface("Node")
+        .field("id", "ID!")
+        .object("User")
+        .field("id", "ID!")
+        .field("name", "String")
+        .field("age", "Int")
+        .implements("Node")
+        .object("Dog")
+        .field("name", "String")
+        .object("Cat")
+        .field("name", "String")
+        .union("Pet", &["Dog", "Cat"])
+        .enum_type("Role", &["ADMIN", "USER"])
+        .input_object("UserInput")
+        .field("name", "String!")
+        .field("email", "String")
+        .query_type("Query")
+        .build();
+    assert!(old.diff(&old).is_empty());
+
+    let new = RegistryBuilder::new()
+        .object("Query")
+        .field("user", "User")
+        .arg("id", "ID!")
+        .arg("verbose", "Boolean!")
+        .field("users", "[User!]")
+        .arg("first", "Int")
+        .arg("after", "String")
+        .interface("Node")
+        .field("id", "ID!")
+        .object("User")
+        .field("id", "ID!")
+        .field("name", "String!")
+        .field("email", "String")
+        .object("Dog")
+        .field("name", "String")
+        .object("Cat")
+        .field("name", "String")
+        .object("Bird")
+        .field("name", "String")
+        .union("Pet", &["Dog", "Cat", "Bird"])
+        .enum_type("Role", &["ADMIN", "GUEST"])
+        .input_object("UserInput")
+        .field("name", "String")
+        .field("email", "Int")
+        .field("age", "Int!")
+        .query_type("Query")
+        .build();
+    let diff = old.diff(&new);
+    let messages = |changes: &[Change]| changes.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert!(diff.is_breaking());
+    assert_eq!(
+        messages(&diff.breaking),
+        vec![
+            "Required argument `Query.user(verbose:)` was added",
+            "Enum value `Role.USER` was removed",
+            "`User` no longer implements `Node`",
+            "Field `User.age` was removed",
+            "The type of `UserInput.email` changed from `String` to `Int`",
+            "Required input field `UserInput.age` was added",
+        ]
+    );
+    assert_eq!(
+        messages(&diff.dangerous),
+        vec![
+            "`Bird` was added to the union `Pet`",
+            "Argument `Query.users(after:)` was added",
+            "Enum value `Role.GUEST` was added",
+        ]
+    );
+    assert_eq!(
+        messages(&diff.safe),
+        vec![
+            "The type of `Query.users` changed from `[User]` to `[User!]`",
+            "The type of `User.name` changed from `String` to `String!`",
+            "Field `User.email` was added",
+            "The type of `UserInput.name` changed from `String!` to `String`",
+            "Type `Bird` was added",
+            "Type `Boolean` was added",
+        ]
+    );
+    assert_eq!(diff.breaking[1].location, "Role.USER");
+    assert_eq!(diff.breaking[0].location, "Query.user(verbose:)");
+}
+
+#[test]
+pub fn test_assert_no_breaking_changes_from() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            0
+        }
+
+        async fn name(&self) -> String {
+            todo!()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+    assert_eq!(
+        registry.assert_no_breaking_changes_from(&schema.sdl()),
+        Ok(())
+    );
+
+    // Adding a field is safe.
+    let old_sdl = "type Query { value: Int! }";
+    assert_eq!(registry.assert_no_breaking_changes_from(old_sdl), Ok(()));
+
+    // Removing a field is breaking.
+    let old_sdl = "type Query { value: Int! removed: String }";
+    assert_eq!(
+        registry.assert_no_breaking_changes_from(old_sdl),
+        Err(vec![Change {
+            location: "Query.removed".to_string(),
+            message: "Field `Query.removed` was removed".to_string(),
+        }])
+    );
+
+    // An invalid old SDL is reported as a single breaking change.
+    let changes = registry
+        .assert_no_breaking_changes_from("type Query {")
+        .unwrap_err();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].location, "schema");
+    assert!(changes[0]
+        .message
+        .starts_with("The old SDL cannot be loaded: "));
+}
+
+#[test]
+pub fn test_effective_field_cache_control() {
+    let mut registry = RegistryBuilder::new()
+        .object("Query")
+        .field("node", "Node")
+        .interface("Node")
+        .field("id", "ID!")
+        .field("name", "String")
+        .interface("Named")
+        .field("name", "String")
+        .object("User")
+        .field("id", "ID!")
+        .field("name", "String")
+        .field("age", "Int")
+        .implements("Node")
+        .implements("Named")
+        .query_type("Query")
+        .build();
+    let mut set_cache_control = |type_name: &str, field_name: &str, cache_control| {
+        if let Some(MetaType::Object { fields, .. } | MetaType::Interface { fields, .. }) =
+            registry.types.get_mut(type_name)
+        {
+            fields.get_mut(field_name).unwrap().cache_control = cache_control;
+        }
+    };
+    set_cache_control(
+        "User",
+        "name",
+        CacheControl {
+            public: true,
+            max_age: 60,
+        },
+    );
+    set_cache_control(
+        "Node",
+        "name",
+        CacheControl {
+            public: true,
+            max_age: 30,
+        },
+    );
+    set_cache_control(
+        "Named",
+        "name",
+        CacheControl {
+            public: false,
+            max_age: 0,
+        },
+    );
+    set_cache_control(
+        "User",
+        "age",
+        CacheControl {
+            public: true,
+            max_age: 10,
+        },
+    );
+
+    assert_eq!(
+        registry.effective_field_cache_control("User", "name"),
+        CacheControl {
+            public: false,
+            max_age: 30,
+        }
+    );
+    assert_eq!(
+        registry.effective_field_cache_control("User", "age"),
+        CacheControl {
+            public: true,
+            max_age: 10,
+        }
+    );
+    assert_eq!(
+        registry.effective_field_cache_control("User", "id"),
+        CacheControl::default()
+    );
+    assert_eq!(
+        registry.effective_field_cache_control("User", "missing"),
+        CacheControl::default()
+    );
+
+    let sdl = registry
+        .try_export_sdl(SDLExportOptions::new().include_cache_control())
+        .unwrap();
+    assert!(sdl.contains("\tname: String @cacheControl(maxAge: 30, scope: PRIVATE)\n"));
+    assert!(sdl.contains("\tage: Int @cacheControl(maxAge: 10)\n"));
+}
+
+#[test]
+pub fn test_possible_types_closure() {
+    let registry = RegistryBuilder::new()
+        .object("Query")
+        .field("node", "Node")
+        .interface("Node")
+        .field("id", "ID!")
+        .interface("Resource")
+        .field("id", "ID!")
+        .implements("Node")
+        .interface("Image")
+        .field("id", "ID!")
+        .implements("Resource")
+        .object("Photo")
+        .field("id", "ID!")
+        .implements("Image")
+        .object("Document")
+        .field("id", "ID!")
+        .implements("Resource")
+        .object("User")
+        .field("id", "ID!")
+        .implements("Node")
+        .union("SearchResult", &["User", "Photo"])
+        .query_type("Query")
+        .build();
+    let set = |names: &[&str]| {
+        names
+            .iter()
+            .map(ToString::to_string)
+            .collect::<indexmap::IndexSet<_>>()
+    };
+
+    assert_eq!(
+        registry.possible_types_closure("Node"),
+        set(&["Document", "Photo", "User"])
+    );
+    assert_eq!(
+        registry.possible_types_closure("Resource"),
+        set(&["Document", "Photo"])
+    );
+    assert_eq!(registry.possible_types_closure("Image"), set(&["Photo"]));
+    assert_eq!(
+        registry.possible_types_closure("SearchResult"),
+        set(&["User", "Photo"])
+    );
+    assert!(registry.possible_types_closure("User").is_empty());
+    assert!(registry.possible_types_closure("Missing").is_empty());
+}
+
+#[test]
+pub fn test_enum_values() {
+    let registry = RegistryBuilder::new()
+        .object("Query")
+        .field("role", "Role")
+        .enum_type("Role", &["ADMIN", "USER"])
+        .query_type("Query")
+        .build();
+
+    assert_eq!(
+        registry
+            .enum_values("Role")
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>(),
+        vec!["ADMIN", "USER"]
+    );
+    assert!(registry.types["Role"].enum_values().is_some());
+    assert!(registry.enum_values("Query").is_none());
+    assert!(registry.enum_values("Missing").is_none());
+
+    assert_eq!(registry.enum_value("Role", "USER").unwrap().name, "USER");
+    assert!(registry.enum_value("Role", "GUEST").is_none());
+    assert!(registry.enum_value("Query", "role").is_none());
+}
+
+#[test]
+pub fn test_union_members_and_interface_fields() {
+    let registry = Registry::from_sdl(
+        r#"
+        interface Node {
+            id: ID!
+        }
+
+        type User implements Node {
+            id: ID!
+            name: String!
+        }
+
+        type Post implements Node {
+            id: ID!
+        }
+
+        union SearchResult = User | Post
+
+        type Query {
+            search: [SearchResult!]!
+            node: Node
+        }
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        registry.union_members("SearchResult"),
+        Some(vec!["Post", "User"])
+    );
+    assert!(registry.union_members("Node").is_none());
+    assert!(registry.union_members("Missing").is_none());
+
+    assert_eq!(
+        registry
+            .interface_fields("Node")
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>(),
+        vec!["id"]
+    );
+    assert!(registry.interface_fields("User").is_none());
+    assert!(registry.interface_fields("Missing").is_none());
+}
+
+#[test]
+pub fn test_field_arguments() {
+    #[derive(InputObject)]
+    struct Filter {
+        name: String,
+        tags: Vec<String>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn users(
+            &self,
+            #[graphql(default = 10)] first: i32,
+            #[graphql(default_with = r#"Filter { name: "a\"b".to_string(), tags: vec![] }"#)]
+            filter: Filter,
+            after: Option<String>,
+        ) -> Vec<String> {
+            let _ = (first, filter, after);
+            todo!()
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+
+    let args = registry.field_arguments("Query", "users").unwrap();
+    assert_eq!(
+        args.keys().collect::<Vec<_>>(),
+        vec!["first", "filter", "after"]
+    );
+    assert_eq!(args["first"].parse_default().unwrap().unwrap(), value!(10));
+    assert_eq!(
+        args["filter"].parse_default().unwrap().unwrap(),
+        value!({ "name": "a\"b", "tags": [] })
+    );
+    assert!(args["after"].parse_default().is_none());
+    assert!(registry.field_arguments("Query", "missing").is_none());
+    assert!(registry.field_arguments("Missing", "users").is_none());
+
+    let mut arg = args["first"].clone();
+    arg.default_value = Some("{ first: }".to_string());
+    assert!(arg.parse_default().unwrap().is_err());
+}
+
+#[test]
+pub fn test_set_field_description() {
+    /// A color
+    #[derive(Enum, Copy, Clone, Eq, PartialEq)]
+    enum Color {
+        Red,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn color(&self) -> Color {
+            Color::Red
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+    assert_eq!(registry.types["Color"].description(), Some("A color"));
+    assert_eq!(registry.types["Query"].description(), None);
+
+    registry.set_field_description("Query", "color", "Returns the color");
+    assert_eq!(
+        registry.types["Query"]
+            .field_by_name("color")
+            .unwrap()
+            .description
+            .as_deref(),
+        Some("Returns the color")
+    );
+
+    registry.set_field_description("Query", "missing", "Missing");
+    registry.set_field_description("Color", "RED", "Red");
+    registry.set_field_description("Missing", "color", "Missing");
+    assert!(registry.types["Query"].field_by_name("missing").is_none());
+    assert_eq!(
+        registry.enum_value("Color", "RED").unwrap().description,
+        None
+    );
+    assert!(!registry.types.contains_key("Missing"));
+}
+
+#[test]
+pub fn test_directives() {
+    struct Noop;
+
+    #[async_trait::async_trait]
+    impl CustomDirective for Noop {}
+
+    #[Directive(location = "field")]
+    fn zebra() -> impl CustomDirective {
+        Noop
+    }
+
+    #[Directive(location = "field")]
+    fn apple() -> impl CustomDirective {
+        Noop
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+    zebra.register(&mut registry);
+    apple.register(&mut registry);
+
+    assert_eq!(registry.directive("zebra").unwrap().name, "zebra");
+    assert!(registry.directive("missing").is_none());
+    assert_eq!(
+        registry
+            .directives()
+            .map(|directive| directive.name)
+            .collect::<Vec<_>>(),
+        vec!["apple", "zebra"]
+    );
+}
+
+#[test]
+pub fn test_name_conflicts() {
+    #[derive(SimpleObject)]
+    #[graphql(name = "MyObj")]
+    struct MyObj {
+        name: String,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(name = "MyObj")]
+    struct OtherObj {
+        name: String,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(name = "Ignored")]
+    struct Ignored {
+        name: String,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(name = "Ignored")]
+    struct OtherIgnored {
+        name: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn obj(&self) -> MyObj {
+            todo!()
+        }
+
+        async fn other_obj(&self) -> OtherObj {
+            todo!()
+        }
+
+        async fn ignored(&self) -> Ignored {
+            todo!()
+        }
+
+        async fn other_ignored(&self) -> OtherIgnored {
+            todo!()
+        }
+    }
+
+    let mut registry = Registry {
+        collect_name_conflicts: true,
+        ..Default::default()
+    };
+    registry.ignore_name_conflicts.insert("Ignored".to_string());
+    registry.set_query_type::<Query>();
+
+    assert_eq!(
+        registry.name_conflicts(),
+        vec![(
+            "MyObj".to_string(),
+            vec![
+                std::any::type_name::<MyObj>().to_string(),
+                std::any::type_name::<OtherObj>().to_string(),
+            ]
+        )]
+    );
+
+    let names = registry.names();
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted);
+    assert!(names.contains(&"otherObj".to_string()));
+}
+
+#[test]
+pub fn test_fields_mut() {
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&i32"))]
+    enum Node {
+        User(User),
+    }
+
+    #[derive(SimpleObject)]
+    struct User {
+        id: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn node(&self) -> Node {
+            todo!()
+        }
+    }
+
+    let mut registry = Registry::default();
+    registry.set_query_type::<Query>();
+
+    for name in ["Node", "User"] {
+        let fields = registry.types.get_mut(name).unwrap().fields_mut().unwrap();
+        fields.get_mut("id").unwrap().cache_control.max_age = 60;
+        fields.insert("extra".to_string(), MetaField::new("extra", "String"));
+    }
+    assert!(registry
+        .types
+        .get_mut("Int")
+        .unwrap()
+        .fields_mut()
+        .is_none());
+
+    for name in ["Node", "User"] {
+        let ty = &registry.types[name];
+        assert_eq!(ty.field_by_name("id").unwrap().cache_control.max_age, 60);
+        assert_eq!(ty.field_by_name("extra").unwrap().ty, "String");
+    }
+}
+
+#[test]
+pub fn test_from_sdl() {
+    let registry = Registry::from_sdl(
+        r#"
+        schema {
+            query: Root
+        }
+
+        directive @audit(level: Int = 1) on FIELD_DEFINITION | OBJECT
+        directive @label(name: String!) repeatable on OBJECT
+
+        "A node"
+        interface Node {
+            id: ID!
+        }
+
+        type Root {
+            node(id: ID!): Node
+            oldField: String @deprecated(reason: "Use `node`")
+        }
+
+        type User implements Node @key(fields: "id") @audit {
+            id: ID!
+            name(upper: Boolean = false): String! @audit(level: 2)
+        }
+
+        extend type User {
+            email: String @external
+        }
+
+        extend type Product @key(fields: "upc", resolvable: false) {
+            upc: String!
+        }
+
+        enum Color {
+            RED
+            GREEN
+        }
+
+        extend enum Color {
+            BLUE
+        }
+
+        input Filter @oneOf {
+            name: String
+            id: ID
+        }
+
+        scalar DateTime @specifiedBy(url: "https://example.com/datetime")
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(registry.query_type, "Root");
+    assert_eq!(registry.mutation_type, None);
+    assert!(registry.types.contains_key("__Schema"));
+    assert!(registry.types.contains_key("Boolean"));
+    assert!(registry.directive("skip").is_some());
+
+    let audit = registry.directive("audit").unwrap();
+    assert_eq!(audit.args["level"].default_value.as_deref(), Some("1"));
+    assert_eq!(audit.locations.len(), 2);
+    assert!(!audit.is_repeatable);
+    assert!(registry.directive("label").unwrap().is_repeatable);
+
+    let node = &registry.types["Node"];
+    assert_eq!(node.description(), Some("A node"));
+    match node {
+        MetaType::Interface { possible_types, .. } => {
+            assert!(possible_types.contains("User"));
+        }
+        _ => panic!("`Node` should be an interface"),
+    }
+
+    let root = &registry.types["Root"];
+    assert_eq!(root.field_by_name("node").unwrap().args["id"].ty, "ID!");
+    assert!(root
+        .field_by_name("oldField")
+        .unwrap()
+        .deprecation
+        .is_deprecated());
+
+    let user = &registry.types["User"];
+    assert_eq!(user.keys(), Some(&[MetaKey::new("id")][..]));
+    assert!(user.field_by_name("email").unwrap().external);
+    let name = user.field_by_name("name").unwrap();
+    assert_eq!(name.ty, "String!");
+    assert_eq!(name.args["upper"].default_value.as_deref(), Some("false"));
+    assert_eq!(name.directive_invocations[0].name, "audit");
+    assert!(matches!(user, MetaType::Object { extends: false, .. }));
+    assert!(registry
+        .implements
+        .get("User")
+        .map_or(false, |interfaces| interfaces.contains("Node")));
+
+    let product = &registry.types["Product"];
+    assert!(matches!(product, MetaType::Object { extends: true, .. }));
+    assert!(!product.keys().unwrap()[0].resolvable);
+
+    match &registry.types["Color"] {
+        MetaType::Enum { enum_values, .. } => {
+            assert_eq!(
+                enum_values.keys().collect::<Vec<_>>(),
+                vec!["RED", "GREEN", "BLUE"]
+            );
+        }
+        _ => panic!("`Color` should be an enum"),
+    }
+    assert!(matches!(
+        registry.types["Filter"],
+        MetaType::InputObject { oneof: true, .. }
+    ));
+    assert!(matches!(
+        registry.types["DateTime"],
+        MetaType::Scalar {
+            specified_by_url: Some("https://example.com/datetime"),
+            ..
+        }
+    ));
+
+    assert_eq!(
+        Registry::from_sdl("type Query { a: Int } type Query { b: Int }").err(),
+        Some(FromSdlError::DuplicateType("Query".to_string()))
+    );
+    assert_eq!(
+        Registry::from_sdl("directive @a on FIELD directive @a on OBJECT type Query { a: Int }")
+            .err(),
+        Some(FromSdlError::DuplicateDirective("a".to_string()))
+    );
+    assert_eq!(
+        Registry::from_sdl("type Query { a: Int } extend input Query { b: Int }")
+            .err()
+            .map(|err| err.to_string())
+            .as_deref(),
+        Some("The type `Query` is a `Object`, but it is extended as a `InputObject`")
+    );
+    assert!(matches!(
+        Registry::from_sdl("type Query {"),
+        Err(FromSdlError::Parse(_))
+    ));
+}
+
+#[test]
+pub fn test_from_sdl_round_trip() {
+    let options = SDLExportOptions::new().include_directives();
+    let sdl = Registry::from_sdl(
+        r#"
+        directive @label(name: String!) repeatable on OBJECT | FIELD_DEFINITION
+
+        type Query @label(name: "a") @label(name: "b") {
+            values(first: Int = 10): [String!]! @label(name: "c")
+        }
+        "#,
+    )
+    .unwrap()
+    .try_export_sdl(options)
+    .unwrap();
+    assert!(sdl.contains("repeatable on"));
+
+    let registry = Registry::from_sdl(&sdl).unwrap();
+    assert!(registry.directive("label").unwrap().is_repeatable);
+    assert_eq!(registry.try_export_sdl(options).unwrap(), sdl);
+}
+
+#[test]
+pub fn test_field_and_directive_args() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            0
+        }
+
+        async fn add(&self, a: i32, #[graphql(default = 1)] b: i32) -> i32 {
+            a + b
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let registry = Registry::from_sdl(&schema.sdl()).unwrap();
+
+    let query = &registry.types["Query"];
+    let value = query.field_by_name("value").unwrap();
+    assert!(!value.has_args());
+    assert!(value.arg("a").is_none());
+
+    let add = query.field_by_name("add").unwrap();
+    assert!(add.has_args());
+    assert_eq!(add.arg("a").unwrap().ty, "Int!");
+    assert_eq!(add.arg("b").unwrap().default_value.as_deref(), Some("1"));
+    assert!(add.arg("c").is_none());
+
+    let skip = registry.directive("skip").unwrap();
+    assert_eq!(skip.arg("if").unwrap().ty, "Boolean!");
+    assert!(skip.arg("unless").is_none());
+}
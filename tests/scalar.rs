@@ -46,6 +46,56 @@ pub async fn test_scalar_macro() {
     );
 }
 
+#[tokio::test]
+pub async fn test_scalar_specified_by_url() {
+    struct DateTime(String);
+
+    #[Scalar(specified_by_url = r#"https://example.com/spec?format="rfc3339"\date"#)]
+    impl ScalarType for DateTime {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            match value {
+                Value::String(s) => Ok(DateTime(s)),
+                _ => Err(InputValueError::expected_type(value)),
+            }
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.0.clone())
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn now(&self) -> DateTime {
+            DateTime("2000-01-01T00:00:00Z".to_string())
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "DateTime") { specifiedByURL } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "__type": {
+                "specifiedByURL": r#"https://example.com/spec?format="rfc3339"\date"#,
+            }
+        })
+    );
+
+    assert!(schema
+        .sdl_with_options(SDLExportOptions::new().include_specified_by())
+        .contains(
+            r#"scalar DateTime @specifiedBy(url: "https://example.com/spec?format=\"rfc3339\"\\date")"#
+        ));
+    assert!(schema.sdl().contains("scalar DateTime\n"));
+}
+
 #[tokio::test]
 pub async fn test_float_inf() {
     struct Query;
@@ -440,3 +440,543 @@ pub fn test_macro_generated_union() {
 
     let _ = MyEnum::Val(IntObj { val: 1 });
 }
+
+#[tokio::test]
+pub async fn test_union_member_name() {
+    #[derive(SimpleObject)]
+    struct Animal {
+        name: String,
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(name = "Dog")]
+    struct DogInfo {
+        name: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct Cat {
+        lives: i32,
+    }
+
+    #[derive(Union)]
+    enum Pet {
+        #[graphql(name = "Dog")]
+        Dog(Animal),
+        Cat(Cat),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn pet(&self) -> Pet {
+            Pet::Dog(Animal {
+                name: "Rex".to_string(),
+            })
+        }
+
+        async fn dog(&self) -> Option<DogInfo> {
+            None
+        }
+    }
+
+    let pet = Pet::Dog(Animal {
+        name: "Rex".to_string(),
+    });
+    assert_eq!(pet.introspection_type_name(), "Dog");
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema.sdl().contains("union Pet = Dog | Cat\n"));
+
+    let mut registry = registry::Registry::default();
+    registry.set_query_type::<Query>();
+    assert_eq!(registry.validate(), Ok(()));
+
+    struct OtherQuery;
+
+    #[Object]
+    #[allow(unreachable_code)]
+    impl OtherQuery {
+        async fn pet(&self) -> Pet {
+            todo!()
+        }
+    }
+
+    let mut registry = registry::Registry::default();
+    registry.set_query_type::<OtherQuery>();
+    assert_eq!(
+        registry
+            .validate()
+            .unwrap_err()
+            .into_iter()
+            .map(|err| err.message)
+            .collect::<Vec<_>>(),
+        vec!["The member `Dog` of the union `Pet` is not registered".to_string()]
+    );
+}
+
+#[test]
+#[should_panic(expected = "The member `Cat` is used by more than one variant of the union `Pet`")]
+pub fn test_union_member_name_conflict() {
+    #[derive(SimpleObject)]
+    struct Animal {
+        name: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct Cat {
+        lives: i32,
+    }
+
+    #[derive(Union)]
+    enum Pet {
+        #[graphql(name = "Cat")]
+        Dog(Animal),
+        Cat(Cat),
+    }
+
+    let mut registry = registry::Registry::default();
+    <Pet as OutputType>::create_type_info(&mut registry);
+}
+
+#[test]
+#[should_panic(
+    expected = "The possible type `MyObj2` of the union `MyUnion` is contributed by both the flattened variants `Inner1` and `Inner2`"
+)]
+pub fn test_union_flatten_conflict() {
+    #[derive(SimpleObject)]
+    struct MyObj1 {
+        value1: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct MyObj2 {
+        value2: i32,
+    }
+
+    #[derive(Union)]
+    enum InnerUnion1 {
+        A(MyObj1),
+        B(MyObj2),
+    }
+
+    #[derive(Union)]
+    enum InnerUnion2 {
+        B(MyObj2),
+    }
+
+    #[derive(Union)]
+    enum MyUnion {
+        #[graphql(flatten)]
+        Inner1(InnerUnion1),
+
+        #[graphql(flatten)]
+        Inner2(InnerUnion2),
+    }
+
+    let mut registry = registry::Registry::default();
+    <MyUnion as OutputType>::create_type_info(&mut registry);
+}
+
+#[tokio::test]
+pub async fn test_generic_union() {
+    #[derive(SimpleObject)]
+    struct Leaf {
+        value: i32,
+    }
+
+    #[derive(Union)]
+    enum Node<T: Send + Sync>
+    where
+        T: Clone,
+    {
+        Leaf(Leaf),
+        Branch(T),
+    }
+
+    #[derive(SimpleObject, Clone)]
+    #[graphql(name = "Apple2")]
+    struct AppleBranch {
+        color: String,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn leaf(&self) -> Node<AppleBranch> {
+            Leaf { value: 1 }.into()
+        }
+
+        async fn branch(&self) -> Node<AppleBranch> {
+            Node::Branch(AppleBranch {
+                color: "red".to_string(),
+            })
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema.sdl().contains("union Node = Leaf | Apple2\n"));
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    leaf { ... on Leaf { value } }
+                    branch { ... on Apple2 { color } }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "leaf": { "value": 1 },
+            "branch": { "color": "red" },
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_union_member_tags() {
+    #[derive(SimpleObject)]
+    #[graphql(tag = "public")]
+    struct A {
+        a: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct B {
+        b: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct C {
+        c: i32,
+    }
+
+    #[derive(Union)]
+    enum Inner {
+        B(B),
+        #[graphql(tag = "inner")]
+        C(C),
+    }
+
+    #[derive(Union)]
+    enum MyUnion {
+        #[graphql(tag = "internal", tag = "public")]
+        A(A),
+        #[graphql(flatten, tag = "flattened")]
+        Inner(Inner),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> MyUnion {
+            MyUnion::A(A { a: 1 })
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let sdl = schema.sdl_with_options(SDLExportOptions::new().federation());
+    assert!(sdl.contains(r#"type A @tag(name: "public") @tag(name: "internal") {"#));
+    assert!(sdl.contains(r#"type B @tag(name: "flattened") {"#));
+    assert!(sdl.contains(r#"type C @tag(name: "inner") @tag(name: "flattened") {"#));
+    assert!(sdl.contains("union MyUnion = A | B | C\n"));
+    assert!(schema.sdl().contains("type A {"));
+}
+
+#[tokio::test]
+pub async fn test_union_flatten_interface() {
+    #[derive(SimpleObject)]
+    struct Dog {
+        name: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct Cat {
+        name: String,
+        lives: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct Rock {
+        weight: i32,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "name", type = "&String"))]
+    enum Pet {
+        Dog(Dog),
+        Cat(Cat),
+    }
+
+    #[derive(Union)]
+    enum Thing {
+        #[graphql(flatten)]
+        Pet(Pet),
+        Rock(Rock),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn things(&self) -> Vec<Thing> {
+            vec![
+                Thing::Pet(Pet::Cat(Cat {
+                    name: "Tom".to_string(),
+                    lives: 9,
+                })),
+                Thing::Rock(Rock { weight: 10 }),
+                Thing::Pet(Pet::Dog(Dog {
+                    name: "Rex".to_string(),
+                })),
+            ]
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema.sdl().contains("union Thing = Dog | Cat | Rock\n"));
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    things {
+                        __typename
+                        ... on Dog { name }
+                        ... on Cat { name lives }
+                        ... on Rock { weight }
+                    }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "things": [
+                { "__typename": "Cat", "name": "Tom", "lives": 9 },
+                { "__typename": "Rock", "weight": 10 },
+                { "__typename": "Dog", "name": "Rex" },
+            ]
+        })
+    );
+}
+
+#[tokio::test]
+pub async fn test_union_flatten_boxed_interface() {
+    #[derive(SimpleObject)]
+    struct Dog {
+        name: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct Rock {
+        weight: i32,
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "name", type = "&String"))]
+    enum Pet {
+        Dog(Dog),
+    }
+
+    #[derive(Union)]
+    enum Thing {
+        #[graphql(flatten)]
+        Pet(Box<Pet>),
+        Rock(Rock),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn things(&self) -> Vec<Thing> {
+            vec![
+                Thing::Pet(Box::new(Pet::Dog(Dog {
+                    name: "Rex".to_string(),
+                }))),
+                Thing::Rock(Rock { weight: 10 }),
+            ]
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema.sdl().contains("union Thing = Dog | Rock\n"));
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    things {
+                        __typename
+                        ... on Dog { name }
+                        ... on Rock { weight }
+                    }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "things": [
+                { "__typename": "Dog", "name": "Rex" },
+                { "__typename": "Rock", "weight": 10 },
+            ]
+        })
+    );
+}
+
+#[test]
+pub fn test_union_try_from_member() {
+    #[derive(SimpleObject, Debug, PartialEq)]
+    struct Dog {
+        name: String,
+    }
+
+    #[derive(SimpleObject, Debug, PartialEq)]
+    struct Cat {
+        lives: i32,
+    }
+
+    #[derive(SimpleObject, Debug, PartialEq)]
+    struct Rock {
+        weight: i32,
+    }
+
+    #[derive(Union)]
+    enum Pet {
+        Dog(Dog),
+        Cat(Cat),
+    }
+
+    #[derive(Union)]
+    enum Thing {
+        #[graphql(flatten)]
+        Pet(Pet),
+        Rock(Rock),
+    }
+
+    #[derive(Union)]
+    enum Wrapper<T: Send + Sync> {
+        Dog(Dog),
+        Other(T),
+    }
+
+    let dog = Pet::Dog(Dog {
+        name: "Rex".to_string(),
+    });
+    assert_eq!(
+        <&Dog>::try_from(&dog).ok(),
+        Some(&Dog {
+            name: "Rex".to_string()
+        })
+    );
+    assert!(<&Cat>::try_from(&dog).is_err());
+
+    let rock = Thing::Rock(Rock { weight: 10 });
+    assert_eq!(<&Rock>::try_from(&rock).ok(), Some(&Rock { weight: 10 }));
+    let pet = Thing::Pet(Pet::Cat(Cat { lives: 9 }));
+    assert!(<&Rock>::try_from(&pet).is_err());
+
+    let wrapper = Wrapper::<Rock>::Dog(Dog {
+        name: "Rex".to_string(),
+    });
+    assert!(<&Dog>::try_from(&wrapper).is_ok());
+    let wrapper = Wrapper::Other(Rock { weight: 10 });
+    assert!(<&Dog>::try_from(&wrapper).is_err());
+}
+
+#[tokio::test]
+pub async fn test_union_flatten_typename() {
+    #[derive(SimpleObject)]
+    struct MyObj1 {
+        value1: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct MyObj2 {
+        value2: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct MyObj3 {
+        value3: i32,
+    }
+
+    #[derive(Union)]
+    enum Innermost {
+        A(MyObj1),
+        B(MyObj2),
+    }
+
+    #[derive(Union)]
+    enum Middle {
+        #[graphql(flatten)]
+        Innermost(Innermost),
+        C(MyObj3),
+    }
+
+    #[derive(Union)]
+    enum Outer {
+        #[graphql(flatten)]
+        Middle(Middle),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn values(&self) -> Vec<Outer> {
+            vec![
+                Middle::from(Innermost::A(MyObj1 { value1: 1 })).into(),
+                Middle::from(Innermost::B(MyObj2 { value2: 2 })).into(),
+                Middle::C(MyObj3 { value3: 3 }).into(),
+            ]
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ values { __typename } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        value!({
+            "values": [
+                { "__typename": "MyObj1" },
+                { "__typename": "MyObj2" },
+                { "__typename": "MyObj3" },
+            ]
+        })
+    );
+
+    let query = r#"
+    {
+        values {
+            ... on Outer {
+                __typename
+            }
+            ... on MyObj2 {
+                __typename
+                value2
+            }
+        }
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        value!({
+            "values": [
+                { "__typename": "MyObj1" },
+                { "__typename": "MyObj2", "value2": 2 },
+                { "__typename": "MyObj3" },
+            ]
+        })
+    );
+}